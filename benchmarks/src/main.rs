@@ -34,6 +34,9 @@ fn collect_benchmarks() -> Vec<Benchmark> {
     if let Some(sse2) = Backend::x86_sse2() {
         backends.push(("sse2", sse2));
     }
+    if let Some(ssse3) = Backend::x86_ssse3() {
+        backends.push(("ssse3", ssse3));
+    }
     if let Some(avx2) = Backend::x86_avx2() {
         backends.push(("avx2", avx2));
     }
@@ -43,6 +46,9 @@ fn collect_benchmarks() -> Vec<Benchmark> {
     if let Some(simd128) = Backend::wasm32_simd128() {
         backends.push(("simd128", simd128));
     }
+    if let Some(portable_simd) = Backend::portable_simd() {
+        backends.push(("portable_simd", portable_simd));
+    }
 
     let mut benchmarks = Vec::new();
 
@@ -67,6 +73,18 @@ fn collect_benchmarks() -> Vec<Benchmark> {
         benchmarks.push(bench_bulk_rand_chacha(vec![0; read_size]));
     }
 
+    // Converting straight out of the buffer should beat a `read_u64` loop.
+    benchmarks.push(bench_f64_unit_loop());
+    benchmarks.push(bench_f64_unit_fill());
+
+    // Skipping whole iterations only computes their last quarter, so it should beat reading.
+    benchmarks.push(bench_skip_via_read());
+    benchmarks.push(bench_skip_bytes());
+
+    // Deriving a small key from every seed is where `expand_seeds` should shine.
+    benchmarks.push(bench_key_from_new());
+    benchmarks.push(bench_key_from_expand_seeds());
+
     benchmarks
 }
 
@@ -186,6 +204,99 @@ fn bench_bulk(backend_name: &str, backend: Backend, mut dest: Vec<u8>) -> Benchm
     }
 }
 
+fn bench_f64_unit_loop() -> Benchmark {
+    let mut dest = vec![0.0; 1000];
+    Benchmark {
+        label: "f64unit1000/loop".to_string(),
+        work: Box::new(move |n| {
+            let mut rng = ChaCha8Rand::new(SEED);
+            for _ in 0..n {
+                for x in &mut dest {
+                    *x = (rng.read_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64);
+                }
+                black_box(&mut dest);
+            }
+        }),
+    }
+}
+
+fn bench_f64_unit_fill() -> Benchmark {
+    let mut dest = vec![0.0; 1000];
+    Benchmark {
+        label: "f64unit1000/fill".to_string(),
+        work: Box::new(move |n| {
+            let mut rng = ChaCha8Rand::new(SEED);
+            for _ in 0..n {
+                rng.fill_f64_unit(&mut dest);
+                black_box(&mut dest);
+            }
+        }),
+    }
+}
+
+// Large enough that almost all of it is whole iterations.
+const SKIP_LEN: usize = 100 * 992;
+
+fn bench_skip_via_read() -> Benchmark {
+    let mut scratch = vec![0; 992];
+    Benchmark {
+        label: format!("skip{SKIP_LEN}/read"),
+        work: Box::new(move |n| {
+            let mut rng = ChaCha8Rand::new(SEED);
+            for _ in 0..n {
+                for _ in 0..SKIP_LEN / scratch.len() {
+                    rng.read_bytes(&mut scratch);
+                }
+                black_box(&mut rng);
+            }
+        }),
+    }
+}
+
+fn bench_skip_bytes() -> Benchmark {
+    Benchmark {
+        label: format!("skip{SKIP_LEN}/skip_bytes"),
+        work: Box::new(move |n| {
+            let mut rng = ChaCha8Rand::new(SEED);
+            for _ in 0..n {
+                rng.skip_bytes(black_box(SKIP_LEN as u64));
+                black_box(&mut rng);
+            }
+        }),
+    }
+}
+
+fn bench_key_from_new() -> Benchmark {
+    Benchmark {
+        label: "key32/new".to_string(),
+        work: Box::new(move |n| {
+            for _ in 0..n {
+                let mut key = [0; 32];
+                ChaCha8Rand::new(black_box(SEED)).read_bytes(&mut key);
+                black_box(&mut key);
+            }
+        }),
+    }
+}
+
+fn bench_key_from_expand_seeds() -> Benchmark {
+    Benchmark {
+        label: "key32/expand_seeds".to_string(),
+        work: Box::new(move |n| {
+            // Expand seeds in batches like real users would, so seeds can be processed in pairs.
+            let seeds = [*SEED; 64];
+            let mut keys = [[0; 32]; 64];
+            let mut remaining = n as usize;
+            while remaining > 0 {
+                let batch = remaining.min(seeds.len());
+                ChaCha8Rand::expand_seeds(black_box(&seeds[..batch]), &mut keys[..batch]);
+                black_box(&mut keys);
+                remaining -= batch;
+            }
+        }),
+    }
+}
+
 fn bench_bulk_rand_chacha(mut dest: Vec<u8>) -> Benchmark {
     let label = format!("bulk{n}/rand_chacha", n = dest.len());
     Benchmark {
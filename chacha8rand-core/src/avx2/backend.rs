@@ -0,0 +1,183 @@
+use core::mem::MaybeUninit;
+
+use crate::{
+    avx2::safe_arch::{__m256i, Avx2},
+    backend::Impls,
+    common_guts::{init_state, rounds},
+    Backend, Buffer,
+};
+use arrayref::{array_mut_ref, mut_array_refs};
+
+static IMPLS: Impls = Impls::new(by_rounds!(fill_buf), by_rounds!(fill_group))
+    .with_fill_group_pair(by_rounds!(fill_group_pair));
+
+pub(crate) fn detect() -> Option<Backend> {
+    if std::is_x86_feature_detected!("avx2") {
+        // SAFETY: `fill_buf`, `fill_group`, and `fill_group_pair` are only unsafe because they
+        // enable the AVX2 `target_feature`, and we've ensured that AVX2 is available, so they're
+        // now effectively safe functions.
+        unsafe { Some(Backend::new_unchecked(&IMPLS)) }
+    } else {
+        None
+    }
+}
+
+/// # Safety
+///
+/// Requires AVX2 target feature. No other safety requirements.
+#[target_feature(enable = "avx2")]
+pub unsafe fn fill_buf<const ROUNDS: usize>(
+    key: &[u32; 8],
+    stream: u64,
+    buf: &mut MaybeUninit<Buffer>,
+) {
+    // Since we're already inside a function with `target_feature(enable = "avx2)`, the `expect` is
+    // too late to prevent UB. But there is still a chance that it panics if that UB is triggered,
+    // and the check is basically free compared to the work we're doing below, so it doesn't hurt to
+    // use `expect` here.
+    let avx2 = Avx2::new().expect("AVX2 must be available if this backend is invoked");
+
+    let buf = Buffer::bytes_uninit(buf);
+    let mut ctr = avx2.elems([0, 1, 2, 3, 4, 5, 6, 7]);
+    let splat = |x| avx2.splat(x);
+
+    for eight_blocks in 0..2 {
+        let mut x = init_state(ctr, key, stream, splat);
+
+        rounds::<ROUNDS, _>(
+            &mut x,
+            #[inline(always)]
+            |abcd| quarter_round(avx2, abcd),
+        );
+
+        for i in 4..12 {
+            x[i] = avx2.add_u32(x[i], splat(key[i - 4]));
+        }
+
+        let out: &mut [MaybeUninit<u8>; 512] = array_mut_ref![buf, eight_blocks * 512, 512];
+        let (out_lo, out_hi) = mut_array_refs![out, 256, 256];
+        for (i, &xi) in x.iter().enumerate() {
+            let dest_lo: &mut [MaybeUninit<u8>; 16] = array_mut_ref![out_lo, i * 16, 16];
+            let dest_hi: &mut [MaybeUninit<u8>; 16] = array_mut_ref![out_hi, i * 16, 16];
+            avx2.storeu2(xi, dest_hi, dest_lo);
+        }
+
+        ctr = avx2.add_u32(ctr, splat(8));
+    }
+}
+
+/// # Safety
+///
+/// Requires AVX2 target feature. No other safety requirements.
+#[target_feature(enable = "avx2")]
+pub unsafe fn fill_group<const ROUNDS: usize>(
+    key: &[u32; 8],
+    stream: u64,
+    group: u32,
+    group_buf: &mut [MaybeUninit<u8>; 256],
+) {
+    // See `fill_buf` for why `expect` is used here.
+    let avx2 = Avx2::new().expect("AVX2 must be available if this backend is invoked");
+
+    // We only need four blocks, but the code is written for eight at a time. Rather than writing a
+    // 128-bit version of everything, we compute the same four blocks in both halves and throw the
+    // upper half away. This wastes half the work, but it's still plenty fast for the rare cases
+    // where only a single group is needed.
+    let base = 4 * group;
+    let ctr = avx2.elems([0, 1, 2, 3, 0, 1, 2, 3].map(|i| base + i));
+    let splat = |x| avx2.splat(x);
+    let mut x = init_state(ctr, key, stream, splat);
+
+    rounds::<ROUNDS, _>(
+        &mut x,
+        #[inline(always)]
+        |abcd| quarter_round(avx2, abcd),
+    );
+
+    for i in 4..12 {
+        x[i] = avx2.add_u32(x[i], splat(key[i - 4]));
+    }
+
+    let mut discard = [MaybeUninit::uninit(); 16];
+    for (i, &xi) in x.iter().enumerate() {
+        avx2.storeu2(xi, &mut discard, array_mut_ref![group_buf, i * 16, 16]);
+    }
+}
+
+/// # Safety
+///
+/// Requires AVX2 target feature. No other safety requirements.
+#[target_feature(enable = "avx2")]
+pub unsafe fn fill_group_pair<const ROUNDS: usize>(
+    keys: [&[u32; 8]; 2],
+    stream: u64,
+    group: u32,
+    [out_lo, out_hi]: [&mut [MaybeUninit<u8>; 256]; 2],
+) {
+    // See `fill_buf` for why `expect` is used here.
+    let avx2 = Avx2::new().expect("AVX2 must be available if this backend is invoked");
+
+    // This is `fill_group` with the first key in the lower half and the second key in the upper
+    // half, instead of the same key (and the same blocks) in both halves.
+    let base = 4 * group;
+    let ctr = avx2.elems([0, 1, 2, 3, 0, 1, 2, 3].map(|i| base + i));
+    let splat = |x| avx2.splat(x);
+    let key_words: [__m256i; 8] = core::array::from_fn(|i| {
+        let (lo, hi) = (keys[0][i], keys[1][i]);
+        avx2.elems([lo, lo, lo, lo, hi, hi, hi, hi])
+    });
+    let mut x = init_state(ctr, keys[0], stream, splat);
+    x[4..12].copy_from_slice(&key_words);
+
+    rounds::<ROUNDS, _>(
+        &mut x,
+        #[inline(always)]
+        |abcd| quarter_round(avx2, abcd),
+    );
+
+    for i in 4..12 {
+        x[i] = avx2.add_u32(x[i], key_words[i - 4]);
+    }
+
+    for (i, &xi) in x.iter().enumerate() {
+        avx2.storeu2(
+            xi,
+            array_mut_ref![out_hi, i * 16, 16],
+            array_mut_ref![out_lo, i * 16, 16],
+        );
+    }
+}
+
+#[inline(always)]
+fn quarter_round(avx2: Avx2, [mut a, mut b, mut c, mut d]: [__m256i; 4]) -> [__m256i; 4] {
+    a = avx2.add_u32(a, b);
+    d = avx2.xor(d, a);
+    d = rotl::<16, 16>(avx2, d);
+
+    c = avx2.add_u32(c, d);
+    b = avx2.xor(b, c);
+    b = rotl::<12, 20>(avx2, b);
+
+    a = avx2.add_u32(a, b);
+    d = avx2.xor(d, a);
+    d = rotl::<8, 24>(avx2, d);
+
+    c = avx2.add_u32(c, d);
+    b = avx2.xor(b, c);
+    b = rotl::<7, 25>(avx2, b);
+
+    [a, b, c, d]
+}
+
+#[inline(always)]
+fn rotl<const SH_LEFT: i32, const SH_RIGHT: i32>(avx2: Avx2, x: __m256i) -> __m256i {
+    // Note: some of these rotations can be implemented as shuffles, but LLVM manages to figure that
+    // out by itself, so there's no need to complicate the code.
+    const {
+        assert!(SH_RIGHT == (32 - SH_LEFT));
+    }
+    avx2.xor(
+        avx2.shift_left_u32::<SH_LEFT>(x),
+        avx2.shift_right_u32::<SH_RIGHT>(x),
+    )
+}
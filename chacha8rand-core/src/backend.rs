@@ -0,0 +1,289 @@
+use core::{fmt, mem::MaybeUninit};
+
+use arrayref::array_ref;
+
+use crate::Buffer;
+
+// Safety invariant: `impls` only contains functions that are safe to call. Either because they're
+// actually safe functions, or because they only require certain target features that were checked
+// to be available at runtime. In addition, the functions must initialize *every* byte of their
+// output, because we use them to overwrite buffers that are assumed to be initialized.
+//
+// (The runtime detection case is the whole reason why they're `unsafe` fns to begin with.)
+/// One implementation of the ChaCha8Rand block function, e.g., with a certain SIMD instruction set.
+///
+/// All backends produce exactly the same output, they only differ in performance and in which
+/// platforms they're available on. Every `Backend` value is guaranteed to be usable on the
+/// current CPU, which is why you can only get one from the constructors that check this.
+///
+/// The one exception is [`Backend::with_stream`], which deliberately deviates from the spec.
+///
+/// Besides the eight rounds of ChaCha8Rand, every backend also supports 12 and 20 rounds with the
+/// same iteration structure, via methods like [`Backend::refill_with_rounds`]. Those aren't part
+/// of the spec either.
+#[derive(Clone, Copy)]
+pub struct Backend {
+    // The functions for all numbers of rounds live in one `static` per backend, so that a
+    // `Backend` (and every generator holding one) stays two words large.
+    impls: &'static Impls,
+    stream: u64,
+}
+
+type RefillFn = unsafe fn(&[u32; 8], u64, &mut MaybeUninit<Buffer>);
+type FillGroupFn = unsafe fn(&[u32; 8], u64, u32, &mut [MaybeUninit<u8>; 256]);
+type FillGroupPairFn = unsafe fn([&[u32; 8]; 2], u64, u32, [&mut [MaybeUninit<u8>; 256]; 2]);
+type SafeRefillFn = fn(&[u32; 8], u64, &mut MaybeUninit<Buffer>);
+type SafeFillGroupFn = fn(&[u32; 8], u64, u32, &mut [MaybeUninit<u8>; 256]);
+
+/// The functions of one backend, with one entry per number of rounds in the order of
+/// `by_rounds!`. Building a table is harmless, only turning it into a [`Backend`] is `unsafe`.
+pub(crate) struct Impls {
+    refill: [RefillFn; 3],
+    fill_group: [FillGroupFn; 3],
+    /// Only set for backends that can compute groups for two keys faster than one after the other.
+    fill_group_pair: Option<[FillGroupPairFn; 3]>,
+}
+
+impl Impls {
+    #[allow(
+        dead_code,
+        reason = "only used on targets with runtime feature detection"
+    )]
+    pub(crate) const fn new(refill: [RefillFn; 3], fill_group: [FillGroupFn; 3]) -> Self {
+        Impls {
+            refill,
+            fill_group,
+            fill_group_pair: None,
+        }
+    }
+
+    /// Add functions that compute the same group for two keys at once, see
+    /// [`Backend::fill_group_pair`].
+    #[allow(
+        dead_code,
+        reason = "only used on targets with runtime feature detection"
+    )]
+    pub(crate) const fn with_fill_group_pair(self, fill_group_pair: [FillGroupPairFn; 3]) -> Self {
+        Impls {
+            fill_group_pair: Some(fill_group_pair),
+            ..self
+        }
+    }
+}
+
+/// A table of functions that are all safe to call, so it can become a [`Backend`] without any
+/// checks.
+pub(crate) struct SafeImpls(Impls);
+
+impl SafeImpls {
+    pub(crate) const fn new(refill: [SafeRefillFn; 3], fill_group: [SafeFillGroupFn; 3]) -> Self {
+        SafeImpls(Impls {
+            refill: [refill[0], refill[1], refill[2]],
+            fill_group: [fill_group[0], fill_group[1], fill_group[2]],
+            fill_group_pair: None,
+        })
+    }
+}
+
+/// Where the functions for `ROUNDS` rounds are in the arrays created by `by_rounds!`. Fails to
+/// compile for unsupported numbers of rounds.
+const fn rounds_index<const ROUNDS: usize>() -> usize {
+    match ROUNDS {
+        8 => 0,
+        12 => 1,
+        20 => 2,
+        _ => panic!("only 8, 12, and 20 rounds are supported"),
+    }
+}
+
+impl Backend {
+    pub(crate) fn new(impls: &'static SafeImpls) -> Self {
+        // SAFETY: all functions are safe, so they're always safe to call.
+        Backend {
+            impls: &impls.0,
+            stream: 0,
+        }
+    }
+
+    /// Create a backend from functions gated by dynamic feature detection.
+    ///
+    /// ## Safety
+    ///
+    /// The functions in `impls` must be safe to call, as if they were ordinary `fn(...)`s without
+    /// `unsafe` qualifier. For the intended use case of runtime `target_feature` detection, that
+    /// means the functions must be completely safe *except* for requiring certain target features
+    /// to be available, and those target features are in fact available.
+    #[allow(
+        dead_code,
+        reason = "only used on targets with runtime feature detection"
+    )]
+    pub(crate) unsafe fn new_unchecked(impls: &'static Impls) -> Self {
+        // SAFETY: precondition passed on to the caller.
+        Backend { impls, stream: 0 }
+    }
+
+    /// The same backend, but computing the output of another stream for the same keys.
+    ///
+    /// **This is not part of the ChaCha8Rand spec.** The spec leaves three words of the ChaCha8
+    /// block's state (the "nonce") at zero. This puts the little-endian `stream` into the first
+    /// two of them, so every stream ID gives a different block function and different keys give
+    /// different output within each stream. Stream zero is exactly the spec's stream, and all
+    /// other streams are unrelated to it and to each other: they're what ChaCha8 computes for
+    /// different nonces, so no stream can ever overlap with another one.
+    ///
+    /// Everything else, including [`Buffer::new_key`] and the iteration structure, stays the
+    /// same. So a generator that only ever uses one backend value and starts with the same key
+    /// produces one of `2^64` separate streams. Other implementations of ChaCha8Rand, like the one
+    /// in Go's standard library, can only produce stream zero.
+    pub fn with_stream(self, stream: u64) -> Self {
+        Backend { stream, ..self }
+    }
+
+    /// The stream this backend computes, see [`Backend::with_stream`]. Zero unless changed.
+    pub fn stream(self) -> u64 {
+        self.stream
+    }
+
+    /// Compute one full iteration of ChaCha8Rand output for the given key.
+    ///
+    /// Afterwards, [`Buffer::output`] holds the 992 bytes of output and [`Buffer::new_key`] holds
+    /// the key for the next iteration.
+    #[inline]
+    pub fn refill(self, key: &[u32; 8], buf: &mut Buffer) {
+        self.refill_with_rounds::<8>(key, buf);
+    }
+
+    /// Like [`Backend::refill`], but for a buffer that may not have been initialized yet. This
+    /// saves the time it would take to zero it first.
+    #[inline]
+    pub fn refill_uninit<'a>(
+        self,
+        key: &[u32; 8],
+        buf: &'a mut MaybeUninit<Buffer>,
+    ) -> &'a mut Buffer {
+        self.refill_uninit_with_rounds::<8>(key, buf)
+    }
+
+    /// Compute only one of the four 256-byte groups that make up a full buffer.
+    ///
+    /// Every group is made up of four ChaCha8 blocks (block counters `4 * group..4 * group + 4`)
+    /// with their words interleaved, exactly as they appear in the full buffer. This is useful
+    /// when only a part of an iteration's output is needed, e.g., only the new key at the very end.
+    ///
+    /// The result for `group` is always the same as bytes `256 * group..256 * (group + 1)` of
+    /// the [`Buffer`] filled by [`Backend::refill`]. `group` must be less than four.
+    #[inline]
+    pub fn fill_group(self, key: &[u32; 8], group: u32, out: &mut [u8; 256]) {
+        self.fill_group_with_rounds::<8>(key, group, out);
+    }
+
+    /// Compute the same group (see [`Backend::fill_group`]) for two keys.
+    ///
+    /// The result is exactly the same as calling [`Backend::fill_group`] for each key, but some
+    /// backends can do both at once. Currently that's the AVX2 backend, whose vectors have room for
+    /// eight blocks but `fill_group` only needs four, so this is about twice as fast as two
+    /// separate calls. Other backends just make two separate calls.
+    #[inline]
+    pub fn fill_group_pair(self, keys: [&[u32; 8]; 2], group: u32, out: [&mut [u8; 256]; 2]) {
+        self.fill_group_pair_with_rounds::<8>(keys, group, out);
+    }
+
+    /// Compute only the key for the next iteration, i.e., what [`Buffer::new_key`] would return
+    /// after a [`Backend::refill`] with the same key.
+    ///
+    /// The new key is at the very end of the buffer, so this only computes the last group of four
+    /// blocks (see [`Backend::fill_group`]) instead of all sixteen. That's useful for skipping over
+    /// whole iterations whose output isn't needed.
+    #[inline]
+    pub fn next_key(self, key: &[u32; 8]) -> [u8; 32] {
+        self.next_key_with_rounds::<8>(key)
+    }
+
+    /// Like [`Backend::refill`], but with `ROUNDS` ChaCha rounds per block instead of eight.
+    ///
+    /// `ROUNDS` must be 8, 12, or 20, anything else fails to compile. Everything except the number
+    /// of rounds works exactly like ChaCha8Rand, but only eight rounds are covered by the spec.
+    #[inline]
+    pub fn refill_with_rounds<const ROUNDS: usize>(self, key: &[u32; 8], buf: &mut Buffer) {
+        // SAFETY: `MaybeUninit<Buffer>` has the same layout as `Buffer`. The refill function
+        // initializes the entire buffer (see type invariant), so it's still initialized afterwards.
+        let buf = unsafe { &mut *(buf as *mut Buffer).cast::<MaybeUninit<Buffer>>() };
+        self.refill_uninit_with_rounds::<ROUNDS>(key, buf);
+    }
+
+    /// Like [`Backend::refill_uninit`], but with `ROUNDS` rounds, see
+    /// [`Backend::refill_with_rounds`].
+    #[inline]
+    pub fn refill_uninit_with_rounds<'a, const ROUNDS: usize>(
+        self,
+        key: &[u32; 8],
+        buf: &'a mut MaybeUninit<Buffer>,
+    ) -> &'a mut Buffer {
+        let refill_impl = self.impls.refill[const { rounds_index::<ROUNDS>() }];
+        // SAFETY: function is safe to call because that's literally what this type's invariant
+        // states.
+        unsafe { refill_impl(key, self.stream, buf) };
+        // SAFETY: the refill function initialized the whole buffer (see type invariant).
+        unsafe { buf.assume_init_mut() }
+    }
+
+    /// Like [`Backend::fill_group`], but with `ROUNDS` rounds, see
+    /// [`Backend::refill_with_rounds`].
+    #[inline]
+    pub fn fill_group_with_rounds<const ROUNDS: usize>(
+        self,
+        key: &[u32; 8],
+        group: u32,
+        out: &mut [u8; 256],
+    ) {
+        assert!(group < 4, "there are only four groups per iteration");
+        let fill_group_impl = self.impls.fill_group[const { rounds_index::<ROUNDS>() }];
+        // SAFETY: same layout, and the function initializes all of `out` (see type invariant), so
+        // it's still initialized afterwards.
+        let out = unsafe { &mut *(out as *mut [u8; 256]).cast::<[MaybeUninit<u8>; 256]>() };
+        // SAFETY: function is safe to call because that's literally what this type's invariant
+        // states.
+        unsafe { fill_group_impl(key, self.stream, group, out) }
+    }
+
+    /// Like [`Backend::fill_group_pair`], but with `ROUNDS` rounds, see
+    /// [`Backend::refill_with_rounds`].
+    #[inline]
+    pub fn fill_group_pair_with_rounds<const ROUNDS: usize>(
+        self,
+        keys: [&[u32; 8]; 2],
+        group: u32,
+        out: [&mut [u8; 256]; 2],
+    ) {
+        let Some(fill_group_pair_impls) = self.impls.fill_group_pair else {
+            let [out0, out1] = out;
+            self.fill_group_with_rounds::<ROUNDS>(keys[0], group, out0);
+            self.fill_group_with_rounds::<ROUNDS>(keys[1], group, out1);
+            return;
+        };
+        assert!(group < 4, "there are only four groups per iteration");
+        let fill_group_pair_impl = fill_group_pair_impls[const { rounds_index::<ROUNDS>() }];
+        // SAFETY: same layout, and the function initializes all of both outputs (see type
+        // invariant), so they're still initialized afterwards.
+        let out = out
+            .map(|out| unsafe { &mut *(out as *mut [u8; 256]).cast::<[MaybeUninit<u8>; 256]>() });
+        // SAFETY: function is safe to call because that's literally what this type's invariant
+        // states.
+        unsafe { fill_group_pair_impl(keys, self.stream, group, out) }
+    }
+
+    /// Like [`Backend::next_key`], but with `ROUNDS` rounds, see
+    /// [`Backend::refill_with_rounds`].
+    #[inline]
+    pub fn next_key_with_rounds<const ROUNDS: usize>(self, key: &[u32; 8]) -> [u8; 32] {
+        let mut last_group = [0; 256];
+        self.fill_group_with_rounds::<ROUNDS>(key, 3, &mut last_group);
+        *array_ref![last_group, 256 - 32, 32]
+    }
+}
+
+impl fmt::Debug for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Backend { .. }")
+    }
+}
@@ -1,27 +1,37 @@
 // The constant words in the first row of the initial state
-const C0: u32 = u32::from_le_bytes(*b"expa");
-const C1: u32 = u32::from_le_bytes(*b"nd 3");
-const C2: u32 = u32::from_le_bytes(*b"2-by");
-const C3: u32 = u32::from_le_bytes(*b"te k");
+pub(crate) const C0: u32 = u32::from_le_bytes(*b"expa");
+pub(crate) const C1: u32 = u32::from_le_bytes(*b"nd 3");
+pub(crate) const C2: u32 = u32::from_le_bytes(*b"2-by");
+pub(crate) const C3: u32 = u32::from_le_bytes(*b"te k");
 
-pub(crate) fn init_state<T: Copy>(ctr: T, key: &[u32; 8], splat: impl Fn(u32) -> T) -> [T; 16] {
+// The stream ID goes into state words 13 and 14, which the spec leaves at zero. Stream zero is the
+// spec's stream, see `Backend::with_stream`.
+//
+// This must be inline(always) for the same reasons as `rounds` (see below). Without it, LLVM
+// sometimes outlines it once there are several callers with the same `splat`, which costs the AVX2
+// backend about 20% of its throughput.
+#[inline(always)]
+pub(crate) fn init_state<T: Copy>(
+    ctr: T,
+    key: &[u32; 8],
+    stream: u64,
+    splat: impl Fn(u32) -> T,
+) -> [T; 16] {
+    let (stream_lo, stream_hi) = (stream as u32, (stream >> 32) as u32);
     #[rustfmt::skip]
     let x = [
         splat(C0),     splat(C1),     splat(C2),     splat(C3),
         splat(key[0]), splat(key[1]), splat(key[2]), splat(key[3]),
         splat(key[4]), splat(key[5]), splat(key[6]), splat(key[7]),
-        ctr,           splat(0),      splat(0),      splat(0)
+        ctr,           splat(stream_lo), splat(stream_hi), splat(0)
     ];
     x
 }
 
 // NB: if `qr` is a closure and dynamic feature detection is involved, that closure really needs to
-// be inline(always) so it gets inlined and we get reasonable codegen. (Luckily, `init_state`
-// doesn't seem to have the same problem with `splat`. Maybe because splatting is comparatively
-// trivial and called less often.)
+// be inline(always) so it gets inlined and we get reasonable codegen.
 #[inline(always)]
-pub(crate) fn eight_rounds<T: Copy>(x: &mut [T; 16], qr: impl Fn([T; 4]) -> [T; 4]) {
-    const ROUNDS: u32 = 8;
+pub(crate) fn rounds<const ROUNDS: usize, T: Copy>(x: &mut [T; 16], qr: impl Fn([T; 4]) -> [T; 4]) {
     for _ in (0..ROUNDS).step_by(2) {
         // Odd round: columns
         [x[0], x[4], x[8], x[12]] = qr([x[0], x[4], x[8], x[12]]);
@@ -0,0 +1,319 @@
+//! The block function of the [ChaCha8Rand][spec] generator, with SIMD backends.
+//!
+//! This crate contains the part of the [`chacha8rand`][chacha8rand] crate that turns a 32-byte key
+//! into one *iteration* of output: 992 bytes of pseudorandom data plus the 32-byte key for the next
+//! iteration. That's the part where all the `unsafe` code, SIMD intrinsics and runtime feature
+//! detection lives. Everything else (buffering, reading integers, saving and restoring state, ...)
+//! is in `chacha8rand`, which you should use unless you want to build your own generator on top of
+//! the same verified cores, e.g., with a different buffering policy.
+//!
+//! The two building blocks are [`Backend`], which picks an implementation for the current CPU, and
+//! [`Buffer`], which holds the output of one iteration.
+//!
+//! # Crate Features
+//!
+//! * **`std`**: enables runtime detection of `target_feature`s, which is currently used for the
+//!   AVX2 and SSSE3 backends on x86 and x86_64. The `std` feature of `chacha8rand` enables this
+//!   one.
+//! * **`zeroize`**: implements `Zeroize` for [`Buffer`]. The `zeroize` feature of `chacha8rand`
+//!   enables this one.
+//! * **`unstable_portable_simd`**: adds a backend written with `core::simd`, which is used on
+//!   targets that don't have a hand-written SIMD backend. This requires a nightly compiler and
+//!   isn't covered by SemVer, since `core::simd` itself is unstable. The `unstable_portable_simd`
+//!   feature of `chacha8rand` enables this one.
+//!
+//! [chacha8rand]: https://crates.io/crates/chacha8rand
+//! [spec]: https://c2sp.org/chacha8rand
+#![forbid(unsafe_op_in_unsafe_fn)]
+#![warn(missing_docs)]
+#![no_std]
+#![cfg_attr(feature = "unstable_portable_simd", feature(portable_simd))]
+use core::{fmt, mem::MaybeUninit, ptr::addr_of_mut};
+
+// See the comment on the `std` feature of the chacha8rand crate for why this isn't conditional on
+// the target.
+#[cfg(feature = "std")]
+extern crate std;
+
+/// The instances of a backend function that's generic over the number of rounds, for each of the
+/// supported numbers of rounds, in the order expected by `Backend`.
+macro_rules! by_rounds {
+    ($f:ident) => {
+        [$f::<8>, $f::<12>, $f::<20>]
+    };
+}
+
+mod backend;
+mod common_guts;
+mod scalar;
+#[cfg(test)]
+mod tests;
+
+pub use backend::Backend;
+
+const BUF_TOTAL_LEN: usize = 1024;
+const BUF_OUTPUT_LEN: usize = BUF_TOTAL_LEN - 32;
+
+/// The output of one ChaCha8Rand iteration: 992 bytes of output followed by a 32-byte key.
+///
+/// Use [`Backend::refill`] to fill it, then consume [`Buffer::output`] and feed
+/// [`Buffer::new_key`] into the next refill.
+///
+/// # Layout
+///
+/// The contents are exactly what the [spec] calls one iteration: sixteen ChaCha8 blocks with
+/// counters 0 to 15, where the first four state words of each block are left out of the
+/// feed-forward addition. They're arranged in four groups of 256 bytes, with group `g` holding the
+/// blocks with counters `4 * g` to `4 * g + 3`. Within a group, the blocks are interleaved word by
+/// word: bytes `16 * i + 4 * b..16 * i + 4 * b + 4` of the group hold word `i` of the group's block
+/// `b`, in little-endian byte order. This layout is what makes it cheap to compute four blocks at
+/// once with 128-bit SIMD. The first 992 bytes are the output of the iteration and the last 32
+/// bytes (the final two words of the four blocks in the last group) are the key for the next
+/// iteration.
+///
+/// # Examples
+///
+/// A minimal generator that produces the spec's output stream in chunks of 992 bytes:
+///
+/// ```
+/// use chacha8rand_core::{Backend, Buffer};
+///
+/// fn key_words(bytes: &[u8; 32]) -> [u32; 8] {
+///     core::array::from_fn(|i| u32::from_le_bytes(bytes[4 * i..][..4].try_into().unwrap()))
+/// }
+///
+/// let backend = Backend::detect_best();
+/// let mut key = key_words(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// let mut buf = Buffer::new();
+/// for _ in 0..3 {
+///     backend.refill(&key, &mut buf);
+///     // ... consume buf.output() however you like ...
+///     key = key_words(buf.new_key());
+/// }
+/// ```
+///
+/// [spec]: https://c2sp.org/chacha8rand
+// None of the backends currently require this alignment for soundness, but SIMD memory accesses
+// that cross 32- or 64-byte boundaries are slightly slower on a bunch of CPUs, so higher alignment
+// is occasionally useful. Since we don't do 512-bit SIMD, 32-byte alignment is sufficient.
+#[repr(align(32))]
+#[derive(Clone)]
+pub struct Buffer {
+    bytes: [u8; BUF_TOTAL_LEN],
+}
+
+impl Buffer {
+    /// The total size of the buffer in bytes.
+    pub const LEN: usize = BUF_TOTAL_LEN;
+
+    /// The number of output bytes in every iteration.
+    pub const OUTPUT_LEN: usize = BUF_OUTPUT_LEN;
+
+    /// Create a buffer filled with zeros, ready to be filled by [`Backend::refill`].
+    pub const fn new() -> Self {
+        Buffer {
+            bytes: [0; BUF_TOTAL_LEN],
+        }
+    }
+
+    /// Create a buffer that holds no output yet, only `key` as the key for the next iteration.
+    ///
+    /// The output bytes are all zero and meaningless, but a generator that treats them as already
+    /// consumed can later fill the buffer by calling [`Backend::refill`] with `key`, just like it
+    /// would with the key of any other iteration. This makes it possible to defer the first refill
+    /// after seeding until the first output is needed.
+    pub const fn with_new_key(key: &[u8; 32]) -> Self {
+        let mut bytes = [0; BUF_TOTAL_LEN];
+        let mut i = 0;
+        while i < key.len() {
+            bytes[BUF_OUTPUT_LEN + i] = key[i];
+            i += 1;
+        }
+        Buffer { bytes }
+    }
+
+    /// Compute one iteration for `key` in a way that also works in `const` contexts.
+    ///
+    /// The result is the same as filling a buffer with [`Backend::refill`], but it uses a plain
+    /// scalar implementation that's much slower than the backends. Use it for baking output into
+    /// `const` and `static` items at compile time, not at runtime.
+    pub const fn compute_const(key: &[u32; 8]) -> Self {
+        Buffer {
+            bytes: scalar::fill_buf_const(key),
+        }
+    }
+
+    /// The bytes of a buffer that's about to be (re-)filled, for the backends to write into.
+    #[inline(always)]
+    fn bytes_uninit(buf: &mut MaybeUninit<Buffer>) -> &mut [MaybeUninit<u8>; BUF_TOTAL_LEN] {
+        // SAFETY: the pointer is derived from a mutable reference to the (possibly uninitialized)
+        // buffer, so it's valid and properly aligned for `[u8; BUF_TOTAL_LEN]`, which has the same
+        // layout as `[MaybeUninit<u8>; BUF_TOTAL_LEN]`.
+        unsafe { &mut *addr_of_mut!((*buf.as_mut_ptr()).bytes).cast() }
+    }
+
+    /// The output bytes of the iteration.
+    #[inline]
+    pub const fn output(&self) -> &[u8; BUF_OUTPUT_LEN] {
+        match self.bytes.first_chunk() {
+            Some(output) => output,
+            None => unreachable!(),
+        }
+    }
+
+    /// The key for the next iteration.
+    #[inline]
+    pub const fn new_key(&self) -> &[u8; 32] {
+        match self.bytes.last_chunk() {
+            Some(new_key) => new_key,
+            None => unreachable!(),
+        }
+    }
+}
+
+impl Default for Buffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Overwrites the whole buffer with zeros. Requires crate feature `zeroize`.
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Buffer {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.bytes);
+    }
+}
+
+impl fmt::Debug for Buffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Buffer { .. }")
+    }
+}
+
+macro_rules! arch_backends {
+    ($(#[cfg($cond:meta)] mod $name:ident;)+) => {
+        $(
+            #[cfg($cond)]
+            mod $name {
+                mod safe_arch;
+                mod backend;
+                pub(crate) use backend::detect;
+            }
+
+            #[cfg(not($cond))]
+            mod $name {
+                pub fn detect() -> Option<crate::Backend> {
+                    None
+                }
+            }
+        )+
+    };
+}
+
+arch_backends! {
+    // This backend uses dynamic feature detection, so it's disabled in no_std mode and only gated
+    // on `target_arch`. In theory it could also be enabled in no_std mode when AVX2 is statically
+    // enabled, but that would probably complicate some unsafe code which seems like a bad trade.
+    #[cfg(all(any(target_arch = "x86_64", target_arch = "x86"), feature = "std"))]
+    mod avx2;
+
+    // Same as for AVX2. SSSE3 is almost universal on x86_64 CPUs that are still in use, but it's
+    // not part of the x86_64 baseline, so it's rarely enabled at compile time.
+    #[cfg(all(any(target_arch = "x86_64", target_arch = "x86"), feature = "std"))]
+    mod ssse3;
+
+    // For SSE2 we don't bother with dynamic feature detection. x86_64 basically always has it, it's
+    // also very commonly enabled on 32-bit targets, and when it isn't, we still have a very high
+    // chance that AVX2 is available at runtime.
+    #[cfg(all(any(target_arch = "x86_64", target_arch = "x86"), target_feature = "sse2"))]
+    mod sse2;
+
+    // The neon backend is limited to little-endian because the core::arch intrinsics currently
+    // don't work on aarch64be (https://github.com/rust-lang/stdarch/issues/1484). Even if they
+    // worked, it's a pretty obscure target and difficult to test for (e.g., `cross` doesn't
+    // currently support it) so I'm inclined to leave this out until someone champions it.
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon", target_endian = "little"))]
+    mod neon;
+
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    mod simd128;
+}
+
+// Not an `arch_backends!` module because there's no `safe_arch` to wrap: `core::simd` is already
+// safe and works on every target.
+#[cfg(feature = "unstable_portable_simd")]
+mod portable_simd;
+
+#[cfg(not(feature = "unstable_portable_simd"))]
+mod portable_simd {
+    pub fn detect() -> Option<crate::Backend> {
+        None
+    }
+}
+
+// The constructors are not in the `backend` module to minimize the code that has to worry about
+// upholding `Backend`'s invariant.
+impl Backend {
+    /// The fastest backend available on the current CPU.
+    pub fn detect_best() -> Self {
+        // On x86, we prefer AVX2 over SSSE3 over SSE2. The other SIMD backends aren't
+        // really ordered by preference because they're for mutually exclusive target platforms,
+        // but it's less of a mess to chain them like this than to replicate the `cfg` soup. The
+        // portable SIMD backend is only a fallback for targets without a hand-written backend,
+        // and we only use the scalar backend if none of the SIMD backends are available.
+        avx2::detect()
+            .or_else(ssse3::detect)
+            .or_else(sse2::detect)
+            .or_else(neon::detect)
+            .or_else(simd128::detect)
+            .or_else(portable_simd::detect)
+            .unwrap_or_else(scalar::backend)
+    }
+
+    /// The portable implementation, which is available everywhere.
+    pub fn scalar() -> Self {
+        scalar::backend()
+    }
+
+    /// The AVX2 backend for x86 and x86_64, if the CPU supports it and the `std` feature is
+    /// enabled for runtime feature detection.
+    pub fn x86_avx2() -> Option<Self> {
+        avx2::detect()
+    }
+
+    /// The SSSE3 backend for x86 and x86_64, if the CPU supports it and the `std` feature is
+    /// enabled for runtime feature detection.
+    ///
+    /// This is the SSE2 backend with a faster way to do some of the rotations in ChaCha, which
+    /// makes a difference on older CPUs without AVX2.
+    pub fn x86_ssse3() -> Option<Self> {
+        ssse3::detect()
+    }
+
+    /// The SSE2 backend for x86 and x86_64, if the `sse2` target feature is enabled at compile
+    /// time.
+    pub fn x86_sse2() -> Option<Self> {
+        sse2::detect()
+    }
+
+    /// The NEON backend for little-endian AArch64, if the `neon` target feature is enabled at
+    /// compile time.
+    pub fn aarch64_neon() -> Option<Self> {
+        neon::detect()
+    }
+
+    /// The SIMD backend for WebAssembly, if the `simd128` target feature is enabled at compile
+    /// time.
+    pub fn wasm32_simd128() -> Option<Self> {
+        simd128::detect()
+    }
+
+    /// The backend written with `core::simd`, if the `unstable_portable_simd` feature is enabled.
+    ///
+    /// It's available on every target, but [`Backend::detect_best`] only picks it when there's no
+    /// hand-written SIMD backend for the current target.
+    pub fn portable_simd() -> Option<Self> {
+        portable_simd::detect()
+    }
+}
@@ -1,9 +1,10 @@
-use core::arch::aarch64::uint32x4_t;
+use core::{arch::aarch64::uint32x4_t, mem::MaybeUninit};
 
 use arrayref::array_mut_ref;
 
 use crate::{
-    common_guts::{eight_rounds, init_state},
+    backend::SafeImpls,
+    common_guts::{init_state, rounds},
     neon::safe_arch::{
         add_u32, reinterpret_u32x4_as_u8x16, reinterpret_u8x16_as_u32x4, shift_left_u32, splat,
         store_u8x16, tbl_u8x16, u32x4_from_elems, xor,
@@ -16,31 +17,45 @@ use super::safe_arch::{
     u8x16_from_elems,
 };
 
+static IMPLS: SafeImpls = SafeImpls::new(by_rounds!(fill_buf), by_rounds!(fill_group));
+
 pub fn detect() -> Option<Backend> {
-    Some(Backend::new(fill_buf))
+    Some(Backend::new(&IMPLS))
 }
 
-pub fn fill_buf(key: &[u32; 8], buf: &mut Buffer) {
-    let buf = &mut buf.bytes;
-    let mut ctr = u32x4_from_elems([0, 1, 2, 3]);
+pub fn fill_buf<const ROUNDS: usize>(key: &[u32; 8], stream: u64, buf: &mut MaybeUninit<Buffer>) {
+    let buf = Buffer::bytes_uninit(buf);
     for group in 0..4 {
-        let mut x = init_state(ctr, key, splat);
-
-        eight_rounds(&mut x, quarter_round);
-
-        for i in 4..12 {
-            x[i] = add_u32(x[i], splat(key[i - 4]));
-        }
+        fill_group::<ROUNDS>(
+            key,
+            stream,
+            group as u32,
+            array_mut_ref![buf, group * 256, 256],
+        );
+    }
+}
 
-        let group_buf = array_mut_ref![buf, group * 256, 256];
-        for (i, &xi) in x.iter().enumerate() {
-            store_u8x16(
-                reinterpret_u32x4_as_u8x16(xi),
-                array_mut_ref![group_buf, 16 * i, 16],
-            );
-        }
+#[inline(always)]
+fn fill_group<const ROUNDS: usize>(
+    key: &[u32; 8],
+    stream: u64,
+    group: u32,
+    group_buf: &mut [MaybeUninit<u8>; 256],
+) {
+    let ctr = add_u32(u32x4_from_elems([0, 1, 2, 3]), splat(4 * group));
+    let mut x = init_state(ctr, key, stream, splat);
+
+    rounds::<ROUNDS, _>(&mut x, quarter_round);
+
+    for i in 4..12 {
+        x[i] = add_u32(x[i], splat(key[i - 4]));
+    }
 
-        ctr = add_u32(ctr, splat(4));
+    for (i, &xi) in x.iter().enumerate() {
+        store_u8x16(
+            reinterpret_u32x4_as_u8x16(xi),
+            array_mut_ref![group_buf, 16 * i, 16],
+        );
     }
 }
 
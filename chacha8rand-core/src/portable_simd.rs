@@ -0,0 +1,79 @@
+use core::{mem::MaybeUninit, simd::u32x4};
+
+use crate::{
+    backend::SafeImpls,
+    common_guts::{init_state, rounds},
+    Backend, Buffer,
+};
+use arrayref::array_mut_ref;
+
+static IMPLS: SafeImpls = SafeImpls::new(by_rounds!(fill_buf), by_rounds!(fill_group));
+
+// Unlike the other SIMD backends, this one doesn't need any unsafe code or feature detection:
+// `core::simd` lowers to whatever the target has, and falls back to scalar code where it has no
+// SIMD at all. It's only meant for targets that don't have a hand-written backend, so it doesn't
+// have to be fast everywhere, just not slower than the scalar backend.
+pub(crate) fn detect() -> Option<Backend> {
+    Some(Backend::new(&IMPLS))
+}
+
+fn fill_buf<const ROUNDS: usize>(key: &[u32; 8], stream: u64, buf: &mut MaybeUninit<Buffer>) {
+    let buf = Buffer::bytes_uninit(buf);
+    for group in 0..4 {
+        fill_group::<ROUNDS>(
+            key,
+            stream,
+            group as u32,
+            array_mut_ref![buf, group * 256, 256],
+        );
+    }
+}
+
+fn fill_group<const ROUNDS: usize>(
+    key: &[u32; 8],
+    stream: u64,
+    group: u32,
+    group_buf: &mut [MaybeUninit<u8>; 256],
+) {
+    let ctr = u32x4::from_array([0, 1, 2, 3]) + u32x4::splat(4 * group);
+    let mut x = init_state(ctr, key, stream, u32x4::splat);
+
+    rounds::<ROUNDS, _>(&mut x, quarter_round);
+
+    for i in 4..12 {
+        x[i] += u32x4::splat(key[i - 4]);
+    }
+
+    for (i, xi) in x.iter().enumerate() {
+        let row = array_mut_ref![group_buf, i * 16, 16];
+        for (j, word) in xi.to_array().into_iter().enumerate() {
+            *array_mut_ref![row, j * 4, 4] = word.to_le_bytes().map(MaybeUninit::new);
+        }
+    }
+}
+
+#[inline(always)]
+fn quarter_round([mut a, mut b, mut c, mut d]: [u32x4; 4]) -> [u32x4; 4] {
+    a += b;
+    d ^= a;
+    d = rotl::<16>(d);
+
+    c += d;
+    b ^= c;
+    b = rotl::<12>(b);
+
+    a += b;
+    d ^= a;
+    d = rotl::<8>(d);
+
+    c += d;
+    b ^= c;
+    b = rotl::<7>(b);
+
+    [a, b, c, d]
+}
+
+#[inline(always)]
+fn rotl<const SH: u32>(x: u32x4) -> u32x4 {
+    (x << u32x4::splat(SH)) | (x >> u32x4::splat(32 - SH))
+}
@@ -0,0 +1,142 @@
+use core::mem::MaybeUninit;
+
+use crate::{
+    backend::SafeImpls,
+    common_guts::{init_state, rounds, C0, C1, C2, C3},
+    Backend, Buffer, BUF_TOTAL_LEN,
+};
+use arrayref::array_mut_ref;
+
+static IMPLS: SafeImpls = SafeImpls::new(by_rounds!(fill_buf), by_rounds!(fill_group));
+
+pub(crate) fn backend() -> Backend {
+    Backend::new(&IMPLS)
+}
+
+#[inline(never)]
+fn fill_buf<const ROUNDS: usize>(key: &[u32; 8], stream: u64, buf: &mut MaybeUninit<Buffer>) {
+    let buf = Buffer::bytes_uninit(buf);
+    for quad in 0..4 {
+        let quad_buf = array_mut_ref![buf, quad * 256, 256];
+        for block in 0..4 {
+            let ctr = (quad * 4 + block) as u32;
+            block_strided::<ROUNDS>(
+                key,
+                stream,
+                ctr,
+                array_mut_ref![quad_buf, 4 * block, 256 - 12],
+            );
+        }
+    }
+}
+
+fn fill_group<const ROUNDS: usize>(
+    key: &[u32; 8],
+    stream: u64,
+    quad: u32,
+    quad_buf: &mut [MaybeUninit<u8>; 256],
+) {
+    for block in 0..4 {
+        let ctr = quad * 4 + block as u32;
+        block_strided::<ROUNDS>(
+            key,
+            stream,
+            ctr,
+            array_mut_ref![quad_buf, 4 * block, 256 - 12],
+        );
+    }
+}
+
+fn block_strided<const ROUNDS: usize>(
+    key: &[u32; 8],
+    stream: u64,
+    ctr: u32,
+    out: &mut [MaybeUninit<u8>; 244],
+) {
+    let mut x = init_state(ctr, key, stream, |n| n);
+
+    rounds::<ROUNDS, _>(&mut x, quarter_round);
+
+    for i in 4..12 {
+        x[i] = x[i].wrapping_add(key[i - 4]);
+    }
+
+    for (i, xi) in x.iter().enumerate() {
+        *array_mut_ref![out, i * 16, 4] = xi.to_le_bytes().map(MaybeUninit::new);
+    }
+}
+
+/// The same computation as `fill_buf` for eight rounds and stream zero, but written so that it can
+/// be evaluated at compile time.
+pub(crate) const fn fill_buf_const(key: &[u32; 8]) -> [u8; BUF_TOTAL_LEN] {
+    let mut bytes = [0; BUF_TOTAL_LEN];
+    let mut ctr = 0;
+    while ctr < 16 {
+        let x = block_const(key, ctr as u32);
+        let (quad, block) = (ctr / 4, ctr % 4);
+        let mut i = 0;
+        while i < x.len() {
+            let word = x[i].to_le_bytes();
+            let offset = quad * 256 + 16 * i + 4 * block;
+            let mut j = 0;
+            while j < word.len() {
+                bytes[offset + j] = word[j];
+                j += 1;
+            }
+            i += 1;
+        }
+        ctr += 1;
+    }
+    bytes
+}
+
+const fn block_const(key: &[u32; 8], ctr: u32) -> [u32; 16] {
+    #[rustfmt::skip]
+    let mut x = [
+        C0,     C1,     C2,     C3,
+        key[0], key[1], key[2], key[3],
+        key[4], key[5], key[6], key[7],
+        ctr,    0,      0,      0,
+    ];
+    // `rounds` takes a closure, so it can't be used here.
+    let mut round = 0;
+    while round < 8 {
+        [x[0], x[4], x[8], x[12]] = quarter_round([x[0], x[4], x[8], x[12]]);
+        [x[1], x[5], x[9], x[13]] = quarter_round([x[1], x[5], x[9], x[13]]);
+        [x[2], x[6], x[10], x[14]] = quarter_round([x[2], x[6], x[10], x[14]]);
+        [x[3], x[7], x[11], x[15]] = quarter_round([x[3], x[7], x[11], x[15]]);
+        [x[0], x[5], x[10], x[15]] = quarter_round([x[0], x[5], x[10], x[15]]);
+        [x[1], x[6], x[11], x[12]] = quarter_round([x[1], x[6], x[11], x[12]]);
+        [x[2], x[7], x[8], x[13]] = quarter_round([x[2], x[7], x[8], x[13]]);
+        [x[3], x[4], x[9], x[14]] = quarter_round([x[3], x[4], x[9], x[14]]);
+        round += 2;
+    }
+
+    let mut i = 4;
+    while i < 12 {
+        x[i] = x[i].wrapping_add(key[i - 4]);
+        i += 1;
+    }
+    x
+}
+
+#[inline(always)]
+const fn quarter_round([mut a, mut b, mut c, mut d]: [u32; 4]) -> [u32; 4] {
+    a = a.wrapping_add(b);
+    d ^= a;
+    d = d.rotate_left(16);
+
+    c = c.wrapping_add(d);
+    b ^= c;
+    b = b.rotate_left(12);
+
+    a = a.wrapping_add(b);
+    d ^= a;
+    d = d.rotate_left(8);
+
+    c = c.wrapping_add(d);
+    b ^= c;
+    b = b.rotate_left(7);
+
+    [a, b, c, d]
+}
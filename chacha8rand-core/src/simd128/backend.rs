@@ -0,0 +1,78 @@
+use core::{
+    arch::wasm32::{u32x4, u32x4_add, u32x4_shl, u32x4_shr, v128, v128_xor},
+    mem::MaybeUninit,
+};
+
+use arrayref::array_mut_ref;
+
+use crate::{
+    backend::SafeImpls,
+    common_guts::{init_state, rounds},
+    simd128::safe_arch::{splat, store_as_u8x16},
+    Backend, Buffer,
+};
+
+static IMPLS: SafeImpls = SafeImpls::new(by_rounds!(fill_buf), by_rounds!(fill_group));
+
+pub fn detect() -> Option<Backend> {
+    Some(Backend::new(&IMPLS))
+}
+
+pub fn fill_buf<const ROUNDS: usize>(key: &[u32; 8], stream: u64, buf: &mut MaybeUninit<Buffer>) {
+    let buf = Buffer::bytes_uninit(buf);
+    for group in 0..4 {
+        fill_group::<ROUNDS>(
+            key,
+            stream,
+            group as u32,
+            array_mut_ref![buf, group * 256, 256],
+        );
+    }
+}
+
+#[inline(always)]
+fn fill_group<const ROUNDS: usize>(
+    key: &[u32; 8],
+    stream: u64,
+    group: u32,
+    group_buf: &mut [MaybeUninit<u8>; 256],
+) {
+    let ctr = u32x4_add(u32x4(0, 1, 2, 3), splat(4 * group));
+    let mut x = init_state(ctr, key, stream, splat);
+
+    rounds::<ROUNDS, _>(&mut x, quarter_round);
+
+    for i in 4..12 {
+        x[i] = u32x4_add(x[i], splat(key[i - 4]));
+    }
+
+    for (i, &xi) in x.iter().enumerate() {
+        store_as_u8x16(xi, array_mut_ref![group_buf, 16 * i, 16]);
+    }
+}
+
+#[inline(always)]
+fn quarter_round([mut a, mut b, mut c, mut d]: [v128; 4]) -> [v128; 4] {
+    a = u32x4_add(a, b);
+    d = v128_xor(d, a);
+    d = rotl(d, 16);
+
+    c = u32x4_add(c, d);
+    b = v128_xor(b, c);
+    b = rotl(b, 12);
+
+    a = u32x4_add(a, b);
+    d = v128_xor(d, a);
+    d = rotl(d, 8);
+
+    c = u32x4_add(c, d);
+    b = v128_xor(b, c);
+    b = rotl(b, 7);
+
+    [a, b, c, d]
+}
+
+#[inline(always)]
+fn rotl(x: v128, amt: u32) -> v128 {
+    v128_xor(u32x4_shl(x, amt), u32x4_shr(x, 32 - amt))
+}
@@ -1,3 +1,5 @@
+use core::mem::MaybeUninit;
+
 #[cfg(target_arch = "x86")]
 use core::arch::x86 as arch;
 #[cfg(target_arch = "x86_64")]
@@ -47,10 +49,10 @@ pub fn shift_right_u32<const IMM8: i32>(x: __m128i) -> __m128i {
     unsafe { _mm_srli_epi32::<IMM8>(x) }
 }
 
-pub fn storeu(x: __m128i, dest: &mut [u8; 16]) {
+pub fn storeu(x: __m128i, dest: &mut [MaybeUninit<u8>; 16]) {
     // SAFETY: (1) Requires the sse2 target feature, which was detected by cfg. (2) Stores 128 bits
-    // through the pointer, which is OK because it's a mutable reference to `[u8; 16]`. There is no
-    // alignment requirement.
+    // through the pointer, which is OK because it's a mutable reference to 16 (possibly
+    // uninitialized) bytes. There is no alignment requirement.
     let mem_addr: *mut __m128i = dest.as_mut_ptr().cast();
     unsafe {
         _mm_storeu_si128(mem_addr, x);
@@ -0,0 +1,125 @@
+use core::mem::MaybeUninit;
+
+use crate::{
+    backend::Impls,
+    common_guts::{init_state, rounds},
+    ssse3::safe_arch::{__m128i, Ssse3},
+    Backend, Buffer,
+};
+use arrayref::array_mut_ref;
+
+static IMPLS: Impls = Impls::new(by_rounds!(fill_buf), by_rounds!(fill_group));
+
+pub(crate) fn detect() -> Option<Backend> {
+    if std::is_x86_feature_detected!("ssse3") {
+        // SAFETY: `fill_buf` and `fill_group` are only unsafe because they enable the SSSE3
+        // `target_feature`, and we've ensured that SSSE3 is available, so they're now effectively
+        // safe functions.
+        unsafe { Some(Backend::new_unchecked(&IMPLS)) }
+    } else {
+        None
+    }
+}
+
+/// # Safety
+///
+/// Requires SSSE3 target feature. No other safety requirements.
+#[target_feature(enable = "ssse3")]
+pub unsafe fn fill_buf<const ROUNDS: usize>(
+    key: &[u32; 8],
+    stream: u64,
+    buf: &mut MaybeUninit<Buffer>,
+) {
+    let buf = Buffer::bytes_uninit(buf);
+    for group in 0..4 {
+        // SAFETY: we're in a function with the same target feature.
+        unsafe {
+            fill_group::<ROUNDS>(
+                key,
+                stream,
+                group as u32,
+                array_mut_ref![buf, group * 256, 256],
+            );
+        }
+    }
+}
+
+/// # Safety
+///
+/// Requires SSSE3 target feature. No other safety requirements.
+#[target_feature(enable = "ssse3")]
+pub unsafe fn fill_group<const ROUNDS: usize>(
+    key: &[u32; 8],
+    stream: u64,
+    group: u32,
+    group_buf: &mut [MaybeUninit<u8>; 256],
+) {
+    // Since we're already inside a function with `target_feature(enable = "ssse3")`, the `expect`
+    // is too late to prevent UB. See the AVX2 backend for why we do it anyway.
+    let ssse3 = Ssse3::new().expect("SSSE3 must be available if this backend is invoked");
+
+    let ctr = ssse3.add_u32(ssse3.elems([0, 1, 2, 3]), ssse3.splat(4 * group));
+    let splat = |x| ssse3.splat(x);
+    let mut x = init_state(ctr, key, stream, splat);
+
+    rounds::<ROUNDS, _>(
+        &mut x,
+        #[inline(always)]
+        |abcd| quarter_round(ssse3, abcd),
+    );
+
+    for i in 4..12 {
+        x[i] = ssse3.add_u32(x[i], splat(key[i - 4]));
+    }
+
+    for (i, &xi) in x.iter().enumerate() {
+        ssse3.storeu(xi, array_mut_ref![group_buf, i * 16, 16]);
+    }
+}
+
+#[inline(always)]
+fn quarter_round(ssse3: Ssse3, [mut a, mut b, mut c, mut d]: [__m128i; 4]) -> [__m128i; 4] {
+    a = ssse3.add_u32(a, b);
+    d = ssse3.xor(d, a);
+    d = rotl16(ssse3, d);
+
+    c = ssse3.add_u32(c, d);
+    b = ssse3.xor(b, c);
+    b = rotl::<12, 20>(ssse3, b);
+
+    a = ssse3.add_u32(a, b);
+    d = ssse3.xor(d, a);
+    d = rotl8(ssse3, d);
+
+    c = ssse3.add_u32(c, d);
+    b = ssse3.xor(b, c);
+    b = rotl::<7, 25>(ssse3, b);
+
+    [a, b, c, d]
+}
+
+#[inline(always)]
+fn rotl<const SH_LEFT: i32, const SH_RIGHT: i32>(ssse3: Ssse3, x: __m128i) -> __m128i {
+    const {
+        assert!(SH_RIGHT == (32 - SH_LEFT));
+    }
+    ssse3.xor(
+        ssse3.shift_left_u32::<SH_LEFT>(x),
+        ssse3.shift_right_u32::<SH_RIGHT>(x),
+    )
+}
+
+// Rotations by whole bytes just move bytes around within each (little-endian) lane, so they can be
+// done with a single shuffle instead of two shifts and a xor. This is the point of this backend.
+
+#[inline(always)]
+fn rotl16(ssse3: Ssse3, x: __m128i) -> __m128i {
+    let indices = ssse3.bytes([2, 3, 0, 1, 6, 7, 4, 5, 10, 11, 8, 9, 14, 15, 12, 13]);
+    ssse3.shuffle_bytes(x, indices)
+}
+
+#[inline(always)]
+fn rotl8(ssse3: Ssse3, x: __m128i) -> __m128i {
+    let indices = ssse3.bytes([3, 0, 1, 2, 7, 4, 5, 6, 11, 8, 9, 10, 15, 12, 13, 14]);
+    ssse3.shuffle_bytes(x, indices)
+}
@@ -0,0 +1,104 @@
+use core::mem::MaybeUninit;
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86 as arch;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64 as arch;
+
+pub use arch::__m128i;
+use arch::{
+    _mm_add_epi32, _mm_set1_epi32, _mm_setr_epi32, _mm_setr_epi8, _mm_shuffle_epi8, _mm_slli_epi32,
+    _mm_srli_epi32, _mm_storeu_si128, _mm_xor_si128,
+};
+
+pub(crate) use detect::Ssse3;
+
+mod detect {
+    // Safety invariant: can only be constructed if SSSE3 (and therefore SSE2) is available.
+    #[derive(Clone, Copy)]
+    pub(crate) struct Ssse3 {
+        _feature_detected: (),
+    }
+
+    impl Ssse3 {
+        pub(crate) fn new() -> Option<Self> {
+            if std::is_x86_feature_detected!("ssse3") {
+                Some(Self {
+                    _feature_detected: (),
+                })
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl Ssse3 {
+    #[inline(always)]
+    pub(crate) fn elems(self, elems: [u32; 4]) -> __m128i {
+        let [e0, e1, e2, e3] = elems.map(|e| e as i32);
+        // SAFETY: only needs SSE2, `self` proves that we have SSSE3, which implies SSE2.
+        unsafe { _mm_setr_epi32(e0, e1, e2, e3) }
+    }
+
+    #[inline(always)]
+    pub(crate) fn bytes(self, bytes: [u8; 16]) -> __m128i {
+        let [b0, b1, b2, b3, b4, b5, b6, b7, b8, b9, b10, b11, b12, b13, b14, b15] =
+            bytes.map(|b| b as i8);
+        // SAFETY: only needs SSE2, `self` proves that we have SSSE3, which implies SSE2.
+        unsafe {
+            _mm_setr_epi8(
+                b0, b1, b2, b3, b4, b5, b6, b7, b8, b9, b10, b11, b12, b13, b14, b15,
+            )
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn splat(self, x: u32) -> __m128i {
+        // SAFETY: only needs SSE2, `self` proves that we have SSSE3, which implies SSE2.
+        unsafe { _mm_set1_epi32(x as i32) }
+    }
+
+    #[inline(always)]
+    pub(crate) fn add_u32(self, x: __m128i, y: __m128i) -> __m128i {
+        // SAFETY: only needs SSE2, `self` proves that we have SSSE3, which implies SSE2.
+        unsafe { _mm_add_epi32(x, y) }
+    }
+
+    #[inline(always)]
+    pub(crate) fn xor(self, x: __m128i, y: __m128i) -> __m128i {
+        // SAFETY: only needs SSE2, `self` proves that we have SSSE3, which implies SSE2.
+        unsafe { _mm_xor_si128(x, y) }
+    }
+
+    #[inline(always)]
+    pub(crate) fn shift_left_u32<const IMM8: i32>(self, x: __m128i) -> __m128i {
+        // SAFETY: only needs SSE2, `self` proves that we have SSSE3, which implies SSE2.
+        unsafe { _mm_slli_epi32::<IMM8>(x) }
+    }
+
+    #[inline(always)]
+    pub(crate) fn shift_right_u32<const IMM8: i32>(self, x: __m128i) -> __m128i {
+        // SAFETY: only needs SSE2, `self` proves that we have SSSE3, which implies SSE2.
+        unsafe { _mm_srli_epi32::<IMM8>(x) }
+    }
+
+    /// Byte `i` of the result is byte `indices[i]` of `x` (`pshufb`). All indices must be less
+    /// than 16, larger ones have a special meaning that we don't need.
+    #[inline(always)]
+    pub(crate) fn shuffle_bytes(self, x: __m128i, indices: __m128i) -> __m128i {
+        // SAFETY: only needs SSSE3, `self` proves that we have it.
+        unsafe { _mm_shuffle_epi8(x, indices) }
+    }
+
+    #[inline(always)]
+    pub(crate) fn storeu(self, x: __m128i, dest: &mut [MaybeUninit<u8>; 16]) {
+        let mem_addr: *mut __m128i = dest.as_mut_ptr().cast();
+        // SAFETY: (1) Requires SSE2, `self` proves that we have SSSE3, which implies SSE2. (2)
+        // Stores 128 bits through the pointer, which is OK because it's a mutable reference to 16
+        // (possibly uninitialized) bytes. There is no alignment requirement.
+        unsafe {
+            _mm_storeu_si128(mem_addr, x);
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use arrayref::array_ref;
+
+use crate::{Backend, Buffer};
+
+macro_rules! test_backends {
+    (
+        $(
+            $(#[cfg($gate:meta)])?
+            $name:ident => $ctor:expr;
+        )+
+    ) => {
+        $(
+            $(#[cfg($gate)])?
+            mod $name {
+                #[test]
+                fn sample_iterations() {
+                    super::sample_iterations($ctor);
+                }
+
+                #[test]
+                fn refill_uninit_matches_refill() {
+                    super::refill_uninit_matches_refill($ctor);
+                }
+
+                #[test]
+                fn fill_group_matches_refill() {
+                    super::fill_group_matches_refill($ctor);
+                }
+
+                #[test]
+                fn fill_group_pair_matches_fill_group() {
+                    super::fill_group_pair_matches_fill_group($ctor);
+                }
+
+                #[test]
+                fn next_key_matches_refill() {
+                    super::next_key_matches_refill($ctor);
+                }
+
+                #[test]
+                fn other_streams_match_scalar() {
+                    super::other_streams_match_scalar($ctor);
+                }
+
+                #[test]
+                fn other_rounds_match_scalar() {
+                    super::other_rounds_match_scalar::<12>($ctor);
+                    super::other_rounds_match_scalar::<20>($ctor);
+                }
+            }
+        )+
+    };
+}
+
+test_backends! {
+    scalar => crate::Backend::scalar();
+    #[cfg(any(
+        target_arch = "x86_64",
+        // because we have no runtime detection for sse2
+        all(target_arch = "x86", target_feature = "sse2"),
+    ))]
+    sse2 => crate::Backend::x86_sse2().expect("this test requires sse2");
+    #[cfg(all(any(target_arch = "x86_64", target_arch = "x86"), feature = "std"))]
+    ssse3 => crate::Backend::x86_ssse3().expect("this test requires ssse3");
+    #[cfg(all(any(target_arch = "x86_64", target_arch = "x86"), feature = "std"))]
+    avx2 => crate::Backend::x86_avx2().expect("this test requires avx2");
+    #[cfg(target_arch = "aarch64")]
+    neon => crate::Backend::aarch64_neon().expect("this test requires neon");
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    simd128 => crate::Backend::wasm32_simd128().expect("this test requires simd128");
+    #[cfg(feature = "unstable_portable_simd")]
+    portable_simd => crate::Backend::portable_simd().expect("this test requires portable_simd");
+}
+
+const SAMPLE_KEY: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+
+// The first four u64s (little endian) of each of the first three iterations, taken from the sample
+// output in the spec. The full sample output is checked by the tests of the chacha8rand crate.
+const SAMPLE_ITERATION_STARTS: [[u64; 4]; 3] = [
+    [
+        0xb773b6063d4616a5,
+        0x1160af22a66abc3c,
+        0x8c2599d9418d287c,
+        0x7ee07e037edc5cd6,
+    ],
+    [
+        0x5f41df72e05e0487,
+        0x25bd7e1e1ae26b1d,
+        0xbe9f4004d662a41d,
+        0x65bf58d483188546,
+    ],
+    [
+        0xb433eec25dca1966,
+        0x530f30dc5cff9a93,
+        0x9ff03d98b53cd335,
+        0xafc4225076558cdf,
+    ],
+];
+
+fn key_from_bytes(bytes: &[u8; 32]) -> [u32; 8] {
+    core::array::from_fn(|i| u32::from_le_bytes(*array_ref![bytes, 4 * i, 4]))
+}
+
+fn sample_iterations(backend: Backend) {
+    let mut key = key_from_bytes(SAMPLE_KEY);
+    let mut buf = Buffer::new();
+    for expected in SAMPLE_ITERATION_STARTS {
+        backend.refill(&key, &mut buf);
+        let start: [u64; 4] =
+            core::array::from_fn(|i| u64::from_le_bytes(*array_ref![buf.output(), 8 * i, 8]));
+        assert_eq!(start, expected);
+        key = key_from_bytes(buf.new_key());
+    }
+}
+
+fn refill_uninit_matches_refill(backend: Backend) {
+    let key = key_from_bytes(SAMPLE_KEY);
+    let mut buf = Buffer::new();
+    backend.refill(&key, &mut buf);
+    let mut uninit = core::mem::MaybeUninit::uninit();
+    let buf2 = backend.refill_uninit(&key, &mut uninit);
+    assert_eq!(buf.bytes, buf2.bytes);
+}
+
+fn fill_group_matches_refill(backend: Backend) {
+    let key = key_from_bytes(SAMPLE_KEY);
+    let mut buf = Buffer::new();
+    backend.refill(&key, &mut buf);
+    for group in 0..4 {
+        let mut group_buf = [0; 256];
+        backend.fill_group(&key, group, &mut group_buf);
+        assert_eq!(group_buf, buf.bytes[group as usize * 256..][..256]);
+    }
+}
+
+fn fill_group_pair_matches_fill_group(backend: Backend) {
+    let key0 = key_from_bytes(SAMPLE_KEY);
+    let key1 = key_from_bytes(&SAMPLE_KEY.map(|b| b ^ 1));
+    for backend in [backend, backend.with_stream(7)] {
+        for group in 0..4 {
+            let mut expected = [[0; 256]; 2];
+            backend.fill_group(&key0, group, &mut expected[0]);
+            backend.fill_group(&key1, group, &mut expected[1]);
+            let mut pair = [[0; 256]; 2];
+            backend.fill_group_pair([&key0, &key1], group, pair.each_mut());
+            assert_eq!(pair, expected);
+
+            backend.fill_group_with_rounds::<20>(&key0, group, &mut expected[0]);
+            backend.fill_group_with_rounds::<20>(&key1, group, &mut expected[1]);
+            backend.fill_group_pair_with_rounds::<20>([&key0, &key1], group, pair.each_mut());
+            assert_eq!(pair, expected);
+        }
+    }
+}
+
+fn next_key_matches_refill(backend: Backend) {
+    let mut key = key_from_bytes(SAMPLE_KEY);
+    let mut buf = Buffer::new();
+    for _ in 0..3 {
+        backend.refill(&key, &mut buf);
+        assert_eq!(&backend.next_key(&key), buf.new_key());
+        key = key_from_bytes(buf.new_key());
+    }
+}
+
+fn other_streams_match_scalar(backend: Backend) {
+    let key = key_from_bytes(SAMPLE_KEY);
+    let mut spec = Buffer::new();
+    backend.refill(&key, &mut spec);
+    for stream in [0, 1, 1 << 32, u64::MAX] {
+        let backend = backend.with_stream(stream);
+        assert_eq!(backend.stream(), stream);
+        let mut buf = Buffer::new();
+        backend.refill(&key, &mut buf);
+        let mut expected = Buffer::new();
+        Backend::scalar()
+            .with_stream(stream)
+            .refill(&key, &mut expected);
+        assert_eq!(buf.bytes, expected.bytes);
+        assert_eq!(buf.bytes == spec.bytes, stream == 0);
+        let mut last_group = [0; 256];
+        backend.fill_group(&key, 3, &mut last_group);
+        assert_eq!(last_group, buf.bytes[768..]);
+    }
+}
+
+fn other_rounds_match_scalar<const ROUNDS: usize>(backend: Backend) {
+    let key = key_from_bytes(SAMPLE_KEY);
+    let mut eight_rounds = Buffer::new();
+    backend.refill(&key, &mut eight_rounds);
+    for stream in [0, u64::MAX] {
+        let backend = backend.with_stream(stream);
+        let mut buf = Buffer::new();
+        backend.refill_with_rounds::<ROUNDS>(&key, &mut buf);
+        let mut expected = Buffer::new();
+        Backend::scalar()
+            .with_stream(stream)
+            .refill_with_rounds::<ROUNDS>(&key, &mut expected);
+        assert_eq!(buf.bytes, expected.bytes);
+        assert_ne!(buf.bytes, eight_rounds.bytes);
+        let mut uninit = core::mem::MaybeUninit::uninit();
+        let buf2 = backend.refill_uninit_with_rounds::<ROUNDS>(&key, &mut uninit);
+        assert_eq!(buf.bytes, buf2.bytes);
+        for group in 0..4 {
+            let mut group_buf = [0; 256];
+            backend.fill_group_with_rounds::<ROUNDS>(&key, group, &mut group_buf);
+            assert_eq!(group_buf, buf.bytes[group as usize * 256..][..256]);
+        }
+        assert_eq!(&backend.next_key_with_rounds::<ROUNDS>(&key), buf.new_key());
+    }
+}
+
+#[test]
+fn twenty_rounds_match_rfc7539() {
+    // The block function test vector from RFC 7539, section 2.3.2. The nonce words 13 and 14 are
+    // where the stream ID goes, and the block counter 1 is the second block of the first group.
+    let key: [u32; 8] =
+        core::array::from_fn(|i| u32::from_le_bytes(core::array::from_fn(|j| (4 * i + j) as u8)));
+    let stream = 0x4a00_0000_0900_0000;
+    let rfc_output: [u32; 16] = [
+        0xe4e7f110, 0x15593bd1, 0x1fdd0f50, 0xc47120a3, 0xc7f4d1c7, 0x0368c033, 0x9aaa2204,
+        0x4e6cd4c3, 0x466482d2, 0x09aa9f07, 0x05d7c214, 0xa2028bd9, 0xd19c12b5, 0xb94e16de,
+        0xe883d0cb, 0x4e3c50a2,
+    ];
+    // ChaCha8Rand only adds the key back into words 4..12, so undo the feed-forward of the other
+    // words of the initial state.
+    let initial: [u32; 16] = [
+        0x61707865, 0x3320646e, 0x79622d32, 0x6b206574, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0x09000000,
+        0x4a000000, 0,
+    ];
+    let mut group = [0; 256];
+    Backend::scalar()
+        .with_stream(stream)
+        .fill_group_with_rounds::<20>(&key, 0, &mut group);
+    for (i, (&rfc_word, &initial_word)) in rfc_output.iter().zip(&initial).enumerate() {
+        let block_word = u32::from_le_bytes(*array_ref![group, 16 * i + 4, 4]);
+        assert_eq!(block_word, rfc_word.wrapping_sub(initial_word), "word {i}");
+    }
+}
+
+#[test]
+fn compute_const_matches_refill() {
+    const KEY: [u32; 8] = [
+        0x44434241, 0x48474645, 0x4c4b4a49, 0x504f4e4d, 0x54535251, 0x58575655, 0x32315a59,
+        0x36353433,
+    ];
+    const BUF: Buffer = Buffer::compute_const(&KEY);
+    assert_eq!(KEY, key_from_bytes(SAMPLE_KEY));
+    let mut key = KEY;
+    let mut buf = Buffer::new();
+    for _ in 0..3 {
+        Backend::scalar().refill(&key, &mut buf);
+        assert_eq!(buf.bytes, Buffer::compute_const(&key).bytes);
+        key = key_from_bytes(buf.new_key());
+    }
+    Backend::scalar().refill(&KEY, &mut buf);
+    assert_eq!(buf.bytes, BUF.bytes);
+}
+
+#[test]
+fn backend_is_a_table_pointer_and_stream_id() {
+    // Generators carry a `Backend`, so the function tables for all round counts must stay out of
+    // line.
+    assert!(size_of::<Backend>() <= size_of::<&()>() + size_of::<u64>());
+}
@@ -0,0 +1,161 @@
+//! Derive macros for the [`chacha8rand`](https://docs.rs/chacha8rand) crate.
+//!
+//! Don't depend on this crate directly. Enable the `macros` feature of `chacha8rand` instead, which
+//! re-exports everything from here and documents how to use it. The generated code refers to items
+//! from `::chacha8rand`, including some that are hidden and not covered by SemVer, so the two
+//! crates must always be used in lockstep.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned, Data, DeriveInput, Error, Expr, Field,
+    Fields, GenericParam, WherePredicate,
+};
+
+/// See the `chacha8rand` documentation of this macro.
+#[proc_macro_derive(Random, attributes(random))]
+pub fn derive_random(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    random(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn random(mut input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "`Random` can only be derived for structs (try `UniformEnum` for fieldless enums)",
+        ));
+    };
+    // Fields are sampled in declaration order, which is also the order in which struct expressions
+    // evaluate their field initializers.
+    let body = match &data.fields {
+        Fields::Named(fields) => {
+            let inits = fields
+                .named
+                .iter()
+                .map(|field| {
+                    let name = &field.ident;
+                    let value = sample_field(field)?;
+                    Ok(quote!(#name: #value,))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote!(Self { #(#inits)* })
+        }
+        Fields::Unnamed(fields) => {
+            let inits = fields
+                .unnamed
+                .iter()
+                .map(sample_field)
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote!(Self(#(#inits),*))
+        }
+        Fields::Unit => quote!(Self),
+    };
+
+    let bounds: Vec<WherePredicate> = input
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+                Some(parse_quote!(#ident: ::chacha8rand::Random))
+            }
+            GenericParam::Lifetime(_) | GenericParam::Const(_) => None,
+        })
+        .collect();
+    input.generics.make_where_clause().predicates.extend(bounds);
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::chacha8rand::Random for #name #ty_generics #where_clause {
+            fn random(rng: &mut ::chacha8rand::ChaCha8Rand) -> Self {
+                #body
+            }
+        }
+    })
+}
+
+fn sample_field(field: &Field) -> syn::Result<TokenStream2> {
+    let mut range: Option<Expr> = None;
+    for attr in &field.attrs {
+        if !attr.path().is_ident("random") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("range") {
+                if range.is_some() {
+                    return Err(meta.error("duplicate `range` attribute"));
+                }
+                range = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute, expected `range = ...`"))
+            }
+        })?;
+    }
+    let ty = &field.ty;
+    Ok(match range {
+        Some(range) => quote_spanned! {range.span()=>
+            ::chacha8rand::__private::sample_range::<#ty, _>(rng, #range)
+        },
+        None => quote_spanned! {ty.span()=>
+            <#ty as ::chacha8rand::Random>::random(rng)
+        },
+    })
+}
+
+/// See the `chacha8rand` documentation of this macro.
+#[proc_macro_derive(UniformEnum)]
+pub fn derive_uniform_enum(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    uniform_enum(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn uniform_enum(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "`UniformEnum` can only be derived for enums",
+        ));
+    };
+    if data.variants.is_empty() {
+        return Err(Error::new(
+            input.ident.span(),
+            "`UniformEnum` can't be derived for enums without variants",
+        ));
+    }
+    let mut arms = Vec::with_capacity(data.variants.len());
+    for (i, variant) in data.variants.iter().enumerate() {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new(
+                variant.span(),
+                "`UniformEnum` can only be derived for enums whose variants have no fields",
+            ));
+        }
+        let i = u32::try_from(i).map_err(|_| Error::new(variant.span(), "too many variants"))?;
+        let variant = &variant.ident;
+        arms.push(quote!(#i => Self::#variant,));
+    }
+    let num_variants = arms.len() as u32;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::chacha8rand::Random for #name #ty_generics #where_clause {
+            fn random(rng: &mut ::chacha8rand::ChaCha8Rand) -> Self {
+                match ::chacha8rand::__private::read_u32_below(rng, #num_variants) {
+                    #(#arms)*
+                    _ => ::core::unreachable!(),
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,101 @@
+use alloc::vec::Vec;
+
+use crate::ChaCha8Rand;
+
+/// A precomputed table for sampling indices with probability proportional to integer weights in
+/// constant time per sample.
+///
+/// [`ChaCha8Rand::choose_weighted`] needs a pass over all weights for every draw, which gets
+/// expensive when drawing many times from the same weights. This type uses the alias method
+/// instead: construction takes linear time and memory, and then every draw takes constant time.
+/// Index `i` is picked with probability exactly `weights[i] / sum(weights)`, because the whole
+/// table is computed with integer arithmetic.
+///
+/// The construction is Vose's variant of the alias method, specified as follows so that the table
+/// (and hence every draw) is reproducible. Let `n` be the number of weights and `total` their sum.
+///
+/// 1. Compute `scaled[i] = weights[i] * n` (without overflow). Go through the indices in
+///    ascending order and push each to the `small` stack if `scaled[i] < total`, and to the
+///    `large` stack otherwise.
+/// 2. While both stacks are non-empty, pop `s` from `small` and `l` from `large`. Bucket `s` gets
+///    threshold `scaled[s]` and alias `l`. Then `scaled[l]` is reduced by `total - scaled[s]`,
+///    and `l` is pushed back to `small` or `large` according to the same rule as before.
+/// 3. All indices left on either stack get threshold `total` (and are their own alias).
+///
+/// A draw picks a bucket `i` uniformly from `0..n` (like [`ChaCha8Rand::choose`] does), then
+/// draws `r` with [`ChaCha8Rand::read_u64_below`]`(total)`, and returns `i` if `r` is less than
+/// the threshold of bucket `i` and its alias otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::{ChaCha8Rand, WeightedAlias};
+///
+/// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// let letters = ['e', 't', 'a', 'o', 'z'];
+/// let table = WeightedAlias::new(&[127, 91, 82, 75, 1]).unwrap();
+/// let text: String = (0..20).map(|_| letters[table.sample(&mut rng)]).collect();
+/// assert_eq!(text.len(), 20);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WeightedAlias {
+    total: u64,
+    /// Threshold and alias for each bucket.
+    pub(crate) buckets: Vec<(u64, usize)>,
+}
+
+impl WeightedAlias {
+    /// Build the table for the given weights.
+    ///
+    /// Returns `None` if `weights` is empty, all weights are zero, or their sum overflows `u64`.
+    pub fn new(weights: &[u64]) -> Option<Self> {
+        let total = weights
+            .iter()
+            .try_fold(0u64, |sum, &w| sum.checked_add(w))?;
+        if total == 0 {
+            return None;
+        }
+        let n = weights.len() as u128;
+        let mut scaled: Vec<u128> = weights.iter().map(|&w| u128::from(w) * n).collect();
+        let mut buckets: Vec<(u64, usize)> = (0..weights.len()).map(|i| (total, i)).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..weights.len()).partition(|&i| scaled[i] < u128::from(total));
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            large.pop();
+            // `scaled[s] < total`, so the cast can't truncate.
+            buckets[s] = (scaled[s] as u64, l);
+            scaled[l] -= u128::from(total) - scaled[s];
+            if scaled[l] < u128::from(total) {
+                small.push(l);
+            } else {
+                large.push(l);
+            }
+        }
+        Some(WeightedAlias { total, buckets })
+    }
+
+    /// The number of weights the table was built from.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Always `false`, because building a table from zero weights isn't possible.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Sample an index. Indices with weight zero are never returned.
+    #[inline]
+    pub fn sample(&self, rng: &mut ChaCha8Rand) -> usize {
+        let i = rng.read_index(self.buckets.len());
+        let (threshold, alias) = self.buckets[i];
+        if rng.read_u64_below(self.total) < threshold {
+            i
+        } else {
+            alias
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use alloc::collections::VecDeque;
+use core::{fmt, panic::Location};
+
+use crate::ChaChaRand;
+
+/// One read operation recorded in a [`ChaCha8Rand`]'s audit log, see
+/// [`ChaCha8Rand::enable_audit_log`]. Requires crate feature `audit`.
+///
+/// The `Display` impl prints it on one line, e.g., `src/main.rs:12:9: 8 bytes at 992`, which is
+/// handy for writing logs that you'll later compare with `diff`.
+///
+/// [`ChaCha8Rand`]: crate::ChaCha8Rand
+/// [`ChaCha8Rand::enable_audit_log`]: crate::ChaCha8Rand::enable_audit_log
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AuditEntry {
+    /// Where the read method was called.
+    pub location: &'static Location<'static>,
+    /// The generator's [`ChaCha8Rand::position`] right before the read.
+    ///
+    /// [`ChaCha8Rand::position`]: crate::ChaCha8Rand::position
+    pub position: u128,
+    /// How many bytes were consumed.
+    pub len: u64,
+}
+
+impl fmt::Display for AuditEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} bytes at {}",
+            self.location, self.len, self.position
+        )
+    }
+}
+
+/// The most recent entries of the audit log, up to `capacity` many.
+#[derive(Clone)]
+pub(crate) struct AuditLog {
+    entries: VecDeque<AuditEntry>,
+    capacity: usize,
+}
+
+impl AuditLog {
+    pub(crate) const fn disabled() -> Self {
+        AuditLog {
+            entries: VecDeque::new(),
+            capacity: 0,
+        }
+    }
+}
+
+/// Recording which code consumes output. Requires crate feature `audit`.
+impl<const ROUNDS: usize> ChaChaRand<ROUNDS> {
+    /// Start recording every read operation, keeping the most recent `capacity` of them.
+    ///
+    /// Each entry says where the read was called, at which [`ChaCha8Rand::position`], and how
+    /// many bytes it consumed. This is meant for hunting down desyncs in lockstep simulations
+    /// (e.g., multiplayer games): when two peers' generators disagree, compare their logs to find
+    /// the first call site where their consumption histories differ. The log is kept in memory and
+    /// only has room for `capacity` entries, so the oldest ones are dropped as new ones come in.
+    ///
+    /// All the methods that consume output count as reads: `read_u32`, `read_u64`, `read_u128`,
+    /// all `read_bytes` variants, [`ChaCha8Rand::consume`], and [`ChaCha8Rand::skip_bytes`], as
+    /// well as the convenience methods built on top of them like `read_array` or `read_vec`. When
+    /// this feature is enabled, they're all `#[track_caller]`, so the recorded location is where
+    /// *your* code called them. Reads done by other parts of this crate on your behalf (e.g.,
+    /// trait impls or [`ChaCha8Rand::sample`]) are recorded at their location inside this crate,
+    /// which still tells you what kind of value was sampled. Jumping around in the stream with
+    /// [`ChaCha8Rand::seek_to`] or by restoring a snapshot isn't recorded, but the positions of
+    /// subsequent entries reflect it.
+    ///
+    /// Calling this again clears the log, and a `capacity` of zero turns recording off again.
+    /// Cloning a generator clones its log. The log doesn't affect the output or comparisons
+    /// between generators.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// rng.enable_audit_log(100);
+    /// rng.read_u64();
+    /// rng.read_bytes(&mut [0; 5]);
+    /// let lens: Vec<u64> = rng.audit_log().map(|entry| entry.len).collect();
+    /// assert_eq!(lens, [8, 5]);
+    /// for entry in rng.audit_log() {
+    ///     println!("{entry}");
+    /// }
+    /// ```
+    ///
+    /// [`ChaCha8Rand::position`]: crate::ChaCha8Rand::position
+    /// [`ChaCha8Rand::consume`]: crate::ChaCha8Rand::consume
+    /// [`ChaCha8Rand::skip_bytes`]: crate::ChaCha8Rand::skip_bytes
+    /// [`ChaCha8Rand::sample`]: crate::ChaCha8Rand::sample
+    /// [`ChaCha8Rand::seek_to`]: crate::ChaCha8Rand::seek_to
+    pub fn enable_audit_log(&mut self, capacity: usize) {
+        self.audit = AuditLog {
+            entries: VecDeque::new(),
+            capacity,
+        };
+    }
+
+    /// The entries in the audit log, from oldest to newest. Empty unless recording was turned on
+    /// with [`ChaCha8Rand::enable_audit_log`].
+    ///
+    /// [`ChaCha8Rand::enable_audit_log`]: crate::ChaCha8Rand::enable_audit_log
+    pub fn audit_log(&self) -> impl ExactSizeIterator<Item = AuditEntry> + '_ {
+        self.audit.entries.iter().copied()
+    }
+
+    #[track_caller]
+    pub(crate) fn audit_read(&mut self, len: u64) {
+        if self.audit.capacity == 0 {
+            return;
+        }
+        let position = self.position();
+        let log = &mut self.audit;
+        if log.entries.len() == log.capacity {
+            log.entries.pop_front();
+        }
+        log.entries.push_back(AuditEntry {
+            location: Location::caller(),
+            position,
+            len,
+        });
+    }
+}
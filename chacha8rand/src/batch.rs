@@ -0,0 +1,75 @@
+use core::iter;
+
+use crate::{seed_from_bytes, Backend, ChaCha8Rand, BUF_OUTPUT_LEN};
+
+impl ChaCha8Rand {
+    /// Compute the first `N` bytes of output for each of many seeds.
+    ///
+    /// For every `i`, `out[i]` receives the same bytes that `ChaCha8Rand::new(&seeds[i])` would
+    /// produce from its first `read_bytes(&mut out[i])` call. The result is the same, but this
+    /// function can be a lot faster when `N` is small. Creating a generator always computes a full
+    /// iteration (1024 bytes) of output, most of which is wasted if you only need, say, a 32-byte
+    /// key per entity. Internally, an iteration is computed in four independent groups of 256
+    /// bytes, and this function only computes as many groups as needed to cover `N` bytes. For
+    /// `N <= 256` that's a quarter of the work. On top of that, seeds are processed in pairs with
+    /// [`Backend::fill_group_pair`], which lets the AVX2 backend fill both halves of its vectors
+    /// with useful work (one seed each) instead of computing every block twice.
+    ///
+    /// Values of `N` larger than 992 (the output of a single iteration) are supported, but don't
+    /// benefit from any of this.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `seeds` and `out` have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chacha8rand::ChaCha8Rand;
+    ///
+    /// let mut root = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let entity_seeds: Vec<[u8; 32]> = (0..1000).map(|_| root.read_seed()).collect();
+    /// let mut entity_keys = vec![[0u8; 16]; entity_seeds.len()];
+    /// ChaCha8Rand::expand_seeds(&entity_seeds, &mut entity_keys);
+    ///
+    /// let mut expected = [0; 16];
+    /// ChaCha8Rand::new(&entity_seeds[42]).read_bytes(&mut expected);
+    /// assert_eq!(entity_keys[42], expected);
+    /// ```
+    pub fn expand_seeds<const N: usize>(seeds: &[[u8; 32]], out: &mut [[u8; N]]) {
+        assert_eq!(
+            seeds.len(),
+            out.len(),
+            "need exactly one output array per seed"
+        );
+        let backend = Backend::detect_best();
+        if N > BUF_OUTPUT_LEN {
+            for (seed, out) in seeds.iter().zip(out) {
+                Self::with_backend_impl(seed, backend).read_bytes(out);
+            }
+            return;
+        }
+        let mut seed_pairs = seeds.chunks_exact(2);
+        let mut out_pairs = out.chunks_exact_mut(2);
+        for (seeds, outs) in (&mut seed_pairs).zip(&mut out_pairs) {
+            let keys = [seed_from_bytes(&seeds[0]), seed_from_bytes(&seeds[1])];
+            let (out0, out1) = outs.split_at_mut(1);
+            for (group, (chunk0, chunk1)) in
+                iter::zip(out0[0].chunks_mut(256), out1[0].chunks_mut(256)).enumerate()
+            {
+                let mut group_bufs = [[0; 256]; 2];
+                backend.fill_group_pair([&keys[0], &keys[1]], group as u32, group_bufs.each_mut());
+                chunk0.copy_from_slice(&group_bufs[0][..chunk0.len()]);
+                chunk1.copy_from_slice(&group_bufs[1][..chunk1.len()]);
+            }
+        }
+        for (seed, out) in iter::zip(seed_pairs.remainder(), out_pairs.into_remainder()) {
+            let key = seed_from_bytes(seed);
+            for (group, chunk) in out.chunks_mut(256).enumerate() {
+                let mut group_buf = [0; 256];
+                backend.fill_group(&key, group as u32, &mut group_buf);
+                chunk.copy_from_slice(&group_buf[..chunk.len()]);
+            }
+        }
+    }
+}
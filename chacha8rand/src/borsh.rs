@@ -0,0 +1,30 @@
+use borsh::{
+    io::{Error, ErrorKind, Read, Result, Write},
+    BorshDeserialize, BorshSerialize,
+};
+
+use crate::ChaCha8State;
+
+/// Serialization with borsh. Requires crate feature `borsh`.
+///
+/// The encoding is the same as [`ChaCha8State::to_bytes`], which also matches what deriving the
+/// borsh traits for the struct would produce: the 32 seed bytes, then `bytes_consumed` as `u16`,
+/// then `iteration` as `u64`, all integers in little-endian byte order.
+impl BorshSerialize for ChaCha8State {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+/// Serialization with borsh. Requires crate feature `borsh`.
+///
+/// Deserialization fails with [`ErrorKind::InvalidData`] if `bytes_consumed` is out of range, like
+/// [`ChaCha8State::from_bytes`].
+impl BorshDeserialize for ChaCha8State {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut bytes = [0; ChaCha8State::ENCODED_LEN];
+        reader.read_exact(&mut bytes)?;
+        ChaCha8State::from_bytes(&bytes)
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "invalid ChaCha8State"))
+    }
+}
@@ -0,0 +1,162 @@
+use core::{cmp, fmt, mem::MaybeUninit};
+
+use crate::{Backend, ChaCha8Rand, StreamPosition};
+
+/// Configures and creates a [`ChaCha8Rand`] with more options than the basic constructors.
+///
+/// Create one with [`ChaCha8Rand::builder`], set the options you care about, and call
+/// [`ChaCha8RandBuilder::build`]. Options that aren't set keep their defaults, which match
+/// [`ChaCha8Rand::new`]:
+///
+/// * [`backend`][ChaCha8RandBuilder::backend]: the best [`Backend`] for the current CPU.
+/// * [`position`][ChaCha8RandBuilder::position]: the start of the stream.
+/// * [`lazy`][ChaCha8RandBuilder::lazy]: `false`, i.e., the buffer is filled right away.
+/// * [`output_digest`][ChaCha8RandBuilder::output_digest]: `false` (requires crate feature
+///   `output_digest`).
+/// * [`stream`][ChaCha8RandBuilder::stream]: zero, i.e., the stream described by the spec.
+///
+/// Except for the stream ID, none of the options affect the output, only when and how it's
+/// computed. Like for seeds and snapshots, the `Debug` impl doesn't print the seed.
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::{Backend, ChaCha8Rand, StreamPosition};
+///
+/// let seed = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+/// let mut rng = ChaCha8Rand::builder(seed)
+///     .backend(Backend::scalar())
+///     .position(StreamPosition::from_byte_offset(8).unwrap())
+///     .build();
+/// assert_eq!(rng.read_u64(), 0x1160af22a66abc3c);
+/// ```
+#[derive(Clone)]
+pub struct ChaCha8RandBuilder {
+    seed: [u8; 32],
+    backend: Option<Backend>,
+    position: StreamPosition,
+    lazy: bool,
+    #[cfg(feature = "output_digest")]
+    output_digest: bool,
+    stream: u64,
+}
+
+impl ChaCha8RandBuilder {
+    /// Start configuring a generator with the given seed. Same as [`ChaCha8Rand::builder`].
+    pub fn new(seed: &[u8; 32]) -> Self {
+        ChaCha8RandBuilder {
+            seed: *seed,
+            backend: None,
+            position: StreamPosition::START,
+            lazy: false,
+            #[cfg(feature = "output_digest")]
+            output_digest: false,
+            stream: 0,
+        }
+    }
+
+    /// Use a specific [`Backend`], like [`ChaCha8Rand::with_backend`] does.
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Start at `position` in the stream instead of at the beginning.
+    ///
+    /// The result is the same as calling [`ChaCha8Rand::seek_to`] after construction, including
+    /// that the position is counted from the seed and the generator can seek back to earlier
+    /// positions. But the builder skips directly to the iteration containing `position` without
+    /// computing the output of the first iteration.
+    pub fn position(mut self, position: StreamPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Defer filling the buffer until the first output is needed.
+    ///
+    /// Creating a generator normally computes its first 992 bytes of output right away. With this
+    /// option, that only happens once something actually reads from it (or peeks, skips, etc.),
+    /// which saves time if you create many generators and don't use all of them. It doesn't save
+    /// any memory, and the generator's behavior is otherwise exactly the same.
+    ///
+    /// This only makes a difference when starting at the beginning of the stream. Reaching any
+    /// other [`position`][ChaCha8RandBuilder::position] involves computing the iteration that
+    /// contains it, so the buffer is filled anyway.
+    pub fn lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+
+    /// Keep a running checksum of the output, like calling [`ChaCha8Rand::enable_output_digest`]
+    /// right after construction. The digest starts at the configured
+    /// [`position`][ChaCha8RandBuilder::position].
+    ///
+    /// Requires crate feature `output_digest`.
+    #[cfg(feature = "output_digest")]
+    pub fn output_digest(mut self, output_digest: bool) -> Self {
+        self.output_digest = output_digest;
+        self
+    }
+
+    /// Use the stream with the given ID, like [`ChaCha8Rand::with_stream`] does. **This changes
+    /// the output to something other than what the spec describes**, unless `stream_id` is zero.
+    ///
+    /// This takes precedence over any stream ID that the [`backend`][ChaCha8RandBuilder::backend]
+    /// was set up with.
+    pub fn stream(mut self, stream_id: u64) -> Self {
+        self.stream = stream_id;
+        self
+    }
+
+    /// Create the generator.
+    pub fn build(self) -> ChaCha8Rand {
+        let backend = self
+            .backend
+            .unwrap_or_else(Backend::detect_best)
+            .with_stream(self.stream);
+        let mut rng = MaybeUninit::uninit();
+        ChaCha8Rand::init_in(&mut rng, &self.seed, backend, true);
+        // SAFETY: `init_in` initialized it.
+        let mut rng = unsafe { rng.assume_init() };
+        // Skipping from the unfilled state goes straight to the right iteration, without
+        // computing any output that would be thrown away.
+        let mut remaining = self.position.to_byte_offset();
+        while remaining > 0 {
+            let n = cmp::min(remaining, u128::from(u64::MAX)) as u64;
+            rng.skip_bytes_impl(n);
+            remaining -= u128::from(n);
+        }
+        if !self.lazy {
+            rng.fill_if_unfilled();
+        }
+        // Getting to the start position doesn't count as producing output.
+        count!(rng, jump_to(rng.position()));
+        #[cfg(feature = "output_digest")]
+        if self.output_digest {
+            rng.enable_output_digest();
+        }
+        rng
+    }
+}
+
+impl fmt::Debug for ChaCha8RandBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut f = f.debug_struct("ChaCha8RandBuilder");
+        f.field("backend", &self.backend)
+            .field("position", &self.position)
+            .field("lazy", &self.lazy);
+        #[cfg(feature = "output_digest")]
+        f.field("output_digest", &self.output_digest);
+        f.field("stream", &self.stream).finish_non_exhaustive()
+    }
+}
+
+impl ChaCha8Rand {
+    /// Start configuring a generator with the given seed, for options that [`ChaCha8Rand::new`]
+    /// doesn't offer.
+    ///
+    /// See [`ChaCha8RandBuilder`] for the available options.
+    pub fn builder(seed: &[u8; 32]) -> ChaCha8RandBuilder {
+        ChaCha8RandBuilder::new(seed)
+    }
+}
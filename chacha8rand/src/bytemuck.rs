@@ -0,0 +1,44 @@
+use bytemuck::Pod;
+
+use crate::ChaCha8Rand;
+
+/// Filling plain-old-data types. Requires crate feature `bytemuck`.
+impl ChaCha8Rand {
+    /// Fill all bytes of `dest` with uniformly random data.
+    ///
+    /// This is [`ChaCha8Rand::read_bytes`] on the memory of `dest`, so it works for any type that
+    /// implements [`Pod`]: vertex structs, particle arrays, SIMD vectors, and so on. Every bit
+    /// pattern is valid for such types, so every field ends up uniformly random, and the whole
+    /// slice consumes `size_of_val(dest)` bytes.
+    ///
+    /// The bytes are written in memory order, which has a caveat for reproducibility: multi-byte
+    /// fields (e.g., a `u32` or `f32`) are interpreted in the target's native byte order. On
+    /// little-endian targets, which is virtually all of them, a `u32` field gets the same value
+    /// [`ChaCha8Rand::read_u32`] would return, but on big-endian targets it's byte-swapped. There
+    /// is no way to fix this up generically, because `Pod` doesn't describe where the fields are.
+    /// If you need identical values on big-endian targets, fill each field with the corresponding
+    /// `read_*` method instead, or swap the bytes of each field after filling it.
+    ///
+    /// Also keep in mind that random bytes make for unusual floating-point values: NaNs,
+    /// infinities, subnormals, and numbers across the entire exponent range. To get floats in
+    /// `[0, 1)`, use [`ChaCha8Rand::fill_f32_unit`] or [`ChaCha8Rand::fill_f64_unit`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// #[derive(Clone, Copy, bytemuck::Zeroable, bytemuck::Pod)]
+    /// #[repr(C)]
+    /// struct Particle {
+    ///     seed: u32,
+    ///     color: [u8; 4],
+    /// }
+    ///
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let mut particles = vec![Particle { seed: 0, color: [0; 4] }; 1000];
+    /// rng.fill_pod(&mut particles);
+    /// ```
+    pub fn fill_pod<T: Pod>(&mut self, dest: &mut [T]) {
+        self.read_bytes(bytemuck::cast_slice_mut(dest));
+    }
+}
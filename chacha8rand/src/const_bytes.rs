@@ -0,0 +1,62 @@
+use crate::{Buffer, ChaCha8Rand};
+
+impl ChaCha8Rand {
+    /// The first `N` bytes of output for `seed`, computed in a way that works in `const` contexts.
+    ///
+    /// The result is the same as creating a generator with [`ChaCha8Rand::new`] and reading `N`
+    /// bytes with [`ChaCha8Rand::read_bytes`]. The difference is that this is a `const fn`, so
+    /// you can use it to bake tables (e.g., for tabulation hashing or noise functions) or test
+    /// fixtures into your binary at compile time, with no cost at startup. It uses a plain
+    /// scalar implementation of ChaCha8, so calling it at runtime is much slower than using a
+    /// generator, especially if a SIMD backend is available.
+    ///
+    /// To get integers or other types, convert the bytes like `read_u32` and friends do, e.g.,
+    /// with `u64::from_le_bytes` on eight-byte chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chacha8rand::ChaCha8Rand;
+    ///
+    /// const SEED: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+    /// static TABLE: [u8; 4096] = ChaCha8Rand::const_bytes(SEED);
+    ///
+    /// let mut rng = ChaCha8Rand::new(SEED);
+    /// let mut expected = [0; 4096];
+    /// rng.read_bytes(&mut expected);
+    /// assert_eq!(TABLE, expected);
+    /// ```
+    pub const fn const_bytes<const N: usize>(seed: &[u8; 32]) -> [u8; N] {
+        let mut out = [0; N];
+        let mut key = seed_from_bytes_const(seed);
+        let mut pos = 0;
+        while pos < N {
+            let buf = Buffer::compute_const(&key);
+            let output = buf.output();
+            let mut i = 0;
+            while i < output.len() && pos < N {
+                out[pos] = output[i];
+                pos += 1;
+                i += 1;
+            }
+            key = seed_from_bytes_const(buf.new_key());
+        }
+        out
+    }
+}
+
+/// Same as `seed_from_bytes`, but a `const fn`.
+const fn seed_from_bytes_const(bytes: &[u8; 32]) -> [u32; 8] {
+    let mut key = [0; 8];
+    let mut i = 0;
+    while i < key.len() {
+        key[i] = u32::from_le_bytes([
+            bytes[4 * i],
+            bytes[4 * i + 1],
+            bytes[4 * i + 2],
+            bytes[4 * i + 3],
+        ]);
+        i += 1;
+    }
+    key
+}
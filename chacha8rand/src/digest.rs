@@ -0,0 +1,124 @@
+use crate::{ChaChaRand, BUF_OUTPUT_LEN};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The bookkeeping behind [`ChaCha8Rand::output_digest`].
+///
+/// Like the byte counts in `Counters`, bytes aren't hashed as they're consumed, because that
+/// would slow down every read. Instead, `absorbed` remembers up to where the current buffer has
+/// been hashed, and the bytes between that and `bytes_consumed` are hashed whenever the buffer is
+/// about to be overwritten or the generator is about to jump to another position.
+///
+/// [`ChaCha8Rand::output_digest`]: crate::ChaCha8Rand::output_digest
+#[derive(Clone)]
+pub(crate) struct OutputDigest {
+    hash: Option<u64>,
+    absorbed: usize,
+}
+
+impl OutputDigest {
+    pub(crate) fn disabled(bytes_consumed: usize) -> Self {
+        OutputDigest {
+            hash: None,
+            absorbed: bytes_consumed,
+        }
+    }
+
+    fn hash_pending(&self, output: &[u8], bytes_consumed: usize) -> Option<u64> {
+        let pending = output.get(self.absorbed..bytes_consumed).unwrap_or(&[]);
+        self.hash.map(|hash| fnv1a(hash, pending))
+    }
+}
+
+fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &b in bytes {
+        hash ^= u64::from(b);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+impl<const ROUNDS: usize> ChaChaRand<ROUNDS> {
+    /// Start keeping a running checksum of all output handed out from now on, see
+    /// [`ChaCha8Rand::output_digest`].
+    ///
+    /// If the digest was already enabled, it starts over from scratch.
+    ///
+    /// Requires crate feature `output_digest`.
+    ///
+    /// [`ChaCha8Rand::output_digest`]: crate::ChaCha8Rand::output_digest
+    pub fn enable_output_digest(&mut self) {
+        self.digest = OutputDigest {
+            hash: Some(FNV_OFFSET_BASIS),
+            absorbed: self.bytes_consumed,
+        };
+    }
+
+    /// A checksum of all output this generator handed out since [`enable_output_digest`] was
+    /// called, or `None` if it never was.
+    ///
+    /// The digest is the 64-bit [FNV-1a hash][fnv] of the concatenation of all bytes consumed
+    /// by reading (with `read_u32`, `read_bytes`, [`ChaCha8Rand::consume`], and so on), in order.
+    /// That's a stable definition, so it doesn't matter how the bytes were read, and you can
+    /// recompute it from a log of the output if needed. Bytes passed over by
+    /// [`ChaCha8Rand::skip_bytes`] or [`ChaCha8Rand::seek_to`] aren't hashed, since they were
+    /// never handed out, but the digest carries on across those jumps as well as across
+    /// re-seeding and restoring snapshots.
+    ///
+    /// This is meant for detecting divergence between replicas of a deterministic simulation:
+    /// compare digests every now and then instead of logging the whole stream. It's not a
+    /// cryptographic hash, so it's only good at catching accidents. Keeping it up to date costs
+    /// some time on every refill, which is why it's opt-in. Cloning a generator clones its
+    /// digest, but the digest doesn't affect the output or comparisons between generators.
+    /// Since it also adds 24 bytes of bookkeeping to every generator, it's only available with
+    /// crate feature `output_digest`.
+    ///
+    /// Another way to check replicas is comparing their [`ChaCha8Rand::position`], which is
+    /// always available for free but can't tell if one replica skipped bytes that the other read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let seed = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+    /// let mut a = ChaCha8Rand::new(seed);
+    /// let mut b = ChaCha8Rand::new(seed);
+    /// a.enable_output_digest();
+    /// b.enable_output_digest();
+    /// a.read_u64();
+    /// b.read_bytes(&mut [0; 8]);
+    /// assert_eq!(a.output_digest(), b.output_digest());
+    /// a.read_u32();
+    /// b.skip_bytes(4);
+    /// assert_eq!(a.position(), b.position());
+    /// assert_ne!(a.output_digest(), b.output_digest());
+    /// ```
+    ///
+    /// [`enable_output_digest`]: crate::ChaCha8Rand::enable_output_digest
+    /// [fnv]: http://www.isthe.com/chongo/tech/comp/fnv/index.html
+    /// [`ChaCha8Rand::consume`]: crate::ChaCha8Rand::consume
+    /// [`ChaCha8Rand::skip_bytes`]: crate::ChaCha8Rand::skip_bytes
+    /// [`ChaCha8Rand::seek_to`]: crate::ChaCha8Rand::seek_to
+    /// [`ChaCha8Rand::position`]: crate::ChaCha8Rand::position
+    pub fn output_digest(&self) -> Option<u64> {
+        self.digest
+            .hash_pending(self.buf.output(), self.bytes_consumed)
+    }
+
+    /// Hash the bytes consumed from the current buffer that haven't been hashed yet. Must be
+    /// called before anything overwrites the buffer or moves `bytes_consumed` without handing
+    /// out the bytes in between, followed by `digest_resync` afterwards.
+    pub(crate) fn digest_flush(&mut self) {
+        self.digest.hash = self
+            .digest
+            .hash_pending(self.buf.output(), self.bytes_consumed);
+        self.digest.absorbed = self.bytes_consumed;
+    }
+
+    /// Continue hashing from the current position in the buffer.
+    pub(crate) fn digest_resync(&mut self) {
+        debug_assert!(self.bytes_consumed <= BUF_OUTPUT_LEN);
+        self.digest.absorbed = self.bytes_consumed;
+    }
+}
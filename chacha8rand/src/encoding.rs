@@ -0,0 +1,165 @@
+use core::{error::Error, fmt, str::FromStr};
+
+use arrayref::{array_refs, mut_array_refs};
+
+use crate::{ChaCha8State, RestoreStateError, Seed, BUF_OUTPUT_LEN};
+
+const TEXT_PREFIX_V1: &str = "chacha8rand:v1:";
+const TEXT_PREFIX_V2: &str = "chacha8rand:v2:";
+
+/// Compact binary format, e.g., for storing snapshots in files or sending them over the network.
+impl ChaCha8State {
+    /// The length of the binary encoding produced by [`ChaCha8State::to_bytes`].
+    pub const ENCODED_LEN: usize = 42;
+
+    /// Encode the snapshot in the canonical binary format.
+    ///
+    /// The format is the 32-byte seed, followed by `bytes_consumed` as little-endian `u16`,
+    /// followed by `iteration` as little-endian `u64`. It's fixed-size, doesn't depend on the
+    /// platform, and won't change in future versions, so it's a good choice for exchanging
+    /// snapshots between programs. Unlike the `Debug` impl, this includes the seed, so anyone who
+    /// gets the encoded snapshot can reproduce the rest of the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::{ChaCha8Rand, ChaCha8State};
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// rng.read_u64();
+    /// let bytes = rng.clone_state().to_bytes();
+    /// assert_eq!(&bytes[..32], b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// assert_eq!(bytes[32..], [8, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    ///
+    /// let state = ChaCha8State::from_bytes(&bytes).unwrap();
+    /// rng.try_restore_state(&state).unwrap();
+    /// assert_eq!(rng.read_u64(), 0x1160af22a66abc3c);
+    /// ```
+    pub fn to_bytes(&self) -> [u8; Self::ENCODED_LEN] {
+        let mut bytes = [0; Self::ENCODED_LEN];
+        let (seed, bytes_consumed, iteration) = mut_array_refs![&mut bytes, 32, 2, 8];
+        *seed = self.seed;
+        *bytes_consumed = self.bytes_consumed.to_le_bytes();
+        *iteration = self.iteration.to_le_bytes();
+        bytes
+    }
+
+    /// Decode a snapshot from the format produced by [`ChaCha8State::to_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if `bytes_consumed` is out of range, just like [`ChaCha8Rand::try_restore_state`]
+    /// would. Every other combination of bytes is a valid snapshot.
+    ///
+    /// [`ChaCha8Rand::try_restore_state`]: crate::ChaCha8Rand::try_restore_state
+    pub fn from_bytes(bytes: &[u8; Self::ENCODED_LEN]) -> Result<Self, RestoreStateError> {
+        let (seed, bytes_consumed, iteration) = array_refs![bytes, 32, 2, 8];
+        let bytes_consumed = u16::from_le_bytes(*bytes_consumed);
+        if usize::from(bytes_consumed) > BUF_OUTPUT_LEN {
+            return Err(RestoreStateError { _private: () });
+        }
+        Ok(ChaCha8State {
+            seed: *seed,
+            bytes_consumed,
+            iteration: u64::from_le_bytes(*iteration),
+        })
+    }
+}
+
+/// Human-readable text format, e.g., for log lines, command line flags and bug reports.
+///
+/// The format is `chacha8rand:v2:<seed>:<iteration>:<bytes_consumed>`, where `<seed>` is 64 hex
+/// digits and the other two fields are decimal numbers. The prefix makes it easy to recognize (and
+/// grep for) and leaves room for other versions of the format in the future. Unlike the `Debug`
+/// impl, this prints the seed, so keep in mind that anyone who sees it can reproduce the rest of
+/// the stream.
+///
+/// # Examples
+///
+/// ```
+/// # use chacha8rand::{ChaCha8Rand, ChaCha8State};
+/// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// rng.read_u64();
+/// let text = rng.clone_state().to_string();
+/// assert_eq!(
+///     text,
+///     "chacha8rand:v2:\
+///      4142434445464748494a4b4c4d4e4f505152535455565758595a313233343536:0:8"
+/// );
+/// let state: ChaCha8State = text.parse().unwrap();
+/// rng.try_restore_state(&state).unwrap();
+/// assert_eq!(rng.read_u64(), 0x1160af22a66abc3c);
+/// ```
+impl fmt::Display for ChaCha8State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{TEXT_PREFIX_V2}{seed:x}:{iteration}:{bytes_consumed}",
+            seed = Seed(self.seed),
+            iteration = self.iteration,
+            bytes_consumed = self.bytes_consumed,
+        )
+    }
+}
+
+/// Parses the format produced by the `Display` impl.
+///
+/// Hex digits may be upper- or lowercase. Out-of-range `bytes_consumed` values are rejected, just
+/// like [`ChaCha8Rand::try_restore_state`][crate::ChaCha8Rand::try_restore_state] would. The older
+/// `chacha8rand:v1:<seed>:<bytes_consumed>` format, which didn't record the iteration, is still
+/// accepted and parsed as iteration zero.
+impl FromStr for ChaCha8State {
+    type Err = ParseStateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = ParseStateError { _private: () };
+        let (hex, iteration, bytes_consumed) = if let Some(rest) = s.strip_prefix(TEXT_PREFIX_V2) {
+            let (hex, rest) = rest.split_once(':').ok_or(err)?;
+            let (iteration, bytes_consumed) = rest.split_once(':').ok_or(err)?;
+            (hex, parse_decimal::<u64>(iteration)?, bytes_consumed)
+        } else {
+            let rest = s.strip_prefix(TEXT_PREFIX_V1).ok_or(err)?;
+            let (hex, bytes_consumed) = rest.split_once(':').ok_or(err)?;
+            (hex, 0, bytes_consumed)
+        };
+        let hex = <&[u8; 64]>::try_from(hex.as_bytes()).map_err(|_| err)?;
+        let Seed(seed) = Seed::from_hex(hex).ok_or(err)?;
+        let bytes_consumed = parse_decimal::<u16>(bytes_consumed)?;
+        if usize::from(bytes_consumed) > BUF_OUTPUT_LEN {
+            return Err(err);
+        }
+        Ok(ChaCha8State {
+            seed,
+            bytes_consumed,
+            iteration,
+        })
+    }
+}
+
+fn parse_decimal<T: FromStr>(s: &str) -> Result<T, ParseStateError> {
+    let err = ParseStateError { _private: () };
+    // `FromStr` for integers accepts a leading `+`, which we don't want to be part of the format.
+    if !s.bytes().all(|c| c.is_ascii_digit()) {
+        return Err(err);
+    }
+    s.parse().map_err(|_| err)
+}
+
+/// Error returned when parsing a [`ChaCha8State`] from a string fails.
+#[derive(Clone, Copy)]
+pub struct ParseStateError {
+    _private: (),
+}
+
+impl fmt::Debug for ParseStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ParseStateError")
+    }
+}
+
+impl fmt::Display for ParseStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid ChaCha8State string")
+    }
+}
+
+impl Error for ParseStateError {}
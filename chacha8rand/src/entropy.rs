@@ -0,0 +1,102 @@
+use core::{error::Error, fmt};
+
+use crate::ChaCha8Rand;
+
+/// Seeding from the operating system. Requires crate feature `getrandom_0_2` or `getrandom_0_3`.
+impl ChaCha8Rand {
+    /// Create a new generator with a fresh seed from the operating system's random number
+    /// generator, via [`getrandom`](https://crates.io/crates/getrandom).
+    ///
+    /// The seed is returned along with the generator, so that you can store or log it and
+    /// reproduce the same stream later by passing it to [`ChaCha8Rand::new`]. If you don't need
+    /// that, you can just ignore it, but consider whether you'll want to reproduce a run after the
+    /// fact before you do.
+    ///
+    /// This works with either supported version of `getrandom`, whichever one you enabled the
+    /// corresponding crate feature for. If both are enabled, v0.3 is used.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `getrandom` fails, which is very unlikely on most platforms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::{ChaCha8Rand, Seed};
+    /// let (mut rng, seed) = ChaCha8Rand::from_entropy()?;
+    /// println!("Using seed {:x}", Seed(seed));
+    /// let mut replay = ChaCha8Rand::new(&seed);
+    /// assert_eq!(rng.read_u64(), replay.read_u64());
+    /// # Ok::<(), chacha8rand::EntropyError>(())
+    /// ```
+    pub fn from_entropy() -> Result<(Self, [u8; 32]), EntropyError> {
+        let mut seed = [0; 32];
+        fill(&mut seed)?;
+        Ok((Self::new(&seed), seed))
+    }
+}
+
+#[cfg(feature = "getrandom_0_3")]
+fn fill(seed: &mut [u8; 32]) -> Result<(), EntropyError> {
+    getrandom_0_3::fill(seed).map_err(|e| EntropyError {
+        inner: Inner::V0_3(e),
+    })
+}
+
+#[cfg(not(feature = "getrandom_0_3"))]
+fn fill(seed: &mut [u8; 32]) -> Result<(), EntropyError> {
+    getrandom::getrandom(seed).map_err(|e| EntropyError {
+        inner: Inner::V0_2(e),
+    })
+}
+
+/// Error returned from [`ChaCha8Rand::from_entropy`] if the operating system couldn't provide a
+/// seed.
+///
+/// This wraps the error from whichever version of `getrandom` was used, and prints just like it.
+pub struct EntropyError {
+    inner: Inner,
+}
+
+enum Inner {
+    #[cfg(not(feature = "getrandom_0_3"))]
+    V0_2(getrandom::Error),
+    #[cfg(feature = "getrandom_0_3")]
+    V0_3(getrandom_0_3::Error),
+}
+
+impl EntropyError {
+    /// The OS-specific error code, if the error came from the operating system.
+    pub fn raw_os_error(&self) -> Option<i32> {
+        match &self.inner {
+            #[cfg(not(feature = "getrandom_0_3"))]
+            Inner::V0_2(e) => e.raw_os_error(),
+            #[cfg(feature = "getrandom_0_3")]
+            Inner::V0_3(e) => e.raw_os_error(),
+        }
+    }
+}
+
+impl fmt::Debug for EntropyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.inner {
+            #[cfg(not(feature = "getrandom_0_3"))]
+            Inner::V0_2(e) => f.debug_tuple("EntropyError").field(e).finish(),
+            #[cfg(feature = "getrandom_0_3")]
+            Inner::V0_3(e) => f.debug_tuple("EntropyError").field(e).finish(),
+        }
+    }
+}
+
+impl fmt::Display for EntropyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.inner {
+            #[cfg(not(feature = "getrandom_0_3"))]
+            Inner::V0_2(e) => fmt::Display::fmt(e, f),
+            #[cfg(feature = "getrandom_0_3")]
+            Inner::V0_3(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl Error for EntropyError {}
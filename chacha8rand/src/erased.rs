@@ -0,0 +1,71 @@
+use crate::ChaCha8Rand;
+
+/// An object-safe view of a random byte stream, for passing generators through `dyn` boundaries.
+///
+/// This is meant for plugin architectures and similar situations where the code consuming
+/// randomness shouldn't have to name [`ChaCha8Rand`] (or commit to a particular version of the
+/// `rand_core` traits). It only covers the basic read methods. The semantics are the same as for
+/// the inherent methods of the same name, so all methods consume bytes from the same stream in the
+/// same order.
+///
+/// Getting a trait object doesn't require allocation: `&mut rng` coerces to
+/// `&mut dyn ErasedRng`.
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::{ChaCha8Rand, ErasedRng};
+///
+/// fn roll_d20(rng: &mut dyn ErasedRng) -> u32 {
+///     // Fine for an example, but see `read_u32` docs about the modulo bias.
+///     rng.read_u32() % 20 + 1
+/// }
+///
+/// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// let roll = roll_d20(&mut rng);
+/// assert!((1..=20).contains(&roll));
+/// ```
+pub trait ErasedRng {
+    /// See [`ChaCha8Rand::read_u32`].
+    fn read_u32(&mut self) -> u32;
+
+    /// See [`ChaCha8Rand::read_u64`].
+    fn read_u64(&mut self) -> u64;
+
+    /// See [`ChaCha8Rand::read_bytes`].
+    fn read_bytes(&mut self, dest: &mut [u8]);
+}
+
+impl ErasedRng for ChaCha8Rand {
+    #[inline]
+    fn read_u32(&mut self) -> u32 {
+        ChaCha8Rand::read_u32(self)
+    }
+
+    #[inline]
+    fn read_u64(&mut self) -> u64 {
+        ChaCha8Rand::read_u64(self)
+    }
+
+    #[inline]
+    fn read_bytes(&mut self, dest: &mut [u8]) {
+        ChaCha8Rand::read_bytes(self, dest)
+    }
+}
+
+impl<R: ErasedRng + ?Sized> ErasedRng for &mut R {
+    #[inline]
+    fn read_u32(&mut self) -> u32 {
+        (**self).read_u32()
+    }
+
+    #[inline]
+    fn read_u64(&mut self) -> u64 {
+        (**self).read_u64()
+    }
+
+    #[inline]
+    fn read_bytes(&mut self, dest: &mut [u8]) {
+        (**self).read_bytes(dest)
+    }
+}
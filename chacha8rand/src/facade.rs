@@ -0,0 +1,164 @@
+use core::ops::{Bound, RangeBounds, RangeInclusive};
+
+use crate::ChaCha8Rand;
+
+/// Accept any kind of range (`a..b`, `a..=b`, `a..`, `..b`, `..`) and turn it into the equivalent
+/// inclusive range, panicking if it's empty.
+macro_rules! inclusive_range {
+    ($ty:ty, $range:expr) => {{
+        let range = $range;
+        let start = match range.start_bound() {
+            Bound::Included(&start) => Some(start),
+            Bound::Excluded(&start) => start.checked_add(1),
+            Bound::Unbounded => Some(<$ty>::MIN),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => Some(end),
+            Bound::Excluded(&end) => end.checked_sub(1),
+            Bound::Unbounded => Some(<$ty>::MAX),
+        };
+        match (start, end) {
+            (Some(start), Some(end)) if start <= end => RangeInclusive::new(start, end),
+            _ => panic!("can't sample from an empty range"),
+        }
+    }};
+}
+
+macro_rules! int_shorthands {
+    ($($ty:ident),+) => {
+        $(
+            #[doc = concat!("Sample a uniformly distributed `", stringify!($ty), "` from `range`.")]
+            ///
+            /// Any kind of range works, including `..` for all values of the type. The range is
+            /// converted to the equivalent inclusive range and sampled with
+            /// [`ChaCha8Rand::read_range`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the range is empty.
+            #[inline]
+            pub fn $ty(&mut self, range: impl RangeBounds<$ty>) -> $ty {
+                self.read_range(inclusive_range!($ty, range))
+            }
+        )+
+    };
+}
+
+/// Shorthands in the style of the [`fastrand`](https://crates.io/crates/fastrand) crate.
+///
+/// Small tools and tests often just want "a number from this range" or "a random letter" without
+/// thinking about which method to call. These methods are named after the type or kind of value
+/// they return, just like fastrand's, but they work on an explicitly seeded generator instead of
+/// a thread-local one. They're thin wrappers around the `read_*` methods, so they're exactly as
+/// reproducible: every method documents which building block it uses. The output doesn't match
+/// fastrand's, which uses a different generator. Shuffling a slice is
+/// [`ChaCha8Rand::shuffle`], which already has the right name.
+///
+/// # Examples
+///
+/// ```
+/// # use chacha8rand::ChaCha8Rand;
+/// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// let die = rng.u32(1..=6);
+/// let coin = rng.bool();
+/// let index = rng.usize(..10);
+/// let id: String = (0..8).map(|_| rng.alphanumeric()).collect();
+/// let mut cards = [1, 2, 3, 4, 5];
+/// rng.shuffle(&mut cards);
+/// # let _ = (die, coin, index, id);
+/// ```
+impl ChaCha8Rand {
+    int_shorthands!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+    /// Return `true` or `false` with equal probability.
+    ///
+    /// This is [`ChaCha8Rand::sample::<bool>`](ChaCha8Rand::sample), so it consumes one byte.
+    #[inline]
+    pub fn bool(&mut self) -> bool {
+        self.sample()
+    }
+
+    /// Sample a uniformly distributed `f32` in `[0, 1)`, see [`ChaCha8Rand::read_f32`].
+    #[inline]
+    pub fn f32(&mut self) -> f32 {
+        self.read_f32()
+    }
+
+    /// Sample a uniformly distributed `f64` in `[0, 1)`, see [`ChaCha8Rand::read_f64`].
+    #[inline]
+    pub fn f64(&mut self) -> f64 {
+        self.read_f64()
+    }
+
+    /// Return a random ASCII letter or digit.
+    ///
+    /// Each of the 62 characters is equally likely. The character is picked with
+    /// [`ChaCha8Rand::read_u32_below`]`(62)` from the uppercase letters, then the lowercase
+    /// letters, then the digits, in ASCII order.
+    #[inline]
+    pub fn alphanumeric(&mut self) -> char {
+        const CHARS: &[u8; 62] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+        char::from(CHARS[self.read_u32_below(62) as usize])
+    }
+
+    /// Return a random lowercase ASCII letter, picked with
+    /// [`ChaCha8Rand::read_u32_below`]`(26)`.
+    #[inline]
+    pub fn lowercase(&mut self) -> char {
+        char::from(b'a' + self.read_u32_below(26) as u8)
+    }
+
+    /// Return a random uppercase ASCII letter, picked with
+    /// [`ChaCha8Rand::read_u32_below`]`(26)`.
+    #[inline]
+    pub fn uppercase(&mut self) -> char {
+        char::from(b'A' + self.read_u32_below(26) as u8)
+    }
+
+    /// Return a random digit in the given base, e.g., `0`-`9` for base 10 or `0`-`9` and `a`-`f`
+    /// for base 16.
+    ///
+    /// The value is picked with [`ChaCha8Rand::read_u32_below`]`(base)` and converted with
+    /// [`char::from_digit`], so letters are lowercase.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is zero or greater than 36.
+    #[inline]
+    pub fn digit(&mut self, base: u32) -> char {
+        assert!(
+            (1..=36).contains(&base),
+            "base must be between 1 and 36, got {base}"
+        );
+        char::from_digit(self.read_u32_below(base), base).expect("digit is less than base")
+    }
+
+    /// Return a uniformly random item from `iter`, or `None` if it's empty.
+    ///
+    /// The index is picked like for [`ChaCha8Rand::choose`], and nothing is consumed from the
+    /// stream for empty iterators. Unlike [`ChaCha8Rand::choose`], this works with any iterator
+    /// that knows its length, e.g., the keys of a map or a range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let weekday = rng.choice(["Mon", "Tue", "Wed", "Thu", "Fri"]).unwrap();
+    /// let even = rng.choice((0..100).step_by(2)).unwrap();
+    /// assert_eq!(even % 2, 0);
+    /// # let _ = weekday;
+    /// ```
+    pub fn choice<I>(&mut self, iter: I) -> Option<I::Item>
+    where
+        I: IntoIterator,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut iter = iter.into_iter();
+        let len = iter.len();
+        if len == 0 {
+            return None;
+        }
+        iter.nth(self.read_index(len))
+    }
+}
@@ -0,0 +1,157 @@
+// Everything that needs transcendental functions (logarithms, exponentials, ...) uses `libm`
+// instead of the `std` methods. The latter call into the platform's math library, whose results
+// can differ in the last bits between platforms, which would make sampled values irreproducible.
+use core::cmp;
+
+use crate::{ChaCha8Rand, BUF_OUTPUT_LEN};
+
+/// Map 64 uniformly random bits to a uniformly distributed `f64` in `[0, 1)`.
+///
+/// This uses the 53 most significant bits as an integer and scales it by 2<sup>-53</sup>, so every
+/// result is a multiple of 2<sup>-53</sup> and all of those multiples are equally likely. The
+/// conversion and multiplication are exact, so the result doesn't depend on rounding modes or
+/// other platform details.
+#[inline]
+pub(crate) fn unit_f64_from_bits(bits: u64) -> f64 {
+    const SCALE: f64 = 1.0 / (1u64 << 53) as f64;
+    (bits >> 11) as f64 * SCALE
+}
+
+/// Like [`unit_f64_from_bits`] but for `f32`, using the 24 most significant bits.
+#[inline]
+pub(crate) fn unit_f32_from_bits(bits: u32) -> f32 {
+    const SCALE: f32 = 1.0 / (1u32 << 24) as f32;
+    (bits >> 8) as f32 * SCALE
+}
+
+impl ChaCha8Rand {
+    /// Consume eight bytes and return a uniformly distributed `f64` in `[0, 1)`.
+    ///
+    /// The bytes are read as a little-endian `u64` (as if by [`ChaCha8Rand::read_u64`]) and its
+    /// 53 most significant bits are scaled by 2<sup>-53</sup>: the result is exactly
+    /// `(x >> 11) as f64 / 2^53`. See [`ChaCha8Rand::fill_f64_unit`] for filling a whole slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let critical_hit = rng.read_f64() < 0.05;
+    /// # let _ = critical_hit;
+    /// ```
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_f64(&mut self) -> f64 {
+        unit_f64_from_bits(self.read_u64())
+    }
+
+    /// Consume four bytes and return a uniformly distributed `f32` in `[0, 1)`.
+    ///
+    /// The bytes are read as a little-endian `u32` (as if by [`ChaCha8Rand::read_u32`]) and its
+    /// 24 most significant bits are scaled by 2<sup>-24</sup>: the result is exactly
+    /// `(x >> 8) as f32 / 2^24`. See [`ChaCha8Rand::fill_f32_unit`] for filling a whole slice.
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_f32(&mut self) -> f32 {
+        unit_f32_from_bits(self.read_u32())
+    }
+
+    /// Fill `out` with uniformly distributed `f64`s in `[0, 1)`, consuming eight bytes for each.
+    ///
+    /// Every eight bytes are read as a little-endian `u64`, and its 53 most significant bits are
+    /// scaled by 2<sup>-53</sup>. In other words, `out[i]` is `(x >> 11) as f64 / 2^53` for the
+    /// `i`-th `u64` read from the stream. All multiples of 2<sup>-53</sup> in `[0, 1)` are equally
+    /// likely, and the conversion is exact, so it's easy to reproduce in other languages and won't
+    /// change between releases.
+    ///
+    /// This gives the same results as calling [`ChaCha8Rand::read_u64`] and converting each value
+    /// separately, but it converts straight out of the internal buffer in large batches, which
+    /// compilers can vectorize.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let mut xs = [0.0; 1000];
+    /// rng.fill_f64_unit(&mut xs);
+    /// assert_eq!(xs[0], (0xb773b6063d4616a5u64 >> 11) as f64 / (1u64 << 53) as f64);
+    /// assert!(xs.iter().all(|x| (0.0..1.0).contains(x)));
+    /// ```
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn fill_f64_unit(&mut self, out: &mut [f64]) {
+        self.fill_converted(out, |bytes| unit_f64_from_bits(u64::from_le_bytes(bytes)));
+    }
+
+    /// Fill `out` with uniformly distributed `f32`s in `[0, 1)`, consuming four bytes for each.
+    ///
+    /// This is the single-precision version of [`ChaCha8Rand::fill_f64_unit`]: every four bytes
+    /// are read as a little-endian `u32`, and its 24 most significant bits are scaled by
+    /// 2<sup>-24</sup>, i.e., `out[i]` is `(x >> 8) as f32 / 2^24` for the `i`-th `u32` read from
+    /// the stream. All multiples of 2<sup>-24</sup> in `[0, 1)` are equally likely and the
+    /// conversion is exact.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let mut xs = [0.0f32; 1000];
+    /// rng.fill_f32_unit(&mut xs);
+    /// assert_eq!(xs[0], (0x3d4616a5u32 >> 8) as f32 / (1u32 << 24) as f32);
+    /// assert!(xs.iter().all(|x| (0.0..1.0).contains(x)));
+    /// ```
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn fill_f32_unit(&mut self, out: &mut [f32]) {
+        self.fill_converted(out, |bytes| unit_f32_from_bits(u32::from_le_bytes(bytes)));
+    }
+
+    /// Fill `out` by converting consecutive `N`-byte chunks of the stream, directly from the
+    /// buffer wherever possible.
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    fn fill_converted<T, const N: usize>(
+        &mut self,
+        mut out: &mut [T],
+        convert: impl Fn([u8; N]) -> T,
+    ) {
+        audit_read!(self, (out.len() * N) as u64);
+        while !out.is_empty() {
+            let available = &self.buf.output()[self.bytes_consumed..];
+            let n = cmp::min(available.len() / N, out.len());
+            if n == 0 {
+                // The next chunk straddles a refill (or the buffer is exhausted).
+                let mut bytes = [0; N];
+                self.read_bytes_impl(&mut bytes);
+                out[0] = convert(bytes);
+                out = &mut out[1..];
+                continue;
+            }
+            for (x, chunk) in out[..n].iter_mut().zip(available.chunks_exact(N)) {
+                *x = convert(chunk.try_into().expect("chunks_exact yields N bytes"));
+            }
+            self.bytes_consumed += n * N;
+            debug_assert!(self.bytes_consumed <= BUF_OUTPUT_LEN);
+            out = &mut out[n..];
+        }
+    }
+
+    /// Consume eight bytes for a uniformly distributed `f64` in `(0, 1]`, so it's safe to take
+    /// the logarithm.
+    #[cfg(any(feature = "distributions", feature = "graphgen"))]
+    #[inline]
+    pub(crate) fn read_unit_f64_nonzero(&mut self) -> f64 {
+        1.0 - self.read_f64()
+    }
+
+    /// Consume 16 bytes for a standard normally distributed `f64`, using the Box-Muller transform.
+    ///
+    /// Only one of the two values the transform produces is used, so every sample consumes the
+    /// same number of bytes and there's no hidden state in the generator.
+    #[cfg(feature = "distributions")]
+    pub(crate) fn read_standard_normal(&mut self) -> f64 {
+        let radius = libm::sqrt(-2.0 * libm::log(self.read_unit_f64_nonzero()));
+        let angle = self.read_f64() * core::f64::consts::TAU;
+        radius * libm::cos(angle)
+    }
+}
@@ -0,0 +1,135 @@
+//! Conversion from and to the state format of Go's `math/rand/v2.ChaCha8`.
+//!
+//! Go's generator consumes its output in 64-bit words and marshals its state as `"chacha8:"`, the
+//! number of words consumed from the current iteration (big-endian `u64`), and the seed of the
+//! current iteration. Since the words are read in little-endian order, the byte offset within the
+//! iteration is just eight times the word count. Go's `Read` method buffers the unread bytes of a
+//! partially consumed word, and `MarshalBinary` prepends them as `"readbuf:"`, a length byte, and
+//! the bytes themselves.
+
+use arrayref::{array_ref, array_refs};
+
+use crate::{seed_from_bytes, Backend, ChaCha8State, RestoreStateError, BUF_OUTPUT_LEN};
+
+const STATE_MAGIC: &[u8; 8] = b"chacha8:";
+const READBUF_MAGIC: &[u8; 8] = b"readbuf:";
+const STATE_LEN: usize = 48;
+
+/// Interop with Go's `math/rand/v2` package.
+impl ChaCha8State {
+    /// Convert the output of `MarshalBinary` on a Go `*rand.ChaCha8` into a snapshot.
+    ///
+    /// Go and this crate implement the same specification, so restoring the snapshot continues
+    /// with exactly the output that the Go generator would have produced next (as bytes from
+    /// `Read` or as little-endian `Uint64` values). Go doesn't count iterations, so the snapshot's
+    /// `iteration` is zero.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `data` isn't in the format Go produces, if the position is out of range, or if the
+    /// buffered bytes of a partially read word don't match the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::{ChaCha8Rand, ChaCha8State};
+    /// // Go: r := rand.NewChaCha8([32]byte([]byte("ABCDEFGHIJKLMNOPQRSTUVWXYZ123456")))
+    /// //     r.Uint64()
+    /// //     data, _ := r.MarshalBinary()
+    /// let mut data = Vec::from(*b"chacha8:");
+    /// data.extend_from_slice(&1u64.to_be_bytes());
+    /// data.extend_from_slice(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    ///
+    /// let state = ChaCha8State::from_go_binary(&data)?;
+    /// let mut rng = ChaCha8Rand::new(&[0; 32]);
+    /// rng.try_restore_state(&state)?;
+    /// assert_eq!(rng.read_u64(), 0x1160af22a66abc3c);
+    /// # Ok::<(), chacha8rand::RestoreStateError>(())
+    /// ```
+    pub fn from_go_binary(data: &[u8]) -> Result<Self, RestoreStateError> {
+        let err = || RestoreStateError { _private: () };
+        let (read_buf, data): (&[u8], &[u8]) = match data.strip_prefix(READBUF_MAGIC) {
+            Some([len, rest @ ..]) => {
+                let len = usize::from(*len);
+                if len > 8 || rest.len() < len {
+                    return Err(err());
+                }
+                rest.split_at(len)
+            }
+            Some([]) => return Err(err()),
+            None => (&[], data),
+        };
+        let data = <&[u8; STATE_LEN]>::try_from(data).map_err(|_| err())?;
+        let (magic, used, seed) = array_refs![data, 8, 8, 32];
+        if magic != STATE_MAGIC {
+            return Err(err());
+        }
+
+        let words_used = u64::from_be_bytes(*used);
+        if words_used > (BUF_OUTPUT_LEN / 8) as u64 {
+            return Err(err());
+        }
+        // Unread bytes of the last word Go consumed don't count as consumed for us.
+        let bytes_consumed = (words_used as usize * 8)
+            .checked_sub(read_buf.len())
+            .ok_or_else(err)?;
+        let state = ChaCha8State {
+            seed: *seed,
+            bytes_consumed: bytes_consumed as u16,
+            iteration: 0,
+        };
+        if !read_buf.is_empty() {
+            let word = state.output_word(words_used as usize - 1);
+            if word[8 - read_buf.len()..] != *read_buf {
+                return Err(err());
+            }
+        }
+        Ok(state)
+    }
+
+    /// Convert this snapshot into the format that `UnmarshalBinary` on a Go `*rand.ChaCha8`
+    /// accepts.
+    ///
+    /// The Go generator continues with the same output as a generator restored from this snapshot.
+    /// The `iteration` field is lost because Go doesn't track it. If the snapshot is in the middle
+    /// of a 64-bit word, the output includes the rest of that word in the format Go uses for its
+    /// `Read` buffer, which requires computing the word.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes_consumed` is out of range, which can't happen for snapshots taken by
+    /// [`ChaCha8Rand::clone_state`][crate::ChaCha8Rand::clone_state].
+    #[cfg(feature = "alloc")]
+    pub fn to_go_binary(&self) -> alloc::vec::Vec<u8> {
+        let bytes_consumed = usize::from(self.bytes_consumed);
+        assert!(
+            bytes_consumed <= BUF_OUTPUT_LEN,
+            "bytes_consumed out of range"
+        );
+        let mut out = alloc::vec::Vec::with_capacity(8 + 1 + 8 + STATE_LEN);
+        let words_used = bytes_consumed.div_ceil(8);
+        let unread = words_used * 8 - bytes_consumed;
+        if unread > 0 {
+            out.extend_from_slice(READBUF_MAGIC);
+            out.push(unread as u8);
+            let word = self.output_word(words_used - 1);
+            out.extend_from_slice(&word[8 - unread..]);
+        }
+        out.extend_from_slice(STATE_MAGIC);
+        out.extend_from_slice(&(words_used as u64).to_be_bytes());
+        out.extend_from_slice(&self.seed);
+        out
+    }
+
+    /// The bytes of the 64-bit word at index `word` in the current iteration's output.
+    fn output_word(&self, word: usize) -> [u8; 8] {
+        let byte_offset = word * 8;
+        let mut group = [0; 256];
+        Backend::detect_best().fill_group(
+            &seed_from_bytes(&self.seed),
+            (byte_offset / 256) as u32,
+            &mut group,
+        );
+        *array_ref![group, byte_offset % 256, 8]
+    }
+}
@@ -0,0 +1,145 @@
+//! Random graphs, e.g., as fixtures for property tests of graph algorithms.
+//!
+//! Graphs are produced as lists of undirected edges `(u, v)` between nodes `0..n`, with `u > v`
+//! and without self-loops or duplicate edges. Both generators only need time proportional to the
+//! number of nodes plus edges, so they're practical for graphs with millions of nodes.
+//!
+//! Requires crate feature `graphgen`.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::ChaCha8Rand;
+
+/// Generate a random graph in the Erdős–Rényi model `G(n, p)`: each of the `n * (n - 1) / 2`
+/// possible edges is present independently with probability `p`.
+///
+/// The edges are produced lazily in lexicographic order of `(u, v)`. Instead of flipping a coin for
+/// every possible edge, the iterator jumps directly to the next present edge by sampling the
+/// geometrically distributed number of absent edges in between (Batagelj and Brandes, "Efficient
+/// generation of large random networks", 2005). Each jump consumes eight bytes, as if by drawing
+/// `U` uniformly from `(0, 1]` and skipping `floor(ln(U) / ln(1 - p))` edges. So sparse graphs
+/// are cheap no matter how many nodes they have, and no memory is needed.
+///
+/// # Panics
+///
+/// Panics if `p` isn't between 0 and 1 (inclusive).
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::{graphgen, ChaCha8Rand};
+///
+/// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// // A million nodes with an average degree of about 5.
+/// let edges = graphgen::erdos_renyi(&mut rng, 1_000_000, 5e-6);
+/// for (u, v) in edges.take(10) {
+///     assert!(v < u && u < 1_000_000);
+/// }
+/// ```
+pub fn erdos_renyi(rng: &mut ChaCha8Rand, n: usize, p: f64) -> ErdosRenyi<'_> {
+    assert!(
+        (0.0..=1.0).contains(&p),
+        "edge probability must be in [0, 1]"
+    );
+    ErdosRenyi {
+        rng,
+        n: if p == 0.0 { 0 } else { n },
+        ln_q: libm::log1p(-p),
+        u: 1,
+        v: 0,
+    }
+}
+
+/// The iterator returned by [`erdos_renyi`].
+pub struct ErdosRenyi<'a> {
+    rng: &'a mut ChaCha8Rand,
+    n: usize,
+    /// `ln(1 - p)`, which is negative infinity for `p == 1`.
+    ln_q: f64,
+    /// The next possible edge is `(u, v)`, unless `v >= u` (then it's really further along).
+    u: usize,
+    v: usize,
+}
+
+impl Iterator for ErdosRenyi<'_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.u >= self.n {
+            return None;
+        }
+        // The float-to-int cast saturates, which is fine because huge skips end the iteration.
+        let skip = libm::floor(libm::log(self.rng.read_unit_f64_nonzero()) / self.ln_q) as usize;
+        self.v = self.v.saturating_add(skip);
+        while self.v >= self.u && self.u < self.n {
+            self.v -= self.u;
+            self.u += 1;
+        }
+        if self.u >= self.n {
+            return None;
+        }
+        let edge = (self.u, self.v);
+        self.v += 1;
+        Some(edge)
+    }
+}
+
+impl core::iter::FusedIterator for ErdosRenyi<'_> {}
+
+impl core::fmt::Debug for ErdosRenyi<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ErdosRenyi { .. }")
+    }
+}
+
+/// Generate a random graph in the Barabási–Albert model of preferential attachment.
+///
+/// The graph starts with `m` isolated nodes. Then each new node `u` in `m..n` is connected to
+/// `m` distinct existing nodes, chosen with probability proportional to their current degree
+/// (node `m` is connected to all initial nodes, which all have degree zero). This produces
+/// scale-free graphs with a power-law degree distribution, like many real-world networks.
+///
+/// The result has `(n - m) * m` edges, in the order they were added. Targets are sampled by picking
+/// uniformly random entries from the list of all edge endpoints so far (as if by
+/// [`ChaCha8Rand::read_u64_below`] with bound `2 * edges.len()`), and picking again if the node was
+/// already chosen for the current `u`. This takes linear time and memory in the number of edges
+/// (Batagelj and Brandes, 2005).
+///
+/// # Panics
+///
+/// Panics if `m` is zero or not less than `n`.
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::{graphgen, ChaCha8Rand};
+///
+/// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// let edges = graphgen::barabasi_albert(&mut rng, 1000, 3);
+/// assert_eq!(edges.len(), 997 * 3);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn barabasi_albert(rng: &mut ChaCha8Rand, n: usize, m: usize) -> Vec<(usize, usize)> {
+    assert!(
+        0 < m && m < n,
+        "number of edges per node must be positive and less than the number of nodes"
+    );
+    let mut edges: Vec<(usize, usize)> = Vec::with_capacity((n - m) * m);
+    edges.extend((0..m).map(|v| (m, v)));
+    for u in m + 1..n {
+        let first_new = edges.len();
+        for _ in 0..m {
+            let v = loop {
+                let endpoint = rng.read_u64_below(2 * first_new as u64) as usize;
+                let (a, b) = edges[endpoint / 2];
+                let v = if endpoint % 2 == 0 { a } else { b };
+                if !edges[first_new..].iter().any(|&(_, w)| w == v) {
+                    break v;
+                }
+            };
+            edges.push((u, v));
+        }
+    }
+    edges
+}
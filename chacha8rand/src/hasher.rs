@@ -0,0 +1,213 @@
+use core::{
+    fmt,
+    hash::{BuildHasher, Hasher},
+};
+
+use crate::ChaCha8Rand;
+
+/// A [`BuildHasher`] for `HashMap` and `HashSet` with keys drawn from a [`ChaCha8Rand`].
+///
+/// The standard library's `RandomState` picks fresh random keys for every map, so iteration order
+/// (and anything that depends on it) changes from one run of the program to the next. That's
+/// usually what you want, because it protects against hash flooding, but it gets in the way of
+/// reproducible test runs and simulations. This type draws the keys from a generator instead, so
+/// a map built with the same seed iterates in the same order on every run, while different maps
+/// still get independent, high-quality keys.
+///
+/// The hash function is SipHash-1-3 (the one `RandomState` currently uses) with the two 64-bit
+/// keys from [`ChaCha8RandomState::new`]. Unlike the standard library, this crate commits to the
+/// algorithm, and integers are always hashed as little-endian bytes with `usize` and `isize`
+/// widened to 64 bits, so hashes (and thus iteration orders) are the same on all platforms and
+/// won't change in future releases. They still depend on the `Hash` impls of your key types,
+/// which are up to their authors. Like all keyed hashes, this only resists hash flooding if the
+/// seed is unpredictable.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use chacha8rand::{ChaCha8Rand, ChaCha8RandomState};
+///
+/// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// let mut scores = HashMap::with_hasher(ChaCha8RandomState::new(&mut rng));
+/// scores.insert("alice", 3);
+/// scores.insert("bob", 5);
+/// // The iteration order is the same every time the program runs.
+/// let names: Vec<&str> = scores.keys().copied().collect();
+/// # let _ = names;
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct ChaCha8RandomState {
+    k0: u64,
+    k1: u64,
+}
+
+impl ChaCha8RandomState {
+    /// Draw the keys for a new map from `rng`, consuming 16 bytes.
+    ///
+    /// The keys are two `u64`s read with [`ChaCha8Rand::read_u64`], in order.
+    pub fn new(rng: &mut ChaCha8Rand) -> Self {
+        let k0 = rng.read_u64();
+        let k1 = rng.read_u64();
+        ChaCha8RandomState { k0, k1 }
+    }
+}
+
+impl BuildHasher for ChaCha8RandomState {
+    type Hasher = ChaCha8Hasher;
+
+    #[inline]
+    fn build_hasher(&self) -> ChaCha8Hasher {
+        ChaCha8Hasher::new_with_keys(self.k0, self.k1)
+    }
+}
+
+impl fmt::Debug for ChaCha8RandomState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ChaCha8RandomState { .. }")
+    }
+}
+
+/// The [`Hasher`] created by [`ChaCha8RandomState`], see there for details.
+#[derive(Clone)]
+pub struct ChaCha8Hasher {
+    v: [u64; 4],
+    /// Bytes that don't fill a whole 64-bit word yet, in the low `tail_len` bytes.
+    tail: u64,
+    tail_len: usize,
+    /// Total number of bytes written. Only the lowest byte goes into the hash.
+    len: usize,
+}
+
+impl ChaCha8Hasher {
+    pub(crate) fn new_with_keys(k0: u64, k1: u64) -> Self {
+        ChaCha8Hasher {
+            v: [
+                k0 ^ 0x736f_6d65_7073_6575,
+                k1 ^ 0x646f_7261_6e64_6f6d,
+                k0 ^ 0x6c79_6765_6e65_7261,
+                k1 ^ 0x7465_6462_7974_6573,
+            ],
+            tail: 0,
+            tail_len: 0,
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn absorb(v: &mut [u64; 4], m: u64) {
+        v[3] ^= m;
+        sip_round(v);
+        v[0] ^= m;
+    }
+}
+
+#[inline]
+fn sip_round(v: &mut [u64; 4]) {
+    v[0] = v[0].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(13) ^ v[0];
+    v[0] = v[0].rotate_left(32);
+    v[2] = v[2].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(16) ^ v[2];
+    v[0] = v[0].wrapping_add(v[3]);
+    v[3] = v[3].rotate_left(21) ^ v[0];
+    v[2] = v[2].wrapping_add(v[1]);
+    v[1] = v[1].rotate_left(17) ^ v[2];
+    v[2] = v[2].rotate_left(32);
+}
+
+impl Hasher for ChaCha8Hasher {
+    fn write(&mut self, mut bytes: &[u8]) {
+        self.len = self.len.wrapping_add(bytes.len());
+        if self.tail_len > 0 {
+            let fill = (8 - self.tail_len).min(bytes.len());
+            for (i, &b) in bytes[..fill].iter().enumerate() {
+                self.tail |= u64::from(b) << (8 * (self.tail_len + i));
+            }
+            self.tail_len += fill;
+            bytes = &bytes[fill..];
+            if self.tail_len < 8 {
+                return;
+            }
+            Self::absorb(&mut self.v, self.tail);
+            self.tail = 0;
+            self.tail_len = 0;
+        }
+        let mut words = bytes.chunks_exact(8);
+        for word in &mut words {
+            let m = u64::from_le_bytes(word.try_into().expect("chunks are eight bytes"));
+            Self::absorb(&mut self.v, m);
+        }
+        for (i, &b) in words.remainder().iter().enumerate() {
+            self.tail |= u64::from(b) << (8 * i);
+        }
+        self.tail_len = words.remainder().len();
+    }
+
+    fn finish(&self) -> u64 {
+        let mut v = self.v;
+        let b = ((self.len as u64 & 0xff) << 56) | self.tail;
+        Self::absorb(&mut v, b);
+        v[2] ^= 0xff;
+        for _ in 0..3 {
+            sip_round(&mut v);
+        }
+        v[0] ^ v[1] ^ v[2] ^ v[3]
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+}
+
+impl fmt::Debug for ChaCha8Hasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ChaCha8Hasher { .. }")
+    }
+}
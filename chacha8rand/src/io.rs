@@ -0,0 +1,130 @@
+use core::cmp;
+use std::io;
+
+use crate::ChaCha8Rand;
+
+/// Streaming output into writers. Requires crate feature `std`.
+impl ChaCha8Rand {
+    /// Write the next `n` bytes of output into `writer`.
+    ///
+    /// The bytes are the same as [`ChaCha8Rand::read_bytes`] would produce, but they're passed to
+    /// the writer straight from the internal buffer, one chunk of at most 992 bytes at a time. So
+    /// this needs no intermediate buffer, no matter how large `n` is. You may want to wrap
+    /// `writer` in a `BufWriter` if it's expensive to call `write` with chunks of that size.
+    ///
+    /// If the writer returns an error, it's passed on and the generator's position in the stream
+    /// is somewhere between where it started and where it would have ended up without the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let mut file = Vec::new(); // or std::fs::File, etc.
+    /// rng.write_to(&mut file, 5_000)?;
+    /// assert_eq!(file.len(), 5_000);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn write_to(&mut self, writer: &mut impl io::Write, n: u64) -> io::Result<()> {
+        audit_read!(self, n);
+        let mut remaining = n;
+        while remaining > 0 {
+            if self.bytes_consumed >= self.buf.output().len() {
+                self.refill();
+            }
+            let src = &self.buf.output()[self.bytes_consumed..];
+            let chunk_len = cmp::min(src.len() as u64, remaining) as usize;
+            writer.write_all(&src[..chunk_len])?;
+            self.bytes_consumed += chunk_len;
+            remaining -= chunk_len as u64;
+        }
+        Ok(())
+    }
+}
+
+/// Using the generator as an infinite reader. Requires crate feature `std`.
+///
+/// Every read fills the entire buffer with the same bytes that [`ChaCha8Rand::read_bytes`] would
+/// produce and returns `Ok(buf.len())`, so reads never fail and never come up short. Keep in mind
+/// that the stream never ends: `io::copy` or `read_to_end` will loop forever unless you limit the
+/// reader with [`io::Read::take`].
+///
+/// ```
+/// # use chacha8rand::ChaCha8Rand;
+/// use std::io::{self, Read};
+///
+/// let rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// let mut test_data = Vec::new();
+/// io::copy(&mut rng.take(10_000), &mut test_data)?;
+/// assert_eq!(test_data.len(), 10_000);
+/// # Ok::<(), io::Error>(())
+/// ```
+impl io::Read for ChaCha8Rand {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.read_bytes(buf);
+        Ok(buf.len())
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [io::IoSliceMut<'_>]) -> io::Result<usize> {
+        let mut total = 0;
+        for buf in bufs {
+            self.read_bytes(buf);
+            total += buf.len();
+        }
+        Ok(total)
+    }
+
+    #[inline]
+    fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        self.read_bytes(buf);
+        Ok(())
+    }
+}
+
+/// Using the generator as an infinite buffered reader. Requires crate feature `std`.
+///
+/// The trait methods delegate to the inherent [`ChaCha8Rand::fill_buf`] and
+/// [`ChaCha8Rand::consume`], and `fill_buf` never returns an error or an empty slice.
+impl io::BufRead for ChaCha8Rand {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(ChaCha8Rand::fill_buf(self))
+    }
+
+    #[inline]
+    fn consume(&mut self, amount: usize) {
+        ChaCha8Rand::consume(self, amount);
+    }
+}
+
+/// Seeking within the infinite stream. Requires crate feature `std`.
+///
+/// Positions are the same as in [`ChaCha8Rand::position`] and [`ChaCha8Rand::seek_to`], but have
+/// to fit into a `u64`. The stream has no end, so `SeekFrom::End` is always an error.
+impl io::Seek for ChaCha8Rand {
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            io::SeekFrom::Start(offset) => Some(u128::from(offset)),
+            io::SeekFrom::Current(delta) => self.position().checked_add_signed(i128::from(delta)),
+            io::SeekFrom::End(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "can't seek relative to the end of an infinite stream",
+                ));
+            }
+        };
+        let target = target
+            .and_then(|target| u64::try_from(target).ok())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )
+            })?;
+        self.seek_to(u128::from(target))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+        Ok(target)
+    }
+}
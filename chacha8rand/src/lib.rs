@@ -28,11 +28,16 @@
 //! println!("The coin came up {heads_or_tails}.");
 //! ```
 //!
+//! With one of the `getrandom_*` [crate features](#crate-features), [`ChaCha8Rand::from_entropy`]
+//! packages this pattern into one call and returns the seed along with the generator.
+//!
 //! The best place and format to store the seed will vary, but 64 hex digits is a good default
 //! because it can be copied and pasted as (technically) human-readable text. However, if you want
 //! to let humans *pick a seed by hand* for any reason, then asking them for exactly 64 hex digits
 //! would be a bit rude. For such cases, it's more convenient to accept an UTF-8 string and feed it
-//! into a hash function with 256 bit output, such as SHA-256 or Blake3.
+//! into a hash function with 256 bit output, such as SHA-256 or Blake3. The `sha2`
+//! [crate feature](#crate-features) adds [`ChaCha8Rand::new_from_str`], which does exactly that
+//! with SHA-256, so that everyone who uses it gets the same stream for the same string.
 //!
 //! In any case, once you've created a [`ChaCha8Rand`] instance with an initial seed, you can
 //! consume its output as a sequence of bytes or as stream of 32-bit or 64-bit integers. If you need
@@ -94,27 +99,89 @@
 //!
 //! # <a name="crate-features"></a> Crate Features
 //!
-//! The crate is `no_std` and "no `alloc`" by default. There are currently two crate features you
+//! The crate is `no_std` and "no `alloc`" by default. There are currently a few crate features you
 //! might enable when depending on `chacha8rand`. You can manually add them to Cargo.toml (`features
 //! = [...]` key) or use a command like `cargo add chacha8rand -F rand_core_0_6`. The features are:
 //!
 //! * **`std`**: opts out of `#![no_std]`, enables runtime detection of `target_feature`s for higher
-//!   performance on some targets. It does not (currently) affect the API surface, so ideally
-//!   libraries leave this decision to the top-level binary. For forward compatibility, enabling
-//!   this feature *always* adds a dependency on `std`, even on targets where `std` isn't needed
-//!   today.
+//!   performance on some targets, and implements `std::io` traits such as `io::Read`. Apart from
+//!   those trait impls, it doesn't affect the API surface, so ideally libraries leave this decision
+//!   to the top-level binary. For forward compatibility, enabling this feature *always* adds a
+//!   dependency on `std`, even on targets where `std` isn't needed today.
+//! * **`alloc`**: enables methods that need to allocate memory, e.g., to return a `Vec`. Unlike
+//!   `std`, this *does* add to the API, but it doesn't require anything beyond the `alloc` crate.
+//! * **`audit`**: adds [`ChaCha8Rand::enable_audit_log`] for recording which lines of code
+//!   consumed how much output, to help with hunting down desyncs between replicas of a
+//!   simulation. It makes all read methods `#[track_caller]`, so it's meant for debug builds.
+//!   Implies `alloc`.
+//! * **`borsh`**: implements `BorshSerialize` and `BorshDeserialize` for [`ChaCha8State`], using
+//!   the same encoding as [`ChaCha8State::to_bytes`].
+//! * **`bytemuck`**: adds [`ChaCha8Rand::fill_pod`] for filling slices of any
+//!   [`bytemuck::Pod`](https://docs.rs/bytemuck/1/bytemuck/trait.Pod.html) type with random bytes.
+//! * **`distributions`**: adds the [`distributions`] module with samplers for non-uniform
+//!   distributions such as [`distributions::Gamma`], and methods like `read_normal` for one-off
+//!   samples from common distributions. Together with `alloc`, it also enables weighted sampling
+//!   without replacement and Poisson-disk sampling. This feature (like `graphgen`) pulls in
+//!   [`libm`](https://crates.io/crates/libm) for floating-point math that gives the same results
+//!   on every platform.
+//! * **`graphgen`**: adds the [`graphgen`] module for generating random graphs, e.g., as fixtures
+//!   for property tests of graph algorithms.
+//! * **`getrandom_0_2`** and **`getrandom_0_3`**: add [`ChaCha8Rand::from_entropy`], which seeds
+//!   a new generator from the operating system with `getrandom` v0.2 or v0.3, respectively, and
+//!   also returns the seed. Like with `rand_core`, there's one feature per semver-incompatible
+//!   version, so you can use whichever one the rest of your dependency graph has settled on.
+//! * **`output_digest`**: adds [`ChaCha8Rand::enable_output_digest`] and
+//!   [`ChaCha8Rand::output_digest`] for keeping a running checksum of all output, to compare
+//!   replicas of a simulation cheaply. It adds 24 bytes of bookkeeping to every generator.
+//! * **`quickcheck`**: implements quickcheck's `Arbitrary` (v1) for [`Seed`] and [`ChaCha8State`],
+//!   for property tests over seeds and snapshots. Implies `alloc`.
+//! * **`rand_core_0_5`**: like `rand_core_0_6`, but for `rand_core` v0.5 and `rand` v0.7, for code
+//!   bases that haven't upgraded yet. Both features can be enabled at the same time.
 //! * **`rand_core_0_6`**: implement the `RngCore` and `SeedableRng` traits from `rand_core` v0.6,
 //!   for integration with `rand` v0.8. The upcoming v0.9 release of the rand crates will get
 //!   another feature so that `ChaCha8Rand` can implement both the new and the old versions of these
 //!   traits at the same time.
+//! * **`rkyv`**: implements rkyv's `Archive`, `Serialize`, and `Deserialize` for [`ChaCha8State`].
+//!   Like with any other way of storing snapshots, [`ChaCha8Rand::try_restore_state`] validates
+//!   the deserialized state.
+//! * **`serde`**: implements `Serialize` and `Deserialize` for [`ChaCha8State`] and
+//!   [`ChaCha8Rand`].
+//! * **`sha2`**: adds [`Seed::derive_from_str`] and [`ChaCha8Rand::new_from_str`] for turning
+//!   human-chosen strings into seeds with SHA-256.
+//! * **`stats`**: adds [`ChaCha8Rand::stats`] with counters for how many refills and how many bytes
+//!   of output a generator has produced. It adds 48 bytes of bookkeeping to every generator.
+//! * **`tracing`**: emits trace-level [`tracing`](https://crates.io/crates/tracing) events when a
+//!   generator is re-seeded with [`ChaCha8Rand::set_seed`], restored from a snapshot, or refills
+//!   its buffer, including the iteration index. Comparing these timelines can help with tracking
+//!   down nondeterminism. The events never include seeds or output.
+//! * **`macros`**: adds derive macros for [`Random`], so you can get values of your own types with
+//!   [`ChaCha8Rand::sample`]: `#[derive(Random)]` for structs and `#[derive(UniformEnum)]` for
+//!   fieldless enums.
+//! * **`ndarray`**: adds methods for filling `ndarray` arrays (v0.16) with random numbers, visiting
+//!   the elements in logical order so the result doesn't depend on memory layout. Together with
+//!   `distributions`, this includes normally distributed numbers.
+//! * **`nanorand`**: implements the `Rng` and `SeedableRng` traits from
+//!   [`nanorand`](https://crates.io/crates/nanorand) v0.7, so [`ChaCha8Rand`] can be passed to code
+//!   that's generic over nanorand's generators.
+//! * **`uuid`**: adds methods for generating random (v4) and time-ordered (v7) UUIDs with the
+//!   [`uuid`](https://crates.io/crates/uuid) crate.
+//! * **`zerocopy`**: adds methods for creating and filling values of any
+//!   [`zerocopy::FromBytes`](https://docs.rs/zerocopy/0.8/zerocopy/trait.FromBytes.html) type.
+//! * **`zeroize`**: implements `Zeroize` for [`ChaCha8Rand`], [`ChaCha8State`], and [`Seed`], and
+//!   wipes the seeds and buffer of every [`ChaCha8Rand`] when it's dropped. This crate isn't meant
+//!   for cryptography, but this can still help with policies that require wiping key material.
 //!
-//! Neither feature is enabled by default, so you don't need `no-default-features = true` / `cargo
-//! add --no-default-features`. In fact, please don't, because then your code might break if a later
-//! version moves existing functionality under a new on-by-default feature.
+//! None of these features are enabled by default, so you don't need `no-default-features = true` /
+//! `cargo add --no-default-features`. In fact, please don't, because then your code might break if
+//! a later version moves existing functionality under a new on-by-default feature.
 //!
 //! There are also some features with an "unstable" prefix in their name. Anything covered by these
 //! is for internal use only (e.g., the crate's benchmarks are compiled as a separate crate) and
-//! explicitly not covered by SemVer.
+//! explicitly not covered by SemVer. Currently, `unstable_internals` doesn't enable anything, but
+//! it's kept around for future use. The exception is `unstable_portable_simd`, which you can
+//! enable on a nightly compiler to get a `core::simd` backend on targets that don't have a
+//! hand-written SIMD backend (see [SIMD Backends](ChaCha8Rand#simd-backends)). It's unstable
+//! because `core::simd` is, so it may stop compiling with newer nightlies until it's fixed.
 //!
 //! # Minimum Supported Rust Version (MSRV)
 //!
@@ -127,9 +194,9 @@
 //!
 //! The main reasons why you might not want to use this crate are the use of `unsafe` for accessing
 //! SIMD intrinsics and the relatively large buffer (4x larger than the Go implementation). The
-//! latter means each RNG instance is a little over a thousand bytes large, which may be an issue if
-//! you want to have many instances and care about memory consumption and/or only consume a small
-//! amount of randomness from most of those instances.
+//! latter means each RNG instance is about 1.1 KiB large (more with the `audit`, `output_digest`,
+//! and `stats` features), which may be an issue if you want to have many instances and care about memory
+//! consumption and/or only consume a small amount of randomness from most of those instances.
 //!
 //! [getrandom]: https://crates.io/crates/getrandom
 //! [go-blog]: https://go.dev/blog/chacha8rand
@@ -137,7 +204,13 @@
 #![forbid(unsafe_op_in_unsafe_fn)]
 #![warn(missing_docs)]
 #![no_std]
-use core::{array, cmp, error::Error, fmt};
+use core::{
+    array, cmp,
+    error::Error,
+    fmt, iter,
+    mem::MaybeUninit,
+    ptr::{self, addr_of_mut},
+};
 
 // Currently, we only *need* `std` on x86 for runtime feature detection. But later versions might
 // use runtime detection on more platforms, or implement traits that require `std`. It would suck if
@@ -146,26 +219,239 @@ use core::{array, cmp, error::Error, fmt};
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use arrayref::array_ref;
+#[cfg(feature = "audit")]
+use audit::AuditLog;
+#[cfg(feature = "output_digest")]
+use digest::OutputDigest;
+#[cfg(feature = "stats")]
+use stats::Counters;
 
-mod backend;
-mod common_guts;
+/// Record a read in the generator's audit log if the feature is enabled, otherwise do nothing.
+macro_rules! audit_read {
+    ($rng:expr, $len:expr) => {
+        #[cfg(feature = "audit")]
+        $rng.audit_read($len);
+    };
+}
+
+/// Update the counters behind `ChaCha8Rand::stats` if the feature is enabled, otherwise do nothing.
+macro_rules! count {
+    ($rng:expr, $($call:tt)+) => {
+        #[cfg(feature = "stats")]
+        $rng.counters.$($call)+;
+    };
+}
+
+/// Keep the digest behind `ChaCha8Rand::output_digest` in sync if the feature is enabled, otherwise
+/// do nothing.
+macro_rules! digest {
+    ($($call:tt)+) => {
+        #[cfg(feature = "output_digest")]
+        $($call)+;
+    };
+}
+
+/// Emit a trace-level event with `tracing` if the feature is enabled, otherwise do nothing.
+macro_rules! trace_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::trace!($($arg)*);
+    };
+}
+
+#[cfg(feature = "alloc")]
+mod alias;
+#[cfg(feature = "audit")]
+mod audit;
+mod batch;
+#[cfg(feature = "borsh")]
+mod borsh;
+mod builder;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+mod const_bytes;
+#[cfg(feature = "output_digest")]
+mod digest;
+#[cfg(feature = "distributions")]
+pub mod distributions;
+mod encoding;
+#[cfg(any(feature = "getrandom_0_2", feature = "getrandom_0_3"))]
+mod entropy;
+mod erased;
+mod facade;
+mod float;
+mod go;
+#[cfg(feature = "graphgen")]
+pub mod graphgen;
+mod hasher;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "nanorand")]
+mod nanorand;
+#[cfg(feature = "ndarray")]
+mod ndarray;
+mod net;
+mod permutation;
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+mod poisson_disk;
+mod position;
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+#[cfg(feature = "rand_core_0_5")]
+mod rand_core_0_5;
 #[cfg(feature = "rand_core_0_6")]
 mod rand_core_0_6;
-mod scalar;
+mod random;
+#[cfg(feature = "alloc")]
+mod rng_set;
+mod sample;
+mod seed;
+mod seed_cache;
+#[cfg(feature = "alloc")]
+mod seed_sequence;
+mod seq;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "sha2")]
+mod sha2;
+#[cfg(feature = "stats")]
+mod stats;
+mod storage;
+mod stratified;
 #[cfg(test)]
 mod tests;
+mod text;
+mod uniform;
+#[cfg(feature = "uuid")]
+mod uuid;
+#[cfg(feature = "zerocopy")]
+mod zerocopy;
+#[cfg(feature = "zeroize")]
+mod zeroize;
 
-#[cfg(feature = "unstable_internals")]
-pub use backend::Backend;
-#[cfg(not(feature = "unstable_internals"))]
-use backend::Backend;
+#[cfg(feature = "alloc")]
+pub use alias::WeightedAlias;
+#[cfg(feature = "audit")]
+pub use audit::AuditEntry;
+pub use builder::ChaCha8RandBuilder;
+pub use encoding::ParseStateError;
+#[cfg(any(feature = "getrandom_0_2", feature = "getrandom_0_3"))]
+pub use entropy::EntropyError;
+pub use erased::ErasedRng;
+pub use hasher::{ChaCha8Hasher, ChaCha8RandomState};
+pub use permutation::LazyPermutation;
+pub use position::StreamPosition;
+pub use random::Random;
+#[cfg(feature = "alloc")]
+pub use rng_set::{RngSet, RngSetState};
+pub use seed::Seed;
+pub use seed_cache::SeedCache;
+#[cfg(feature = "alloc")]
+pub use seed_sequence::SeedSequence;
+#[cfg(feature = "stats")]
+pub use stats::Stats;
+pub use storage::Storage;
+pub use uniform::SampleRange;
 
-const BUF_TOTAL_LEN: usize = 1024;
-const BUF_OUTPUT_LEN: usize = BUF_TOTAL_LEN - 32;
+/// Derive [`Random`] for a struct by sampling each field in declaration order.
+///
+/// Every field type must implement [`Random`] itself, which includes all fixed-size integers,
+/// `bool`, `f32` and `f64` (uniform in `[0, 1)`), arrays of `Random` types, and other types that
+/// derive it. Integer fields can instead be restricted to a range with an attribute like
+/// `#[random(range = 1..=6)]`. The range is an arbitrary expression of type `Range` or
+/// `RangeInclusive`, which is evaluated every time the struct is sampled, and sampling from it is
+/// unbiased. Empty ranges cause a panic.
+///
+/// For generic structs, the derived impl requires every type parameter to implement [`Random`].
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::{ChaCha8Rand, Random};
+///
+/// #[derive(Random, Debug)]
+/// struct Config {
+///     #[random(range = 1..=16)]
+///     threads: u32,
+///     verbose: bool,
+///     weights: [f64; 3],
+///     #[random(range = -10..10)]
+///     offset: i8,
+/// }
+///
+/// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// let config: Config = rng.sample();
+/// assert!((1..=16).contains(&config.threads));
+/// assert!((-10..10).contains(&config.offset));
+/// ```
+#[cfg(feature = "macros")]
+pub use chacha8rand_macros::Random;
+
+/// Derive [`Random`] for a fieldless enum, picking each variant with the same probability.
+///
+/// Sampling consumes a `u32` from the generator (very rarely more, to avoid bias) and maps it to
+/// one of the variants in declaration order. Adding, removing or reordering variants thus changes
+/// which values you get for a given seed. The enum must have at least one variant and none of the
+/// variants may have fields. Explicit discriminants are fine but don't affect sampling.
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::{ChaCha8Rand, UniformEnum};
+///
+/// #[derive(UniformEnum, Debug, PartialEq)]
+/// enum Suit {
+///     Clubs,
+///     Diamonds,
+///     Hearts,
+///     Spades,
+/// }
+///
+/// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// let suit: Suit = rng.sample();
+/// # let _ = suit;
+/// ```
+#[cfg(feature = "macros")]
+pub use chacha8rand_macros::UniformEnum;
+
+// Implementation details of the derive macros. Not public API!
+#[doc(hidden)]
+pub mod __private {
+    use crate::ChaCha8Rand;
+
+    pub use crate::uniform::SampleRange;
+
+    #[inline]
+    pub fn read_u32_below(rng: &mut ChaCha8Rand, n: u32) -> u32 {
+        rng.read_u32_below(n)
+    }
+
+    #[inline]
+    pub fn sample_range<T, R: SampleRange<T>>(rng: &mut ChaCha8Rand, range: R) -> T {
+        range.sample_range(rng)
+    }
+}
+
+// Lets the tests use the derive macros, which refer to `::chacha8rand`.
+#[cfg(all(test, feature = "macros"))]
+extern crate self as chacha8rand;
+
+pub use chacha8rand_core::{Backend, Buffer};
+
+const BUF_OUTPUT_LEN: usize = Buffer::OUTPUT_LEN;
 
 /// A deterministic stream of pseudorandom bytes from a 32-byte seed.
 ///
+/// This is the generic type behind [`ChaCha8Rand`], which is what you should use unless you've
+/// got a specific reason not to. `ROUNDS` is the number of ChaCha rounds per block, and the spec
+/// only covers eight. See [below](#other-round-counts) for the off-spec variants
+/// [`ChaCha12Rand`] and [`ChaCha20Rand`]. Everything else on this page applies to all of them,
+/// even where it only talks about [`ChaCha8Rand`].
+///
 /// See the crate documentation for a higher-level introduction and quick-start examples. Here
 /// you'll only find excessive extra details about reproducibility and some notes about
 /// (de-)serialization and SIMD backends.
@@ -228,14 +514,54 @@ const BUF_OUTPUT_LEN: usize = BUF_TOTAL_LEN - 32;
 /// Unlike Go (version 1.23), this crate also uses SIMD on 32-bit x86 targets and Webassembly with
 /// the `simd128` feature. There's also a AVX2 backend for 256-bit SIMD on x86 and x86_64. This
 /// backend uses runtime feature detection (if the `std` feature is enabled) so you don't have to
-/// fiddle with `-Ctarget-feature` and risk the program not working on some older CPUs. Other
-/// instruction sets and more runtime feature detection may be added in the future.
+/// fiddle with `-Ctarget-feature` and risk the program not working on some older CPUs. The same
+/// goes for the SSSE3 backend, which improves on SSE2 for CPUs that don't have AVX2. Other
+/// instruction sets and more runtime feature detection may be added in the future. On nightly,
+/// the `unstable_portable_simd` feature adds a `core::simd` backend for the remaining targets.
+///
+/// The backends are available as [`Backend`] if you want to pick one yourself (see
+/// [`ChaCha8Rand::with_backend`]) or drive them directly with a [`Buffer`], e.g., to implement a
+/// different buffering strategy on top of the same code. They live in the `chacha8rand-core`
+/// crate and are re-exported here.
+///
+/// # <a name="other-round-counts"></a> Other Round Counts
+///
+/// [`ChaCha12Rand`] and [`ChaCha20Rand`] work exactly like [`ChaCha8Rand`], except that every
+/// ChaCha block is computed with 12 or 20 rounds instead of eight. **They're not covered by the
+/// spec**, so other ChaCha8Rand implementations can't reproduce their output, and the output of a
+/// given seed may change if a future version of the spec defines such variants differently. But
+/// they share all the stream semantics described above: the same iteration structure and key
+/// erasure, the same byte order and read granularity rules, snapshots, positions, seeking, and
+/// stream IDs. The extra rounds make them slower (roughly by the ratio of round counts) and don't
+/// make the output any more useful for non-cryptographic purposes. They're only here for people
+/// who need a more conservative security margin to satisfy some policy, or who want to compare
+/// results with ChaCha12- or ChaCha20-based generators. `ROUNDS` can only be 8, 12, or 20; other
+/// values are rejected at compile time when a generator is created.
+///
+/// The core methods on this page are available for all variants. Most of the extras (e.g.,
+/// [`ChaCha8Rand::builder`], [`SeedCache`], the trait impls for `rand_core` and `serde`, and the
+/// sampling helpers) are only implemented for [`ChaCha8Rand`], and so are helpers like
+/// [`ChaCha8Rand::from_entropy`] that construct a generator. Snapshots ([`ChaCha8State`]) don't
+/// record the number of rounds, so only restore them into a generator of the same variant.
+///
+/// ```
+/// use chacha8rand::{ChaCha20Rand, ChaCha8Rand};
+///
+/// let seed = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+/// let mut rng = ChaCha20Rand::new(seed);
+/// assert_ne!(rng.read_u64(), ChaCha8Rand::new(seed).read_u64());
+/// let state = rng.clone_state();
+/// let next = rng.read_u64();
+/// rng.try_restore_state(&state)?;
+/// assert_eq!(rng.read_u64(), next);
+/// # Ok::<(), chacha8rand::RestoreStateError>(())
+/// ```
 ///
 /// [aarch64be-neon]: https://github.com/rust-lang/stdarch/issues/1484
 /// [crate-features]: ./index.html#crate-features
 /// [spec]: https://c2sp.org/chacha8rand
 #[derive(Clone)]
-pub struct ChaCha8Rand {
+pub struct ChaChaRand<const ROUNDS: usize> {
     backend: Backend,
     seed: [u32; 8],
     /// Position in `buf.output()` of the next byte to produce as output. Should be equal to
@@ -243,23 +569,81 @@ pub struct ChaCha8Rand {
     /// and *shouldn't* occur, but we never rely on this for safety and most other code also tries
     /// to handle larger values gracefully.
     bytes_consumed: usize,
+    /// Index of the iteration whose output is in `buf`, counted from the last (re-)seeding. Only
+    /// used for [`ChaCha8Rand::position`], snapshots, and seeking. Increments wrap around, but
+    /// that only happens if a snapshot claiming an absurdly large iteration is restored.
+    iteration: u64,
+    /// The earliest iteration we know the seed of, so that [`ChaCha8Rand::seek_to`] can go
+    /// backwards. That's iteration zero, unless the generator was restored from a snapshot. The
+    /// seed is also exposed as [`ChaCha8Rand::initial_seed`].
+    origin_seed: [u32; 8],
+    origin_iteration: u64,
+    #[cfg(feature = "stats")]
+    counters: Counters,
+    #[cfg(feature = "output_digest")]
+    digest: OutputDigest,
+    #[cfg(feature = "audit")]
+    audit: AuditLog,
     buf: Buffer,
 }
 
-impl fmt::Debug for ChaCha8Rand {
+/// The generator described by the [ChaCha8Rand specification][spec], see [`ChaChaRand`].
+///
+/// [spec]: https://c2sp.org/chacha8rand
+pub type ChaCha8Rand = ChaChaRand<8>;
+
+/// Like [`ChaCha8Rand`], but with 12 rounds. Not covered by the spec, see
+/// [Other Round Counts](ChaChaRand#other-round-counts).
+pub type ChaCha12Rand = ChaChaRand<12>;
+
+/// Like [`ChaCha8Rand`], but with 20 rounds. Not covered by the spec, see
+/// [Other Round Counts](ChaChaRand#other-round-counts).
+pub type ChaCha20Rand = ChaChaRand<20>;
+
+impl<const ROUNDS: usize> fmt::Debug for ChaChaRand<ROUNDS> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("ChaCha8Rand { .. }")
+        write!(f, "ChaCha{ROUNDS}Rand {{ .. }}")
+    }
+}
+
+/// Two generators are equal if they're at the same position of the same stream, i.e., if they'll
+/// produce the same output from now on and [`ChaCha8Rand::position`] agrees.
+///
+/// Only this logical state is compared. The buffered output is determined by it, and the backend
+/// doesn't affect the output. Whether the generator has already refilled its buffer at the end of
+/// an iteration doesn't matter either.
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::{Backend, ChaCha8Rand};
+///
+/// let seed = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+/// let mut a = ChaCha8Rand::new(seed);
+/// let mut b = ChaCha8Rand::with_backend(seed, Backend::scalar());
+/// a.read_u64();
+/// assert_ne!(a, b);
+/// b.read_bytes(&mut [0; 8]);
+/// assert_eq!(a, b);
+/// ```
+impl<const ROUNDS: usize> PartialEq for ChaChaRand<ROUNDS> {
+    fn eq(&self, other: &Self) -> bool {
+        self.logical_state() == other.logical_state() && self.stream() == other.stream()
     }
 }
 
+impl<const ROUNDS: usize> Eq for ChaChaRand<ROUNDS> {}
+
 /// Snapshot of the state of a [`ChaCha8Rand`] instance.
 ///
 /// Created with [`ChaCha8Rand::clone_state`] and used by [`ChaCha8Rand::try_restore_state`]. It
-/// simply records the seed of the current iteration of the generator and how many output bytes of
-/// that iteration have already been consumed. Restoring from it is effectively the same as calling
-/// `rng.set_seed(&seed)` and then throwing away `bytes_consumed` many bytes of output. However,
-/// going through [`ChaCha8Rand::try_restore_state`] can catch some possible mistakes because it
-/// validates that `bytes_consumed` is in the range it should be.
+/// simply records the seed of the current iteration of the generator, how many output bytes of
+/// that iteration have already been consumed, and how many iterations came before it. Restoring
+/// from it is effectively the same as calling `rng.set_seed(&seed)` and then throwing away
+/// `bytes_consumed` many bytes of output, except that [`ChaCha8Rand::position`] continues counting
+/// from the snapshot's position. However, going through [`ChaCha8Rand::try_restore_state`] can
+/// catch some possible mistakes because it validates that `bytes_consumed` is in the range it
+/// should be.
 ///
 /// Possible use cases include:
 ///
@@ -269,9 +653,13 @@ impl fmt::Debug for ChaCha8Rand {
 /// * Forking a randomized algorithm, running it twice with the same randomness but handling
 ///   different input, to see how they diverge (e.g., "what if" queries).
 ///
-/// There are no `serde` impls. Instead, the fields are public so you can (de-)serialize them in any
+/// With the `serde`, `rkyv`, or `borsh` features, this type implements the respective
+/// serialization traits. Otherwise, the fields are public so you can (de-)serialize them in any
 /// way you see fit. In this case you should be prepared to handle errors due to out-of-range
-/// `bytes_consumed` values gracefully.
+/// `bytes_consumed` values gracefully. If you don't need a particular format, the canonical binary
+/// encoding from [`ChaCha8State::to_bytes`] and [`ChaCha8State::from_bytes`] saves you the
+/// trouble. For humans, there's also a compact text format via the `Display` and `FromStr` impls,
+/// see below.
 ///
 /// Nothing stops you from constructing a [`ChaCha8State`] out of thin air (rather than cloning from
 /// an existing generator), but there's probably no reason to do so. You can supply a new seed
@@ -294,42 +682,77 @@ impl fmt::Debug for ChaCha8Rand {
 /// rng.try_restore_state(&state).expect("snapshot is valid because it was not modified");
 /// assert_eq!(rng.read_u64(), first_output);
 /// ```
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
+)]
 pub struct ChaCha8State {
     /// The seed of the current ChaCha8Rand iteration.
     pub seed: [u8; 32],
     /// How much output from the current ChaCha8Rand iteration was already consumed.
     pub bytes_consumed: u16,
+    /// The index of the current iteration, counted from the seed the generator was created with
+    /// (or last re-seeded with). Together with `bytes_consumed`, this gives the snapshot's absolute
+    /// position in the stream, see [`ChaCha8State::position`].
+    pub iteration: u64,
 }
 
-impl fmt::Debug for ChaCha8State {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("ChaCha8State { .. }")
+impl ChaCha8State {
+    /// The absolute position of this snapshot in the stream, as counted by
+    /// [`ChaCha8Rand::position`].
+    ///
+    /// Snapshots taken from the same generator (without re-seeding in between) can be ordered and
+    /// compared by their positions, and the difference between two positions is the number of
+    /// bytes consumed between the two snapshots.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let before = rng.clone_state();
+    /// rng.read_bytes(&mut [0; 5000]);
+    /// let after = rng.clone_state();
+    /// assert_eq!(after.position() - before.position(), 5000);
+    /// ```
+    #[inline]
+    pub fn position(&self) -> u128 {
+        // A fully consumed iteration isn't a canonical `StreamPosition`, so count from its start.
+        StreamPosition::iteration_start(self.iteration).to_byte_offset()
+            + u128::from(self.bytes_consumed)
     }
-}
 
-// None of the backends currently require this alignment for soundness, but SIMD memory accesses
-// that cross 32- or 64-byte boundaries are slightly slower on a bunch of CPUs, so higher alignment
-// is occasionally useful. Since we don't do 512-bit SIMD, 32-byte alignment is sufficient.
-#[repr(align(32))]
-#[derive(Clone)]
-struct Buffer {
-    bytes: [u8; BUF_TOTAL_LEN],
-}
-
-impl Buffer {
+    /// The position of this snapshot as a [`StreamPosition`], or `None` if `bytes_consumed` is out
+    /// of range (so [`ChaCha8Rand::try_restore_state`] would reject the snapshot) or the position
+    /// is past [`StreamPosition::MAX`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::{ChaCha8Rand, StreamPosition};
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// rng.read_bytes(&mut [0; 1000]);
+    /// let state = rng.clone_state();
+    /// assert_eq!(state.stream_position(), StreamPosition::new(1, 8));
+    /// ```
     #[inline]
-    fn output(&self) -> &[u8; BUF_OUTPUT_LEN] {
-        array_ref![&self.bytes, 0, BUF_OUTPUT_LEN]
+    pub fn stream_position(&self) -> Option<StreamPosition> {
+        if usize::from(self.bytes_consumed) > BUF_OUTPUT_LEN {
+            return None;
+        }
+        StreamPosition::iteration_start(self.iteration).checked_add(u128::from(self.bytes_consumed))
     }
+}
 
-    #[inline]
-    fn new_key(&self) -> &[u8; 32] {
-        array_ref![&self.bytes, BUF_OUTPUT_LEN, 32]
+impl fmt::Debug for ChaCha8State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ChaCha8State { .. }")
     }
 }
 
-/// Error returned from [`ChaCha8Rand::try_restore_state`] for corrupted snapshots.
+/// Error returned from [`ChaCha8Rand::try_restore_state`] and [`ChaCha8State::from_bytes`] for
+/// corrupted snapshots.
 pub struct RestoreStateError {
     _private: (),
 }
@@ -348,13 +771,35 @@ impl fmt::Display for RestoreStateError {
 
 impl Error for RestoreStateError {}
 
-impl ChaCha8Rand {
+/// Error returned from [`ChaCha8Rand::seek_to`] for positions it can't reach.
+pub struct SeekError {
+    _private: (),
+}
+
+impl fmt::Debug for SeekError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SeekError")
+    }
+}
+
+impl fmt::Display for SeekError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("stream position out of reachable range")
+    }
+}
+
+impl Error for SeekError {}
+
+impl<const ROUNDS: usize> ChaChaRand<ROUNDS> {
     /// Create a new generator from the given seed.
     ///
     /// This will eagerly generates data to fill the generator's internal buffer. Therefore, it may
     /// be a bit wasteful to call if you won't actually need any output from the generator. Don't
     /// over-complicate your program to avoid that, but keep it in mind if in case it's easy to
-    /// avoid.
+    /// avoid. If you construct many generators from the *same* seed, a [`SeedCache`] can avoid
+    /// recomputing the same data over and over. If you need control over where the generator's
+    /// memory lives, see [`ChaCha8Rand::new_in`]. For other options, such as deferring the work
+    /// until the first output is needed ([`ChaCha8Rand::new_lazy`]), see [`ChaCha8Rand::builder`].
     ///
     /// # Examples
     ///
@@ -372,37 +817,146 @@ impl ChaCha8Rand {
     /// [spec]: https://c2sp.org/chacha8rand
     #[inline]
     pub fn new(seed: &[u8; 32]) -> Self {
-        // On x86, we prefer AVX2 over SSE2 when both are available. The other SIMD backends aren't
-        // really ordered by preference because they're for mutually exclusive target platforms, but
-        // it's less of a mess to chain them like this than to replicate the `cfg` soup. We only use
-        // the scalar backend if none of the SIMD backends are available.
-        let backend = avx2::detect()
-            .or_else(sse2::detect)
-            .or_else(neon::detect)
-            .or_else(simd128::detect)
-            .unwrap_or_else(scalar::backend);
-        Self::with_backend_impl(seed, backend)
+        Self::with_backend_impl(seed, Backend::detect_best())
+    }
+
+    /// Create a new generator from a small integer seed.
+    ///
+    /// This is a shorthand for `ChaCha8Rand::new(&Seed::from_u64(x).0)`. See [`Seed::from_u64`]
+    /// for how the integer is expanded into 32 bytes. The expansion is fixed and documented, so
+    /// the same `x` gives the same stream everywhere, with or without the `rand_core` integration.
+    #[inline]
+    pub fn new_from_u64(x: u64) -> Self {
+        Self::new(&Seed::from_u64(x).0)
+    }
+
+    /// Like [`ChaCha8Rand::new`], but defers filling the buffer until the first output is needed.
+    ///
+    /// This is a shorthand for `ChaCha8Rand::builder(seed).lazy(true).build()`. It makes creating
+    /// a generator almost free, which helps if you create many of them up front and only end up
+    /// using some. The generator still takes up the same amount of memory, and the first read
+    /// (or peek, skip, etc.) costs as much as [`ChaCha8Rand::new`] would have. Apart from that,
+    /// it behaves exactly like one created with [`ChaCha8Rand::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new_lazy(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// // The buffer is only filled here:
+    /// assert_eq!(rng.read_u64(), 0xb773b6063d4616a5);
+    /// ```
+    #[inline]
+    pub fn new_lazy(seed: &[u8; 32]) -> Self {
+        let mut this = MaybeUninit::uninit();
+        Self::init_in(&mut this, seed, Backend::detect_best(), true);
+        // SAFETY: `init_in` initialized it.
+        unsafe { this.assume_init() }
     }
 
-    #[cfg(feature = "unstable_internals")]
-    #[allow(
-        missing_docs,
-        reason = "internal API only exposed unstably for benchmarks"
-    )]
+    /// Like [`ChaCha8Rand::new`], but with a specific [`Backend`] instead of the best one for the
+    /// current CPU.
+    ///
+    /// All backends produce the same output, so this is only useful for comparing their
+    /// performance, or for ruling out a backend when you suspect it misbehaves on some machine.
     #[inline]
     pub fn with_backend(seed: &[u8; 32], backend: Backend) -> Self {
         Self::with_backend_impl(seed, backend)
     }
 
+    /// Create a generator for one of `2^64` separate streams from the same seed.
+    ///
+    /// **This does not produce the ChaCha8Rand stream described by the spec**, except for stream
+    /// ID zero, which is the same as [`ChaCha8Rand::new`]. Other implementations of ChaCha8Rand,
+    /// like the one in Go's standard library, can't reproduce the output of any other stream.
+    ///
+    /// The stream ID goes into the words of the ChaCha8 state that the spec leaves at zero (see
+    /// [`Backend::with_stream`] for the details). So different stream IDs are like different
+    /// ChaCha8 nonces: the streams are guaranteed to never overlap, no matter how much output is
+    /// consumed from each of them, and unlike with seeds derived from a common root seed, this
+    /// doesn't rely on any probabilistic argument. That's useful for massively parallel Monte
+    /// Carlo simulations, where every worker can use the same seed with its own index as the
+    /// stream ID.
+    ///
+    /// Everything else works as usual, and the generator stays on its stream when it's re-seeded
+    /// with [`ChaCha8Rand::set_seed`] or switched to another backend with
+    /// [`ChaCha8Rand::set_backend`]. However, snapshots ([`ChaCha8State`]) don't record the stream
+    /// ID, so only restore them into a generator for the same stream. For the same reason,
+    /// serializing a generator with a non-zero stream ID with `serde` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let seed = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+    /// let mut spec = ChaCha8Rand::new(seed);
+    /// let mut stream0 = ChaCha8Rand::with_stream(seed, 0);
+    /// let mut stream1 = ChaCha8Rand::with_stream(seed, 1);
+    /// assert_eq!(stream0.read_u64(), spec.read_u64());
+    /// assert_ne!(stream1.read_u64(), 0xb773b6063d4616a5);
+    /// assert_eq!(stream1.stream(), 1);
+    /// ```
+    pub fn with_stream(seed: &[u8; 32], stream_id: u64) -> Self {
+        Self::with_backend_impl(seed, Backend::detect_best().with_stream(stream_id))
+    }
+
+    /// The stream ID this generator was created with by [`ChaCha8Rand::with_stream`], or zero for
+    /// the spec's stream.
+    pub fn stream(&self) -> u64 {
+        self.backend.stream()
+    }
+
     fn with_backend_impl(seed: &[u8; 32], backend: Backend) -> Self {
-        let mut this = ChaCha8Rand {
-            seed: [0; 8],
-            bytes_consumed: 0,
-            buf: Buffer { bytes: [0; 1024] },
-            backend,
-        };
-        this.set_seed(seed);
-        this
+        let mut this = MaybeUninit::uninit();
+        Self::init_in(&mut this, seed, backend, false);
+        // SAFETY: `init_in` initialized it.
+        unsafe { this.assume_init() }
+    }
+
+    /// Initialize a generator in place. This avoids zeroing the buffer (which the first refill
+    /// would immediately overwrite anyway) and doesn't need a temporary on the stack.
+    ///
+    /// If `lazy` is true, the first refill is deferred until the first output is needed. The
+    /// generator then looks like it has just consumed all output of the iteration before the
+    /// first one, and the buffer only holds the seed as key for the "next" iteration. See
+    /// [`ChaCha8Rand::is_unfilled`] for what that means for the other fields.
+    fn init_in<'a>(
+        slot: &'a mut MaybeUninit<Self>,
+        seed: &[u8; 32],
+        backend: Backend,
+        lazy: bool,
+    ) -> &'a mut Self {
+        let key = seed_from_bytes(seed);
+        let ptr = slot.as_mut_ptr();
+        // SAFETY: `ptr` is valid for writes and properly aligned because it comes from a
+        // `MaybeUninit<Self>`. The cast from `*mut Buffer` to `*mut MaybeUninit<Buffer>`
+        // preserves these properties. Every field is initialized before we assert that the whole
+        // struct is.
+        unsafe {
+            addr_of_mut!((*ptr).backend).write(backend);
+            addr_of_mut!((*ptr).seed).write(key);
+            addr_of_mut!((*ptr).origin_seed).write(key);
+            addr_of_mut!((*ptr).origin_iteration).write(0);
+            #[cfg(feature = "stats")]
+            addr_of_mut!((*ptr).counters).write(Counters::new(u64::from(!lazy)));
+            #[cfg(feature = "audit")]
+            addr_of_mut!((*ptr).audit).write(AuditLog::disabled());
+            if lazy {
+                addr_of_mut!((*ptr).bytes_consumed).write(BUF_OUTPUT_LEN);
+                #[cfg(feature = "output_digest")]
+                addr_of_mut!((*ptr).digest).write(OutputDigest::disabled(BUF_OUTPUT_LEN));
+                addr_of_mut!((*ptr).iteration).write(u64::MAX);
+                addr_of_mut!((*ptr).buf).write(Buffer::with_new_key(seed));
+            } else {
+                addr_of_mut!((*ptr).bytes_consumed).write(0);
+                #[cfg(feature = "output_digest")]
+                addr_of_mut!((*ptr).digest).write(OutputDigest::disabled(0));
+                addr_of_mut!((*ptr).iteration).write(0);
+                let buf = &mut *addr_of_mut!((*ptr).buf).cast::<MaybeUninit<Buffer>>();
+                backend.refill_uninit_with_rounds::<ROUNDS>(&key, buf);
+            }
+            slot.assume_init_mut()
+        }
     }
 
     /// Reset this generator, as if overwriting it with `ChaCha8Rand::new(seed)`.
@@ -429,12 +983,136 @@ impl ChaCha8Rand {
     /// let result_again = run_simulation(&mut rng);
     /// assert_eq!(result, result_again);
     /// ```
-    pub fn set_seed(self: &mut ChaCha8Rand, seed: &[u8; 32]) {
+    pub fn set_seed(&mut self, seed: &[u8; 32]) {
+        self.reseed(seed);
+        trace_event!("set_seed");
+    }
+
+    /// The common part of `set_seed` and `try_restore_state`.
+    fn reseed(&mut self, seed: &[u8; 32]) {
+        count!(self, reseed(self.position()));
+        count!(self, count_refill());
+        digest!(self.digest_flush());
         self.seed = seed_from_bytes(seed);
         // Fill the buffer immediately because we want the next bytes of output to come directly
         // from the new seed, not from the old seed or from the seed *after* `seed`.
-        self.backend.refill(&self.seed, &mut self.buf);
+        self.backend
+            .refill_with_rounds::<ROUNDS>(&self.seed, &mut self.buf);
         self.bytes_consumed = 0;
+        digest!(self.digest_resync());
+        self.iteration = 0;
+        self.origin_seed = self.seed;
+        self.origin_iteration = 0;
+    }
+
+    /// The seed that this generator was created with, or most recently re-seeded with.
+    ///
+    /// This is the seed passed to [`ChaCha8Rand::new`] (or a similar constructor) or to
+    /// [`ChaCha8Rand::set_seed`], even after the generator has moved on to later iterations that
+    /// use different keys internally. It's meant for things like printing the seed when a
+    /// randomized test fails, without having to pass a copy of the seed around separately. After
+    /// [`ChaCha8Rand::try_restore_state`], it's the seed stored in the snapshot, which is only the
+    /// original seed if the snapshot was taken in the first iteration.
+    ///
+    /// The generator keeps this copy of the seed in memory for its whole lifetime. It's only wiped
+    /// with crate feature `zeroize`, which adds a [`Zeroize` impl][zeroize-impl] that also runs
+    /// when the generator is dropped. Without that feature, don't rely on the seed being gone from
+    /// memory once the generator has used it.
+    ///
+    /// [zeroize-impl]: ChaChaRand#impl-Zeroize-for-ChaChaRand%3CROUNDS%3E
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let seed = *b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+    /// let mut rng = ChaCha8Rand::new(&seed);
+    /// rng.read_bytes(&mut [0; 5000]);
+    /// assert_eq!(rng.initial_seed(), seed);
+    /// ```
+    pub fn initial_seed(&self) -> [u8; 32] {
+        seed_to_bytes(&self.origin_seed)
+    }
+
+    /// Switch to a different [`Backend`] for all future refills.
+    ///
+    /// The seed, the buffered output, the position in the stream, and the stream ID (see
+    /// [`ChaCha8Rand::with_stream`]) are all preserved. Since all backends produce the same output,
+    /// the generator's output doesn't change either; this only affects performance. Like
+    /// [`ChaCha8Rand::with_backend`], it's mostly useful for benchmarks and for moving away from a
+    /// backend you suspect misbehaves on the current machine, without having to re-create the
+    /// generator and restore its state.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chacha8rand::{Backend, ChaCha8Rand};
+    ///
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// assert_eq!(rng.read_u64(), 0xb773b6063d4616a5);
+    /// rng.set_backend(Backend::scalar());
+    /// assert_eq!(rng.read_u64(), 0x1160af22a66abc3c);
+    /// ```
+    #[inline]
+    pub fn set_backend(&mut self, backend: Backend) {
+        self.backend = backend.with_stream(self.backend.stream());
+    }
+
+    /// Fold additional bytes (e.g., entropy that trickles in over time) into the generator's
+    /// state and re-key it.
+    ///
+    /// This gives long-running programs a principled way to improve a weak initial seed, e.g., on
+    /// embedded devices that boot without much entropy but later collect some from sensor noise or
+    /// interrupt timings. The result depends on both the generator's state and all of `data`, so
+    /// it's at least as unpredictable as either one. Mixing in data never makes things worse,
+    /// even if the data is constant or attacker-controlled. But keep in mind that this crate is
+    /// not a CSPRNG and this method doesn't turn it into one.
+    ///
+    /// The construction is fully specified, so it's reproducible: two generators in the same state
+    /// that mix in the same data end up in the same state again. It goes like this:
+    ///
+    /// 1. A chaining key `k` is taken from the stream as if by [`ChaCha8Rand::read_seed`].
+    /// 2. `data` is split into 32-byte chunks, with the last one padded with zero bytes, and then
+    ///    one more chunk is appended: `data.len()` as little-endian `u64` followed by 24 zero
+    ///    bytes. (Empty `data` only yields this final chunk.)
+    /// 3. For each chunk `m`, `k` is replaced by the first 32 bytes of output of a ChaCha8Rand
+    ///    instance seeded with `k XOR m`. This is always [`ChaCha8Rand::new`], even if this
+    ///    generator uses another stream ID or more rounds, which only affect steps 1 and 4.
+    /// 4. Finally, the generator is re-seeded with `k` as if by [`ChaCha8Rand::set_seed`].
+    ///
+    /// Each chunk needs one quarter of the work of a regular iteration, so it's cheap compared to
+    /// most sources of entropy, but not something to call for every byte in a hot loop.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(&[0; 32]); // weak boot seed
+    /// # fn read_sensor_noise() -> u16 { 42 }
+    /// let noise: u16 = read_sensor_noise();
+    /// rng.mix_in(&noise.to_le_bytes());
+    /// ```
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn mix_in(&mut self, data: &[u8]) {
+        let mut key = self.read_seed();
+        // The absorbing step is plain ChaCha8Rand, regardless of this generator's stream ID and
+        // round count, so that the construction is the same for all of them.
+        let backend = self.backend.with_stream(0);
+        let mut absorb = |chunk: &[u8]| {
+            for (k, m) in key.iter_mut().zip(chunk) {
+                *k ^= m;
+            }
+            // The first 32 bytes of output are the start of the first group, so we don't need to
+            // compute the other three.
+            let mut group = [0; 256];
+            backend.fill_group(&seed_from_bytes(&key), 0, &mut group);
+            key = *array_ref![group, 0, 32];
+        };
+        for chunk in data.chunks(32) {
+            absorb(chunk);
+        }
+        absorb(&(data.len() as u64).to_le_bytes());
+        self.set_seed(&key);
     }
 
     /// Consume four bytes of uniformly random data and return them as `u32`.
@@ -444,12 +1122,12 @@ impl ChaCha8Rand {
     /// method enables this conveniently and without involving the `rand_*` crates.
     ///
     /// On the other hand, if you want integers in a range like `0..n` or `m..=n`, you should *not*
-    /// use this method and combine it with the remainder operator `%`. The `rand` crate has
-    /// convenient and efficient APIs for doing that correctly, without introducing bias. It also
-    /// supports more data types, non-uniform distributions, and higher-level operations such as
-    /// shuffling lists. You can use it with ChaCha8Rand by [activating the crate
-    /// feature][rand-feature] so that [`ChaCha8Rand`] implements the rand traits. See the examples
-    /// for more details.
+    /// use this method and combine it with the remainder operator `%`, which introduces bias. Use
+    /// [`ChaCha8Rand::read_u32_below`] instead, or the `rand` crate, which has convenient and
+    /// efficient APIs for doing that correctly. It also supports more data types, non-uniform
+    /// distributions, and higher-level operations such as shuffling lists. You can use it with
+    /// ChaCha8Rand by [activating the crate feature][rand-feature] so that [`ChaCha8Rand`]
+    /// implements the rand traits. See the examples for more details.
     ///
     /// # Examples
     ///
@@ -543,8 +1221,10 @@ impl ChaCha8Rand {
     /// [tab-hash]: https://en.wikipedia.org/wiki/Tabulation_hashing
     /// [rand-feature]: ./index.html#crate-features
     #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
     pub fn read_u32(&mut self) -> u32 {
         const N: usize = size_of::<u32>();
+        audit_read!(self, N as u64);
 
         if self.bytes_consumed > BUF_OUTPUT_LEN - N {
             return self.read_u32_near_buffer_end();
@@ -558,7 +1238,7 @@ impl ChaCha8Rand {
     #[cold]
     fn read_u32_near_buffer_end(&mut self) -> u32 {
         let mut buf = [0; 4];
-        self.read_bytes(&mut buf);
+        self.read_bytes_impl(&mut buf);
         u32::from_le_bytes(buf)
     }
 
@@ -630,8 +1310,10 @@ impl ChaCha8Rand {
     ///
     /// [univ-hash]: https://en.wikipedia.org/wiki/Universal_hashing
     #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
     pub fn read_u64(&mut self) -> u64 {
         const N: usize = size_of::<u64>();
+        audit_read!(self, N as u64);
         // Same code as for u32. Making this code generic over `N` is more trouble than it's worth.
         if self.bytes_consumed > BUF_OUTPUT_LEN - N {
             return self.read_u64_near_buffer_end();
@@ -645,10 +1327,46 @@ impl ChaCha8Rand {
     #[cold]
     fn read_u64_near_buffer_end(&mut self) -> u64 {
         let mut buf = [0; 8];
-        self.read_bytes(&mut buf);
+        self.read_bytes_impl(&mut buf);
         u64::from_le_bytes(buf)
     }
 
+    /// Consume sixteen bytes of uniformly random data and return them as `u128`.
+    ///
+    /// Like [`ChaCha8Rand::read_u32`] and [`ChaCha8Rand::read_u64`], this is always equivalent to
+    /// [`ChaCha8Rand::read_bytes`] plus `u128::from_le_bytes`, but usually more efficient. It's a
+    /// convenient way to get 128-bit hash keys or random identifiers without combining two `u64`s
+    /// by hand (and getting the byte order subtly different from everyone else).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// assert_eq!(rng.read_u128(), 0x1160af22a66abc3c_b773b6063d4616a5);
+    /// ```
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_u128(&mut self) -> u128 {
+        const N: usize = size_of::<u128>();
+        audit_read!(self, N as u64);
+        // Same code as for u32 and u64.
+        if self.bytes_consumed > BUF_OUTPUT_LEN - N {
+            return self.read_u128_near_buffer_end();
+        }
+        let bytes = *array_ref![self.buf.output(), self.bytes_consumed, N];
+        self.bytes_consumed += N;
+        u128::from_le_bytes(bytes)
+    }
+
+    #[inline(never)]
+    #[cold]
+    fn read_u128_near_buffer_end(&mut self) -> u128 {
+        let mut buf = [0; 16];
+        self.read_bytes_impl(&mut buf);
+        u128::from_le_bytes(buf)
+    }
+
     /// Consume uniformly random bytes and write them into `dest`.
     ///
     /// This method is, in some sense, the most foundational way of using the generator. Other
@@ -688,7 +1406,67 @@ impl ChaCha8Rand {
     /// ```
     ///
     /// [uuid]: https://crates.io/crates/uuid
+    #[cfg_attr(feature = "audit", track_caller)]
     pub fn read_bytes(&mut self, dest: &mut [u8]) {
+        audit_read!(self, dest.len() as u64);
+        self.read_bytes_impl(dest);
+    }
+
+    fn read_bytes_impl(&mut self, dest: &mut [u8]) {
+        // SAFETY: `[u8]` and `[MaybeUninit<u8>]` have the same layout, and `read_bytes_uninit`
+        // only writes initialized bytes into `dest`, so it stays initialized.
+        let dest = unsafe { &mut *(dest as *mut [u8] as *mut [MaybeUninit<u8>]) };
+        self.read_bytes_uninit_impl(dest);
+    }
+
+    /// Fill several buffers, one after the other, as if they were a single buffer.
+    ///
+    /// This is equivalent to calling [`ChaCha8Rand::read_bytes`] on each buffer in order, so the
+    /// concatenation of all buffers receives exactly the bytes that one large `read_bytes` call
+    /// would have produced. It's convenient for scatter-gather I/O: `std::io::IoSliceMut`
+    /// dereferences to `[u8]`, so you can fill those as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let (mut header, mut body) = ([0; 16], [0; 1500]);
+    /// rng.read_bytes_vectored(&mut [&mut header, &mut body]);
+    /// assert_eq!(header[0], 0xa5);
+    /// ```
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_bytes_vectored(&mut self, bufs: &mut [&mut [u8]]) {
+        for buf in bufs {
+            self.read_bytes(buf);
+        }
+    }
+
+    /// Like [`ChaCha8Rand::read_bytes`], but for memory that may not be initialized yet.
+    ///
+    /// Returns `dest` as an ordinary byte slice, since it's fully initialized afterwards. This lets
+    /// you fill freshly allocated memory, arenas, or `MaybeUninit` arrays without zeroing them
+    /// first, which saves a pass over the memory for large buffers. The bytes are the same that
+    /// [`ChaCha8Rand::read_bytes`] would produce.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// use std::mem::MaybeUninit;
+    ///
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let mut storage = [MaybeUninit::<u8>::uninit(); 4096];
+    /// let bytes: &mut [u8] = rng.read_bytes_uninit(&mut storage);
+    /// assert_eq!(bytes.len(), 4096);
+    /// ```
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_bytes_uninit<'a>(&mut self, dest: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8] {
+        audit_read!(self, dest.len() as u64);
+        self.read_bytes_uninit_impl(dest)
+    }
+
+    fn read_bytes_uninit_impl<'a>(&mut self, dest: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8] {
         let mut total_bytes_read = 0;
         while total_bytes_read < dest.len() {
             let dest_remainder = &mut dest[total_bytes_read..];
@@ -698,13 +1476,303 @@ impl ChaCha8Rand {
             let src = &self.buf.output()[self.bytes_consumed..];
             let read_now = cmp::min(src.len(), dest_remainder.len());
 
-            dest_remainder[..read_now].copy_from_slice(&src[..read_now]);
+            // SAFETY: the source is `read_now` initialized bytes and the destination has room for
+            // at least that many. They can't overlap because we have a `&mut` to the destination.
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    src.as_ptr(),
+                    dest_remainder.as_mut_ptr().cast::<u8>(),
+                    read_now,
+                );
+            }
 
             total_bytes_read += read_now;
             self.bytes_consumed += read_now;
             debug_assert!(self.bytes_consumed <= self.buf.output().len());
         }
         debug_assert!(total_bytes_read == dest.len());
+        // SAFETY: the loop initialized all of `dest`.
+        unsafe { &mut *(dest as *mut [MaybeUninit<u8>] as *mut [u8]) }
+    }
+
+    /// Borrow the bytes of the internal buffer that haven't been consumed yet.
+    ///
+    /// This works like `std::io::BufRead::fill_buf`: if the buffer is used up, it's refilled first,
+    /// so the returned slice is never empty (it has between 1 and 992 bytes). Looking at the bytes
+    /// doesn't consume them; call [`ChaCha8Rand::consume`] afterwards to advance past however many
+    /// of them you actually used. This avoids copying the bytes into a buffer of your own, which
+    /// helps when you only need a few bytes at a time and decide on the fly how many to take.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// // Take bytes up to and including the first one with the high bit set.
+    /// let bytes = rng.fill_buf();
+    /// let n = bytes.iter().position(|&b| b >= 0x80).map_or(bytes.len(), |i| i + 1);
+    /// rng.consume(n);
+    /// ```
+    #[inline]
+    pub fn fill_buf(&mut self) -> &[u8] {
+        if self.bytes_consumed >= self.buf.output().len() {
+            self.refill();
+        }
+        &self.buf.output()[self.bytes_consumed..]
+    }
+
+    /// Mark the first `amount` bytes returned by [`ChaCha8Rand::fill_buf`] as consumed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `amount` is larger than the slice that `fill_buf` would return right now. For
+    /// example, `consume(0)` is always fine, and right after `fill_buf` returned `n` bytes you can
+    /// consume anything up to `n`.
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn consume(&mut self, amount: usize) {
+        audit_read!(self, amount as u64);
+        // Consuming from a used-up buffer refers to the bytes `fill_buf` would refill it with.
+        if amount > 0 && self.bytes_consumed >= self.buf.output().len() {
+            self.refill();
+        }
+        let available = self.buf.output().len() - self.bytes_consumed;
+        assert!(
+            amount <= available,
+            "can't consume {amount} bytes, only {available} are buffered"
+        );
+        self.bytes_consumed += amount;
+    }
+
+    /// Fill `dest` with the bytes that [`ChaCha8Rand::read_bytes`] would produce next, without
+    /// consuming them.
+    ///
+    /// Afterwards, the generator is still at the same position in the stream, so the next read
+    /// returns the same bytes again. Peeking at bytes that are already in the internal buffer is
+    /// just a copy. Peeking further ahead computes the upcoming iterations on the fly, which costs
+    /// as much as generating them for real but leaves the generator itself untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let mut peeked = [0; 2000];
+    /// rng.peek_bytes(&mut peeked);
+    /// let mut read = [0; 2000];
+    /// rng.read_bytes(&mut read);
+    /// assert_eq!(peeked, read);
+    /// ```
+    pub fn peek_bytes(&self, dest: &mut [u8]) {
+        let buffered = &self.buf.output()[self.bytes_consumed..];
+        let from_buf = cmp::min(buffered.len(), dest.len());
+        dest[..from_buf].copy_from_slice(&buffered[..from_buf]);
+        let mut rest = &mut dest[from_buf..];
+        if rest.is_empty() {
+            return;
+        }
+
+        let mut key = seed_from_bytes(self.buf.new_key());
+        let mut lookahead = MaybeUninit::<Buffer>::uninit();
+        loop {
+            let buf = self
+                .backend
+                .refill_uninit_with_rounds::<ROUNDS>(&key, &mut lookahead);
+            let n = cmp::min(BUF_OUTPUT_LEN, rest.len());
+            rest[..n].copy_from_slice(&buf.output()[..n]);
+            rest = &mut rest[n..];
+            if rest.is_empty() {
+                return;
+            }
+            key = seed_from_bytes(buf.new_key());
+        }
+    }
+
+    /// Discard the next `n` bytes of output.
+    ///
+    /// This has the same effect as reading `n` bytes with [`ChaCha8Rand::read_bytes`] and throwing
+    /// them away, but doesn't need a scratch buffer or copy anything. Every iteration that's
+    /// skipped entirely still has to be visited, because each iteration's key comes from the
+    /// previous one. But only the last quarter of such an iteration (which contains the next key)
+    /// is computed, so skipping is several times faster than generating the same output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let seed = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+    /// let mut rng1 = ChaCha8Rand::new(seed);
+    /// let mut rng2 = ChaCha8Rand::new(seed);
+    /// rng1.read_bytes(&mut [0; 5000]);
+    /// rng2.skip_bytes(5000);
+    /// assert_eq!(rng1.read_u64(), rng2.read_u64());
+    /// ```
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn skip_bytes(&mut self, n: u64) {
+        audit_read!(self, n);
+        self.skip_bytes_impl(n);
+    }
+
+    pub(crate) fn skip_bytes_impl(&mut self, n: u64) {
+        digest!(self.digest_flush());
+        let available = (BUF_OUTPUT_LEN - self.bytes_consumed) as u64;
+        if n <= available {
+            self.bytes_consumed += n as usize;
+            digest!(self.digest_resync());
+            return;
+        }
+
+        // Any iteration that we'd only refill to consume it entirely right away can be skipped by
+        // computing its successor's key. To end up in the same state as `read_bytes` would, we
+        // stop at the iteration containing the last skipped byte, even if that's its last byte.
+        let mut remaining = n - available;
+        let mut key = seed_from_bytes(self.buf.new_key());
+        self.iteration = self.iteration.wrapping_add(1);
+        while remaining > BUF_OUTPUT_LEN as u64 {
+            key = seed_from_bytes(&self.backend.next_key_with_rounds::<ROUNDS>(&key));
+            remaining -= BUF_OUTPUT_LEN as u64;
+            self.iteration = self.iteration.wrapping_add(1);
+        }
+        self.seed = key;
+        self.backend
+            .refill_with_rounds::<ROUNDS>(&self.seed, &mut self.buf);
+        count!(self, count_refill());
+        trace_event!(iteration = self.iteration, "refill");
+        self.bytes_consumed = remaining as usize;
+        digest!(self.digest_resync());
+    }
+
+    /// The total number of output bytes consumed since the generator was created or last re-seeded.
+    ///
+    /// Every way of consuming output counts, no matter if it's `read_u32`, `read_bytes`, or
+    /// [`ChaCha8Rand::skip_bytes`]. Re-seeding with [`ChaCha8Rand::set_seed`] resets the position
+    /// to zero, while [`ChaCha8Rand::try_restore_state`] sets it to the snapshot's position. Use
+    /// [`StreamPosition::from_byte_offset`] if you'd rather have the position split into an
+    /// iteration index and an offset.
+    ///
+    /// This is useful for checking that two generators (e.g., in replicas of a deterministic
+    /// simulation) have consumed the same amount of output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// rng.read_u32();
+    /// rng.read_bytes(&mut [0; 1000]);
+    /// assert_eq!(rng.position(), 1004);
+    /// ```
+    #[inline]
+    pub fn position(&self) -> u128 {
+        if self.is_unfilled() {
+            return StreamPosition::iteration_start(self.origin_iteration).to_byte_offset();
+        }
+        // A used-up buffer isn't a canonical `StreamPosition`, so count from its iteration's start.
+        StreamPosition::iteration_start(self.iteration).to_byte_offset()
+            + self.bytes_consumed as u128
+    }
+
+    /// Move to an absolute position in the stream, as counted by [`ChaCha8Rand::position`].
+    ///
+    /// Afterwards, the generator is in exactly the same state as if it had been re-seeded and then
+    /// consumed `position` bytes. Seeking forward works like [`ChaCha8Rand::skip_bytes`]. Seeking
+    /// backward restarts from the seed the position is counted from and then skips forward again,
+    /// so it costs about as much as seeking forward from the start. Seeking within the current
+    /// iteration is cheap in either direction.
+    ///
+    /// # Errors
+    ///
+    /// Fails without changing anything if `position` is larger than [`StreamPosition::MAX`]
+    /// allows, which you won't reach by accident. After restoring a snapshot with
+    /// [`ChaCha8Rand::try_restore_state`], the generator doesn't know the seeds of earlier
+    /// iterations, so it also can't seek to any position before the start of the snapshot's
+    /// iteration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// rng.seek_to(1_000_000)?;
+    /// let x = rng.read_u64();
+    /// rng.seek_to(8)?;
+    /// assert_eq!(rng.read_u64(), 0x1160af22a66abc3c);
+    /// rng.seek_to(1_000_000)?;
+    /// assert_eq!(rng.read_u64(), x);
+    /// # Ok::<(), chacha8rand::SeekError>(())
+    /// ```
+    pub fn seek_to(&mut self, position: u128) -> Result<(), SeekError> {
+        let target = match StreamPosition::from_byte_offset(position) {
+            Some(target) if target >= StreamPosition::iteration_start(self.origin_iteration) => {
+                target
+            }
+            _ => return Err(SeekError { _private: () }),
+        };
+        // The unfilled marker iteration isn't the current iteration, so don't mistake a target in
+        // it for a cheap seek within the buffer.
+        self.fill_if_unfilled();
+        count!(self, jump_from(self.position()));
+        digest!(self.digest_flush());
+        let iteration_start = StreamPosition::iteration_start(self.iteration);
+        if target < iteration_start {
+            self.seed = self.origin_seed;
+            self.backend
+                .refill_with_rounds::<ROUNDS>(&self.seed, &mut self.buf);
+            count!(self, count_refill());
+            self.bytes_consumed = 0;
+            digest!(self.digest_resync());
+            self.iteration = self.origin_iteration;
+            trace_event!(iteration = self.iteration, "refill");
+        } else if iteration_start.distance(target) <= BUF_OUTPUT_LEN as u128 {
+            self.bytes_consumed = iteration_start.distance(target) as usize;
+            count!(self, jump_to(position));
+            digest!(self.digest_resync());
+            return Ok(());
+        }
+
+        let mut remaining = position - self.position();
+        while remaining > 0 {
+            let n = cmp::min(remaining, u128::from(u64::MAX)) as u64;
+            self.skip_bytes_impl(n);
+            remaining -= u128::from(n);
+        }
+        count!(self, jump_to(position));
+        Ok(())
+    }
+
+    /// Allocate a `Vec` of `len` uniformly random bytes.
+    ///
+    /// This gives the same bytes as [`ChaCha8Rand::read_bytes`] with a `vec![0; len]`, but skips
+    /// zeroing the memory first, which makes a difference for large buffers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let payload = rng.read_vec(64 * 1024);
+    /// assert_eq!(payload.len(), 64 * 1024);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_vec(&mut self, len: usize) -> alloc::vec::Vec<u8> {
+        let mut vec = alloc::vec::Vec::new();
+        self.extend_vec(&mut vec, len);
+        vec
+    }
+
+    /// Append `additional` uniformly random bytes to `vec`.
+    ///
+    /// This reserves the required capacity and writes the bytes directly into it, without zeroing
+    /// the memory first. The bytes are the same as [`ChaCha8Rand::read_bytes`] would produce.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn extend_vec(&mut self, vec: &mut alloc::vec::Vec<u8>, additional: usize) {
+        vec.reserve(additional);
+        let len = vec.len();
+        self.read_bytes_uninit(&mut vec.spare_capacity_mut()[..additional]);
+        // SAFETY: we reserved enough capacity and initialized the `additional` bytes after `len`.
+        unsafe { vec.set_len(len + additional) };
     }
 
     /// Consume 32 uniformly random bytes, suitable for seeding another RNG instance.
@@ -762,7 +1830,11 @@ impl ChaCha8Rand {
     /// entirely different depending on how many turns the player took to reach them. Instead, you
     /// can set up one generator for every aspect of the game's randomness, and derive seeds for all
     /// of them from the seed that the player deals with. Just make sure you don't [accidentally use
-    /// the *same* seed][sts-corr-rand] for each of the generators.
+    /// the *same* seed][sts-corr-rand] for each of the generators. [`RngSet`] can manage such a
+    /// collection of generators for you, including snapshots of all of them at once.
+    ///
+    /// For deeper hierarchies of generators, or if you need to recreate one of them without
+    /// repeating how all the others were derived, consider [`SeedSequence`] instead.
     ///
     /// ```
     /// # use chacha8rand::ChaCha8Rand;
@@ -790,10 +1862,82 @@ impl ChaCha8Rand {
     /// ```
     ///
     /// [sts-corr-rand]: https://forgottenarbiter.github.io/Correlated-Randomness/
+    #[cfg_attr(feature = "audit", track_caller)]
     pub fn read_seed(&mut self) -> [u8; 32] {
-        let mut seed = [0; 32];
-        self.read_bytes(&mut seed);
-        seed
+        self.read_array()
+    }
+
+    /// Consume `N` uniformly random bytes and return them as an array.
+    ///
+    /// This is [`ChaCha8Rand::read_bytes`] without having to declare a buffer first, which is
+    /// handy for nonces, keys, and other fixed-size records. [`ChaCha8Rand::read_seed`] is the
+    /// special case `N = 32`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let nonce: [u8; 12] = rng.read_array();
+    /// let tag = rng.read_array::<4>();
+    /// # let _ = (nonce, tag);
+    /// ```
+    #[inline]
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_array<const N: usize>(&mut self) -> [u8; N] {
+        let mut bytes = [0; N];
+        self.read_bytes(&mut bytes);
+        bytes
+    }
+
+    /// An infinite iterator of seeds, each one consumed from the stream as if by
+    /// [`ChaCha8Rand::read_seed`].
+    ///
+    /// This makes deriving many sub-generators a one-liner, with the derivation order spelled out
+    /// explicitly: the `i`-th seed yielded is always the `i`-th 32-byte chunk read from here on.
+    /// Nothing is consumed for seeds that are never requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut root = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let workers: Vec<ChaCha8Rand> = root
+    ///     .seeds()
+    ///     .take(4)
+    ///     .map(|seed| ChaCha8Rand::new(&seed))
+    ///     .collect();
+    /// assert_eq!(workers.len(), 4);
+    /// ```
+    pub fn seeds(&mut self) -> impl Iterator<Item = [u8; 32]> + '_ {
+        self.iter_with(Self::read_seed)
+    }
+
+    /// Turn any sampling logic into an infinite iterator.
+    ///
+    /// Each call to `next()` calls `f` with this generator and yields the result. This is handy for
+    /// plugging custom sampling code into iterator pipelines without writing a dedicated iterator
+    /// type. The iterator mutably borrows the generator, so other code can't interleave its own
+    /// reads, but you can get the generator back by dropping the iterator. Nothing is consumed
+    /// from the stream for items that are never requested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let dice: Vec<u32> = rng
+    ///     .iter_with(|rng| rng.read_u32_below(6) + 1)
+    ///     .take(10)
+    ///     .collect();
+    /// assert_eq!(dice.len(), 10);
+    /// assert!(dice.iter().all(|d| (1..=6).contains(d)));
+    /// ```
+    pub fn iter_with<'a, T, F>(&'a mut self, mut f: F) -> impl Iterator<Item = T> + 'a
+    where
+        F: FnMut(&mut Self) -> T + 'a,
+    {
+        iter::repeat_with(move || f(self))
     }
 
     /// Take a snapshot of the generator's current state.
@@ -805,10 +1949,18 @@ impl ChaCha8Rand {
         // could behave incorrectly. That code path is also careful about it but defense in depth
         // can't hurt, so let's saturate here.
         debug_assert!(self.bytes_consumed <= BUF_OUTPUT_LEN);
+        if self.is_unfilled() {
+            return ChaCha8State {
+                seed: seed_to_bytes(&self.origin_seed),
+                bytes_consumed: 0,
+                iteration: self.origin_iteration,
+            };
+        }
         let bytes_consumed = cmp::min(self.bytes_consumed, BUF_OUTPUT_LEN) as u16;
         ChaCha8State {
             seed: seed_to_bytes(&self.seed),
             bytes_consumed,
+            iteration: self.iteration,
         }
     }
 
@@ -831,18 +1983,63 @@ impl ChaCha8Rand {
             return Err(RestoreStateError { _private: () });
         }
 
-        // We can just use `set_seed` to fill the buffer and then skip the parts of that chunk that
+        // We can just use `reseed` to fill the buffer and then skip the parts of that chunk that
         // were marked as already consumed by adjusting our position in the refilled buffer.
-        self.set_seed(&state.seed);
+        self.reseed(&state.seed);
         self.bytes_consumed = bytes_consumed;
+        digest!(self.digest_resync());
+        self.iteration = state.iteration;
+        self.origin_iteration = state.iteration;
+        count!(self, jump_to(self.position()));
+        trace_event!(iteration = self.iteration, bytes_consumed, "restore_state");
         Ok(())
     }
 
+    /// Like [`ChaCha8Rand::clone_state`], but a used-up buffer is treated like the start of the
+    /// next iteration, so that the result doesn't depend on when the buffer is refilled.
+    fn logical_state(&self) -> ChaCha8State {
+        let mut state = self.clone_state();
+        if usize::from(state.bytes_consumed) == BUF_OUTPUT_LEN {
+            state.seed = *self.buf.new_key();
+            state.bytes_consumed = 0;
+            state.iteration = state.iteration.wrapping_add(1);
+        }
+        state
+    }
+
+    /// Whether the generator was initialized lazily and hasn't filled its buffer yet.
+    ///
+    /// In that state, the buffer's output is garbage but fully consumed, and its new key is the
+    /// origin seed. The iteration counter is one less than the origin iteration, so the regular
+    /// refill logic (and everything else that starts from `buf.new_key()` once the output is used
+    /// up) continues with the right key and iteration. Only [`ChaCha8Rand::position`] and
+    /// [`ChaCha8Rand::clone_state`] need to special-case it, because they'd otherwise report the
+    /// end of the iteration *before* the origin, and [`ChaCha8Rand::seek_to`] fills the buffer
+    /// first because the iteration before the origin wraps around to `u64::MAX` for a fresh
+    /// generator. Going through `u64::MAX` refills to reach the same combination of fields the
+    /// regular way isn't possible in practice.
+    fn is_unfilled(&self) -> bool {
+        self.bytes_consumed == BUF_OUTPUT_LEN
+            && self.iteration == self.origin_iteration.wrapping_sub(1)
+    }
+
+    fn fill_if_unfilled(&mut self) {
+        if self.is_unfilled() {
+            self.refill();
+        }
+    }
+
     #[inline]
     fn refill(&mut self) {
+        digest!(self.digest_flush());
         self.seed = seed_from_bytes(self.buf.new_key());
-        self.backend.refill(&self.seed, &mut self.buf);
+        self.backend
+            .refill_with_rounds::<ROUNDS>(&self.seed, &mut self.buf);
+        count!(self, count_refill());
         self.bytes_consumed = 0;
+        digest!(self.digest_resync());
+        self.iteration = self.iteration.wrapping_add(1);
+        trace_event!(iteration = self.iteration, "refill");
     }
 }
 
@@ -857,77 +2054,3 @@ fn seed_to_bytes(seed: &[u32; 8]) -> [u8; 32] {
     }
     bytes
 }
-
-macro_rules! arch_backends {
-    ($(#[cfg($cond:meta)] mod $name:ident;)+) => {
-        $(
-            #[cfg($cond)]
-            mod $name {
-                mod safe_arch;
-                mod backend;
-                pub(crate) use backend::detect;
-            }
-
-            #[cfg(not($cond))]
-            mod $name {
-                pub fn detect() -> Option<crate::Backend> {
-                    None
-                }
-            }
-        )+
-    };
-}
-
-arch_backends! {
-    // This backend uses dynamic feature detection, so it's disabled in no_std mode and only gated
-    // on `target_arch`. In theory it could also be enabled in no_std mode when AVX2 is statically
-    // enabled, but that would probably complicate some unsafe code which seems like a bad trade.
-    #[cfg(all(any(target_arch = "x86_64", target_arch = "x86"), feature = "std"))]
-    mod avx2;
-
-    // For SSE2 we don't bother with dynamic feature detection. x86_64 basically always has it, it's
-    // also very commonly enabled on 32-bit targets, and when it isn't, we still have a very high
-    // chance that AVX2 is available at runtime.
-    #[cfg(all(any(target_arch = "x86_64", target_arch = "x86"), target_feature = "sse2"))]
-    mod sse2;
-
-    // The neon backend is limited to little-endian because the core::arch intrinsics currently
-    // don't work on aarch64be (https://github.com/rust-lang/stdarch/issues/1484). Even if they
-    // worked, it's a pretty obscure target and difficult to test for (e.g., `cross` doesn't
-    // currently support it) so I'm inclined to leave this out until someone champions it.
-    #[cfg(all(target_arch = "aarch64", target_feature = "neon", target_endian = "little"))]
-    mod neon;
-
-    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
-    mod simd128;
-}
-
-// These methods only exist to enable the benchmark (compiled as separate crate) to override backend
-// selection and compare performance. It's not in the `backend` module to minimize that code that
-// has to worry about upholding `Backend`'s invariant.
-#[cfg(feature = "unstable_internals")]
-#[allow(
-    missing_docs,
-    reason = "internal APIs only exposed unstably for benchmarks"
-)]
-impl Backend {
-    pub fn scalar() -> Self {
-        scalar::backend()
-    }
-
-    pub fn x86_avx2() -> Option<Self> {
-        avx2::detect()
-    }
-
-    pub fn x86_sse2() -> Option<Self> {
-        sse2::detect()
-    }
-
-    pub fn aarch64_neon() -> Option<Self> {
-        neon::detect()
-    }
-
-    pub fn wasm32_simd128() -> Option<Self> {
-        simd128::detect()
-    }
-}
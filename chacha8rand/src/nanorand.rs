@@ -0,0 +1,35 @@
+use nanorand::{Rng, SeedableRng};
+
+use crate::ChaCha8Rand;
+
+/// Integration with nanorand v0.7. Requires crate feature `nanorand`.
+///
+/// Like nanorand's own `WyRand`, this produces eight bytes per `rand` call, so generic code has to
+/// name the generator as `Rng<8>`. `rand` is equivalent to `read_array::<8>`, and `fill_bytes`
+/// delegates to [`ChaCha8Rand::read_bytes`]. Since nanorand generates integers with `fill_bytes`,
+/// values like `generate::<u32>()` consume exactly as many bytes as they're big, so they're read
+/// from the stream the same way as with the inherent methods (in native byte order, though).
+impl Rng<8> for ChaCha8Rand {
+    #[inline]
+    fn rand(&mut self) -> [u8; 8] {
+        self.read_array()
+    }
+
+    #[inline]
+    fn fill_bytes<Bytes>(&mut self, mut buffer: Bytes)
+    where
+        Bytes: AsMut<[u8]>,
+    {
+        self.read_bytes(buffer.as_mut());
+    }
+}
+
+/// Integration with nanorand v0.7. Requires crate feature `nanorand`.
+///
+/// `reseed` is equivalent to [`ChaCha8Rand::set_seed`].
+impl SeedableRng<32, 8> for ChaCha8Rand {
+    #[inline]
+    fn reseed(&mut self, seed: [u8; 32]) {
+        self.set_seed(&seed);
+    }
+}
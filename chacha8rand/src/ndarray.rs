@@ -0,0 +1,83 @@
+use ndarray::{ArrayViewMut, Dimension};
+
+use crate::ChaCha8Rand;
+
+/// Filling `ndarray` arrays. Requires crate feature `ndarray`.
+///
+/// These methods visit the elements in *logical* order, i.e., the order in which
+/// `ArrayBase::iter` yields them, which is row-major order over the array's shape. The memory
+/// layout (C or Fortran order, custom strides, transposed or sliced views) doesn't affect which
+/// element gets which value, so the results only depend on the seed and the array's shape.
+impl ChaCha8Rand {
+    /// Fill every element of `view` with [`ChaCha8Rand::read_u64`], in logical order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// use ndarray::{Array2, ShapeBuilder};
+    ///
+    /// let seed = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+    /// let mut row_major = Array2::zeros((3, 4));
+    /// let mut col_major = Array2::zeros((3, 4).f());
+    /// ChaCha8Rand::new(seed).fill_ndarray_u64(row_major.view_mut());
+    /// ChaCha8Rand::new(seed).fill_ndarray_u64(col_major.view_mut());
+    /// assert_eq!(row_major, col_major);
+    /// assert_eq!(row_major[(0, 0)], 0xb773b6063d4616a5);
+    /// ```
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn fill_ndarray_u64<D: Dimension>(&mut self, mut view: ArrayViewMut<'_, u64, D>) {
+        for x in view.iter_mut() {
+            *x = self.read_u64();
+        }
+    }
+
+    /// Fill every element of `view` with a uniformly random `f64` in `[0, 1)`, in logical order.
+    ///
+    /// Each element consumes eight bytes of output and uses the 53 most significant bits of the
+    /// resulting `u64` (as returned by [`ChaCha8Rand::read_u64`]), scaled by 2<sup>-53</sup>.
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn fill_ndarray_f64_unit<D: Dimension>(&mut self, mut view: ArrayViewMut<'_, f64, D>) {
+        for x in view.iter_mut() {
+            *x = self.read_f64();
+        }
+    }
+
+    /// Fill every element of `view` with [`ChaCha8Rand::read_normal`]`(mean, std_dev)`, in
+    /// logical order. Requires crate feature `distributions`.
+    ///
+    /// Each element consumes 16 bytes of output. The parameters are checked up front, so this
+    /// panics even if `view` is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as [`ChaCha8Rand::read_normal`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// use ndarray::Array2;
+    ///
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let mut weights = Array2::zeros((64, 32));
+    /// rng.fill_ndarray_normal(weights.view_mut(), 0.0, 0.02);
+    /// ```
+    #[cfg(feature = "distributions")]
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn fill_ndarray_normal<D: Dimension>(
+        &mut self,
+        mut view: ArrayViewMut<'_, f64, D>,
+        mean: f64,
+        std_dev: f64,
+    ) {
+        assert!(mean.is_finite(), "mean must be finite");
+        assert!(
+            std_dev >= 0.0 && std_dev.is_finite(),
+            "standard deviation must be finite and non-negative"
+        );
+        for x in view.iter_mut() {
+            *x = self.read_normal(mean, std_dev);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use crate::ChaCha8Rand;
+
+/// Generating network addresses, e.g., for simulations and test fixtures.
+///
+/// The addresses are built from stream bytes in network byte order: the first byte read becomes
+/// the first octet, and so on. Methods that only randomize part of an address still consume the
+/// full address width, so switching between them doesn't shift the rest of the stream around.
+///
+/// These methods only need `core::net`, so they're available without the `std` feature.
+impl ChaCha8Rand {
+    /// Consume four bytes and return them as an IPv4 address.
+    ///
+    /// Every address is equally likely, including special ones like `0.0.0.0`, loopback, multicast
+    /// and broadcast addresses. Use [`ChaCha8Rand::read_ipv4_addr_in`] if you want to restrict
+    /// the result to some subnet.
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_ipv4_addr(&mut self) -> Ipv4Addr {
+        let mut octets = [0; 4];
+        self.read_bytes(&mut octets);
+        Ipv4Addr::from(octets)
+    }
+
+    /// Consume 16 bytes and return them as an IPv6 address.
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_ipv6_addr(&mut self) -> Ipv6Addr {
+        let mut octets = [0; 16];
+        self.read_bytes(&mut octets);
+        Ipv6Addr::from(octets)
+    }
+
+    /// Consume four bytes and return a uniformly random address in the subnet `network/prefix_len`.
+    ///
+    /// The first `prefix_len` bits are copied from `network` and the remaining bits come from the
+    /// stream. With `prefix_len == 0` this is the same as [`ChaCha8Rand::read_ipv4_addr`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix_len` is larger than 32.
+    ///
+    /// # Examples
+    ///
+    /// The documentation ranges from [RFC 5737](https://www.rfc-editor.org/rfc/rfc5737) are a
+    /// good choice for fixtures that should never reach a real host:
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// use std::net::Ipv4Addr;
+    ///
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let test_net_1 = Ipv4Addr::new(192, 0, 2, 0);
+    /// let addr = rng.read_ipv4_addr_in(test_net_1, 24);
+    /// assert!(addr.is_documentation());
+    /// assert_eq!(addr.octets()[..3], [192, 0, 2]);
+    /// ```
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_ipv4_addr_in(&mut self, network: Ipv4Addr, prefix_len: u8) -> Ipv4Addr {
+        assert!(prefix_len <= 32, "IPv4 prefix length must be at most 32");
+        let mask = u32::MAX
+            .checked_shl(32 - u32::from(prefix_len))
+            .unwrap_or(0);
+        let random = u32::from(self.read_ipv4_addr());
+        Ipv4Addr::from((u32::from(network) & mask) | (random & !mask))
+    }
+
+    /// Consume 16 bytes and return a uniformly random address in the subnet `network/prefix_len`.
+    ///
+    /// The first `prefix_len` bits are copied from `network` and the remaining bits come from the
+    /// stream. With `prefix_len == 0` this is the same as [`ChaCha8Rand::read_ipv6_addr`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix_len` is larger than 128.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// use std::net::Ipv6Addr;
+    ///
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// // 2001:db8::/32 is reserved for documentation (RFC 3849).
+    /// let documentation = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0);
+    /// let addr = rng.read_ipv6_addr_in(documentation, 32);
+    /// assert_eq!(addr.segments()[..2], [0x2001, 0xdb8]);
+    /// ```
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_ipv6_addr_in(&mut self, network: Ipv6Addr, prefix_len: u8) -> Ipv6Addr {
+        assert!(prefix_len <= 128, "IPv6 prefix length must be at most 128");
+        let mask = u128::MAX
+            .checked_shl(128 - u32::from(prefix_len))
+            .unwrap_or(0);
+        let random = u128::from(self.read_ipv6_addr());
+        Ipv6Addr::from((u128::from(network) & mask) | (random & !mask))
+    }
+
+    /// Consume two bytes and return a uniformly random port in the dynamic range `49152..=65535`.
+    ///
+    /// IANA reserves this range for private and ephemeral use, so it won't collide with well-known
+    /// or registered service ports. It also has exactly 2<sup>14</sup> ports, so no bias-avoidance
+    /// tricks are required: the 14 least significant bits of a little-endian `u16` are used.
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_dynamic_port(&mut self) -> u16 {
+        let mut bytes = [0; 2];
+        self.read_bytes(&mut bytes);
+        0xC000 | (u16::from_le_bytes(bytes) & 0x3FFF)
+    }
+
+    /// Consume two bytes and combine `ip` with a port from [`ChaCha8Rand::read_dynamic_port`].
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_socket_addr(&mut self, ip: IpAddr) -> SocketAddr {
+        SocketAddr::new(ip, self.read_dynamic_port())
+    }
+}
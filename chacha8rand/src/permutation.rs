@@ -0,0 +1,152 @@
+use core::fmt;
+
+use crate::ChaCha8Rand;
+
+const ROUNDS: usize = 8;
+
+/// A random permutation of `0..n` that's evaluated on demand, without `O(n)` memory.
+///
+/// This is useful when the index space is far too large to shuffle in memory, e.g., for visiting
+/// ten billion records of an out-of-core data set in random order, or for assigning stable
+/// pseudonyms to sequential IDs. Both directions of the mapping take constant time (a few dozen
+/// arithmetic operations on average) and the whole permutation is described by 64 bytes of
+/// randomness, so it's cheap to copy around and reproducible from the generator's seed.
+///
+/// Internally, this is a balanced Feistel network on the smallest power-of-four domain that
+/// contains `0..n`, with round keys from the generator, plus *cycle walking*: values outside of
+/// `0..n` are fed through the network again until they land inside. The domain is at most four
+/// times larger than `n`, so that takes fewer than four passes on average. The round function is
+/// a fast integer mixer rather than a cryptographic primitive, so while the result is a good
+/// scrambling for simulations and sampling, it's *not* a secure pseudorandom permutation and
+/// can't be relied on to hide the original indices from an adversary.
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::{ChaCha8Rand, LazyPermutation};
+///
+/// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// let perm = LazyPermutation::new(&mut rng, 10_000_000_000);
+/// let first = perm.get(0);
+/// assert!(first < 10_000_000_000);
+/// assert_eq!(perm.inverse(first), 0);
+///
+/// // Small permutations can still be materialized if you want to.
+/// let small = LazyPermutation::new(&mut rng, 10);
+/// let mut all: Vec<u64> = small.iter().collect();
+/// all.sort();
+/// assert_eq!(all, (0..10).collect::<Vec<_>>());
+/// ```
+#[derive(Clone)]
+pub struct LazyPermutation {
+    n: u64,
+    half_bits: u32,
+    keys: [u64; ROUNDS],
+}
+
+impl fmt::Debug for LazyPermutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyPermutation")
+            .field("len", &self.n)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LazyPermutation {
+    /// Create a random permutation of `0..n`, consuming 64 bytes from `rng`.
+    pub fn new(rng: &mut ChaCha8Rand, n: u64) -> Self {
+        // Number of bits needed for the largest value, `n - 1`, rounded up to an even number so we
+        // can split it into two halves. Always at least one bit per half, which also takes care of
+        // `n <= 1`.
+        let bits = u64::BITS - n.saturating_sub(1).leading_zeros();
+        let half_bits = bits.div_ceil(2).max(1);
+        let keys = core::array::from_fn(|_| rng.read_u64());
+        LazyPermutation { n, half_bits, keys }
+    }
+
+    /// The number of elements being permuted.
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Whether this is a permutation of the empty range.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// The element at position `i` of the permutation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is not less than [`LazyPermutation::len`].
+    pub fn get(&self, i: u64) -> u64 {
+        assert!(
+            i < self.n,
+            "index {i} out of range for permutation of length {}",
+            self.n
+        );
+        let mut x = i;
+        loop {
+            x = self.encrypt(x);
+            if x < self.n {
+                return x;
+            }
+        }
+    }
+
+    /// The position of `value` in the permutation, i.e., the inverse of
+    /// [`LazyPermutation::get`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is not less than [`LazyPermutation::len`].
+    pub fn inverse(&self, value: u64) -> u64 {
+        assert!(
+            value < self.n,
+            "value {value} out of range for permutation of length {}",
+            self.n
+        );
+        let mut x = value;
+        loop {
+            x = self.decrypt(x);
+            if x < self.n {
+                return x;
+            }
+        }
+    }
+
+    /// Iterate over the permutation in order, i.e., `get(0)`, `get(1)`, and so on.
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        (0..self.n).map(|i| self.get(i))
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - self.half_bits)
+    }
+
+    fn round(&self, key: u64, half: u64) -> u64 {
+        // SplitMix64's output function: fast, and every input bit affects every output bit.
+        let mut z = half ^ key;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        (z ^ (z >> 31)) & self.mask()
+    }
+
+    fn encrypt(&self, x: u64) -> u64 {
+        let mask = self.mask();
+        let (mut left, mut right) = (x >> self.half_bits, x & mask);
+        for &key in &self.keys {
+            (left, right) = (right, left ^ self.round(key, right));
+        }
+        (left << self.half_bits) | right
+    }
+
+    fn decrypt(&self, x: u64) -> u64 {
+        let mask = self.mask();
+        let (mut left, mut right) = (x >> self.half_bits, x & mask);
+        for &key in self.keys.iter().rev() {
+            (left, right) = (right ^ self.round(key, left), left);
+        }
+        (left << self.half_bits) | right
+    }
+}
@@ -0,0 +1,126 @@
+use alloc::{vec, vec::Vec};
+
+use crate::ChaCha8Rand;
+
+/// How many candidates around an active point are tried before giving up on it. This is the
+/// value recommended in Bridson's paper and part of the reproducibility contract.
+const ATTEMPTS: u32 = 30;
+
+impl ChaCha8Rand {
+    /// Generate a Poisson-disk point set ("blue noise") in `[0, width) × [0, height)`.
+    ///
+    /// No two points are closer than `radius` to each other, and the points fill the rectangle
+    /// densely: no further point can be added without violating the minimum distance (except for
+    /// small gaps where the algorithm gave up). This looks much more natural than independent
+    /// uniform points for procedural placement of trees, rocks, and the like, because uniform
+    /// points tend to clump together.
+    ///
+    /// This is Bridson's algorithm ("Fast Poisson Disk Sampling in Arbitrary Dimensions", 2007),
+    /// fully specified so the point set only depends on the generator's state and the parameters:
+    ///
+    /// 1. The first point is uniformly distributed in the rectangle (`x` sampled before `y`).
+    /// 2. While the list of active points isn't empty, one of them is chosen uniformly at random
+    ///    (as if by [`ChaCha8Rand::read_u64_below`] with bound `active.len()`). Up to 30 candidates
+    ///    are generated around it, each at an angle `2π * U₁` and distance
+    ///    `radius * sqrt(1 + 3 * U₂)`, i.e., uniformly distributed over the annulus between
+    ///    `radius` and `2 * radius`. The first candidate inside the rectangle and far enough from
+    ///    all existing points is added to the result and the list of active points. If none works,
+    ///    the chosen point is removed from the active list with `Vec::swap_remove`.
+    ///
+    /// All uniform numbers `U` are sampled with [`ChaCha8Rand::read_f64`], and the trigonometric
+    /// functions come from `libm`, so the result is the same on all platforms. Points are returned
+    /// in the order they were generated.
+    ///
+    /// The work and memory is proportional to the area divided by `radius²`, so be careful with
+    /// tiny radii.
+    ///
+    /// Requires crate features `alloc` and `distributions`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radius` isn't positive and finite, or if `width` or `height` are negative, NaN,
+    /// or infinite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let trees = rng.poisson_disk_2d(100.0, 50.0, 5.0);
+    /// for (i, [x1, y1]) in trees.iter().enumerate() {
+    ///     for [x2, y2] in &trees[..i] {
+    ///         assert!((x1 - x2).hypot(y1 - y2) >= 5.0);
+    ///     }
+    /// }
+    /// ```
+    pub fn poisson_disk_2d(&mut self, width: f64, height: f64, radius: f64) -> Vec<[f64; 2]> {
+        assert!(
+            radius > 0.0 && radius.is_finite(),
+            "radius must be positive and finite"
+        );
+        assert!(
+            width >= 0.0 && width.is_finite() && height >= 0.0 && height.is_finite(),
+            "extent must be non-negative and finite"
+        );
+        let mut points = Vec::new();
+        if width == 0.0 || height == 0.0 {
+            return points;
+        }
+
+        // With this cell size, every cell contains at most one point.
+        let cell_size = radius / core::f64::consts::SQRT_2;
+        let columns = libm::ceil(width / cell_size) as usize;
+        let rows = libm::ceil(height / cell_size) as usize;
+        // Index into `points` plus one, or zero for empty cells.
+        let mut grid = vec![0usize; columns * rows];
+        let cell_of = |[x, y]: [f64; 2]| {
+            let col = ((x / cell_size) as usize).min(columns - 1);
+            let row = ((y / cell_size) as usize).min(rows - 1);
+            (col, row)
+        };
+
+        let first = [self.read_f64() * width, self.read_f64() * height];
+        let (col, row) = cell_of(first);
+        grid[row * columns + col] = 1;
+        points.push(first);
+        let mut active = vec![0];
+
+        while !active.is_empty() {
+            let active_idx = self.read_u64_below(active.len() as u64) as usize;
+            let [px, py] = points[active[active_idx]];
+            let mut found = false;
+            for _ in 0..ATTEMPTS {
+                let angle = self.read_f64() * core::f64::consts::TAU;
+                let dist = radius * libm::sqrt(1.0 + 3.0 * self.read_f64());
+                let candidate = [px + dist * libm::cos(angle), py + dist * libm::sin(angle)];
+                let [cx, cy] = candidate;
+                if !(0.0..width).contains(&cx) || !(0.0..height).contains(&cy) {
+                    continue;
+                }
+                let (col, row) = cell_of(candidate);
+                // Points within `radius` can be at most two cells away in each direction.
+                let too_close = (row.saturating_sub(2)..(row + 3).min(rows)).any(|r| {
+                    (col.saturating_sub(2)..(col + 3).min(columns)).any(|c| {
+                        let idx = grid[r * columns + c];
+                        idx != 0 && {
+                            let [qx, qy] = points[idx - 1];
+                            let (dx, dy) = (qx - cx, qy - cy);
+                            dx * dx + dy * dy < radius * radius
+                        }
+                    })
+                });
+                if !too_close {
+                    points.push(candidate);
+                    grid[row * columns + col] = points.len();
+                    active.push(points.len() - 1);
+                    found = true;
+                    break;
+                }
+            }
+            if !found {
+                active.swap_remove(active_idx);
+            }
+        }
+        points
+    }
+}
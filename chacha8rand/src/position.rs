@@ -0,0 +1,137 @@
+use crate::BUF_OUTPUT_LEN;
+
+/// An absolute position in the output stream of a [`ChaCha8Rand`][crate::ChaCha8Rand] instance.
+///
+/// The generator works in *iterations*: each one turns the current seed into 992 bytes of output
+/// plus 32 bytes that become the seed of the next iteration. A position in the overall byte stream
+/// can therefore be written either as a single (large) byte offset, or as an iteration index plus
+/// an offset within that iteration. This type stores the latter and converts to and from the
+/// former, so you don't have to sprinkle `992`s and off-by-one-prone divisions around your code.
+///
+/// Positions are always canonical: the offset within the iteration is strictly less than
+/// [`StreamPosition::ITERATION_LEN`]. Consuming *all* output of iteration `i` puts you at offset 0
+/// of iteration `i + 1`. Thanks to this, the derived comparisons agree with comparing byte offsets.
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::StreamPosition;
+///
+/// let pos = StreamPosition::from_byte_offset(2000).unwrap();
+/// assert_eq!((pos.iteration(), pos.offset()), (2, 16));
+/// assert_eq!(pos.checked_sub(17), StreamPosition::new(1, 991));
+/// assert_eq!(pos.distance(StreamPosition::START), 2000);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StreamPosition {
+    // Field order matters for the derived `Ord` impl.
+    iteration: u64,
+    offset: u16,
+}
+
+impl StreamPosition {
+    /// The number of output bytes produced by each iteration of ChaCha8Rand.
+    pub const ITERATION_LEN: u16 = BUF_OUTPUT_LEN as u16;
+
+    /// The very first byte of the stream.
+    pub const START: Self = StreamPosition {
+        iteration: 0,
+        offset: 0,
+    };
+
+    /// The largest representable position.
+    pub const MAX: Self = StreamPosition {
+        iteration: u64::MAX,
+        offset: Self::ITERATION_LEN - 1,
+    };
+
+    /// Create a position from an iteration index and an offset within that iteration.
+    ///
+    /// Returns `None` if `offset` isn't less than [`StreamPosition::ITERATION_LEN`], because that
+    /// wouldn't be a canonical position.
+    #[inline]
+    pub const fn new(iteration: u64, offset: u16) -> Option<Self> {
+        if offset >= Self::ITERATION_LEN {
+            return None;
+        }
+        Some(StreamPosition { iteration, offset })
+    }
+
+    /// The first byte of `iteration`. Unlike [`StreamPosition::new`], this can't fail.
+    #[inline]
+    pub(crate) const fn iteration_start(iteration: u64) -> Self {
+        StreamPosition {
+            iteration,
+            offset: 0,
+        }
+    }
+
+    /// Convert an absolute byte offset (counted from the start of the stream) into a position.
+    ///
+    /// Returns `None` if the iteration index doesn't fit into a `u64`.
+    #[inline]
+    pub const fn from_byte_offset(bytes: u128) -> Option<Self> {
+        let iter_len = Self::ITERATION_LEN as u128;
+        let iteration = bytes / iter_len;
+        if iteration > u64::MAX as u128 {
+            return None;
+        }
+        Some(StreamPosition {
+            iteration: iteration as u64,
+            offset: (bytes % iter_len) as u16,
+        })
+    }
+
+    /// Convert this position into an absolute byte offset (counted from the start of the stream).
+    ///
+    /// This never overflows: even [`StreamPosition::MAX`] is less than 2<sup>74</sup>.
+    #[inline]
+    pub const fn to_byte_offset(self) -> u128 {
+        self.iteration as u128 * Self::ITERATION_LEN as u128 + self.offset as u128
+    }
+
+    /// The index of the iteration this position falls into.
+    #[inline]
+    pub const fn iteration(self) -> u64 {
+        self.iteration
+    }
+
+    /// The offset within the iteration, always less than [`StreamPosition::ITERATION_LEN`].
+    #[inline]
+    pub const fn offset(self) -> u16 {
+        self.offset
+    }
+
+    /// Move `bytes` forward in the stream, or return `None` if that would go past
+    /// [`StreamPosition::MAX`].
+    #[inline]
+    pub const fn checked_add(self, bytes: u128) -> Option<Self> {
+        match self.to_byte_offset().checked_add(bytes) {
+            Some(sum) => Self::from_byte_offset(sum),
+            None => None,
+        }
+    }
+
+    /// Move `bytes` backward in the stream, or return `None` if that would go before
+    /// [`StreamPosition::START`].
+    #[inline]
+    pub const fn checked_sub(self, bytes: u128) -> Option<Self> {
+        match self.to_byte_offset().checked_sub(bytes) {
+            Some(diff) => Self::from_byte_offset(diff),
+            None => None,
+        }
+    }
+
+    /// The number of bytes between two positions, regardless of which one comes first.
+    #[inline]
+    pub const fn distance(self, other: Self) -> u128 {
+        self.to_byte_offset().abs_diff(other.to_byte_offset())
+    }
+
+    /// The number of bytes from `self` forward to `later`, or `None` if `later` actually comes
+    /// before `self`.
+    #[inline]
+    pub const fn bytes_until(self, later: Self) -> Option<u128> {
+        later.to_byte_offset().checked_sub(self.to_byte_offset())
+    }
+}
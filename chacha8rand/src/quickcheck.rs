@@ -0,0 +1,76 @@
+use alloc::boxed::Box;
+
+use quickcheck::{empty_shrinker, single_shrinker, Arbitrary, Gen};
+
+use crate::{ChaCha8State, Seed, BUF_OUTPUT_LEN};
+
+fn arbitrary_bytes(g: &mut Gen) -> [u8; 32] {
+    core::array::from_fn(|_| u8::arbitrary(g))
+}
+
+/// Property testing with quickcheck. Requires crate feature `quickcheck`.
+///
+/// Seeds are uniformly random. The bytes of a seed have no meaning of their own (a seed that
+/// differs in one bit gives a completely unrelated stream), so shrinking only tries the all-zero
+/// seed.
+impl Arbitrary for Seed {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Seed(arbitrary_bytes(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        if self.0 == [0; 32] {
+            empty_shrinker()
+        } else {
+            single_shrinker(Seed([0; 32]))
+        }
+    }
+}
+
+/// Property testing with quickcheck. Requires crate feature `quickcheck`.
+///
+/// Generated snapshots are always valid, i.e., [`ChaCha8Rand::try_restore_state`] accepts them.
+/// The seed is arbitrary like for [`Seed`], and `iteration` uses quickcheck's `u64` generator.
+/// `bytes_consumed` is zero or at the end of the buffer (the two edge cases of an iteration) a
+/// quarter of the time each, and uniformly random in between otherwise.
+///
+/// Shrinking tries the all-zero seed first, then shrinks `iteration` and `bytes_consumed` towards
+/// zero, one at a time. Shrunk snapshots are still valid. To test how your code handles corrupted
+/// snapshots, construct those explicitly instead.
+///
+/// [`ChaCha8Rand::try_restore_state`]: crate::ChaCha8Rand::try_restore_state
+impl Arbitrary for ChaCha8State {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let end = BUF_OUTPUT_LEN as u16;
+        let bytes_consumed = match u8::arbitrary(g) % 4 {
+            0 => 0,
+            1 => end,
+            _ => u16::arbitrary(g) % (end + 1),
+        };
+        ChaCha8State {
+            seed: arbitrary_bytes(g),
+            bytes_consumed,
+            iteration: u64::arbitrary(g),
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let this = *self;
+        let seeds = Seed(this.seed).shrink().map(move |seed| ChaCha8State {
+            seed: seed.0,
+            ..this
+        });
+        let iterations = this
+            .iteration
+            .shrink()
+            .map(move |iteration| ChaCha8State { iteration, ..this });
+        let bytes_consumed = this
+            .bytes_consumed
+            .shrink()
+            .map(move |bytes_consumed| ChaCha8State {
+                bytes_consumed,
+                ..this
+            });
+        Box::new(seeds.chain(iterations).chain(bytes_consumed))
+    }
+}
@@ -0,0 +1,126 @@
+use crate::ChaCha8Rand;
+
+/// Types that know how to sample a value of themselves from a [`ChaCha8Rand`].
+///
+/// Use [`ChaCha8Rand::sample`] to call this, e.g., `rng.sample::<MyEnum>()`. With the `macros`
+/// feature, you can derive it for structs with `#[derive(Random)]` and for fieldless enums with
+/// `#[derive(UniformEnum)]`.
+///
+/// The implementations in this crate consume bytes as follows:
+///
+/// * Integers consume exactly as many bytes as they're wide and interpret them as little-endian,
+///   just like [`ChaCha8Rand::read_u32`] and [`ChaCha8Rand::read_u64`]. `usize` and `isize` are
+///   deliberately left out because their width depends on the target.
+/// * `bool` consumes one byte and uses its lowest bit.
+/// * `f32` and `f64` consume four and eight bytes, respectively, and are uniformly distributed in
+///   `[0, 1)` with 24 and 53 bits of precision.
+/// * Arrays sample their elements in order, and `()` doesn't consume anything.
+///
+/// Implementations should only depend on the bytes they read from the generator, so that sampling
+/// is just as reproducible as the underlying byte stream. Changing how a type is sampled (e.g.,
+/// adding an enum variant) generally changes both the sampled values and how many bytes are
+/// consumed, so it shifts everything that's read from the same generator afterwards.
+pub trait Random {
+    /// Sample a value, consuming as many bytes from `rng` as necessary.
+    fn random(rng: &mut ChaCha8Rand) -> Self;
+}
+
+impl ChaCha8Rand {
+    /// Sample a value of any type that implements [`Random`].
+    ///
+    /// This is the same as `T::random(self)`, but usually reads better and lets type inference
+    /// fill in `T` when possible.
+    #[inline]
+    pub fn sample<T: Random>(&mut self) -> T {
+        T::random(self)
+    }
+}
+
+macro_rules! impl_random_int {
+    ($($ty:ty),*) => {$(
+        impl Random for $ty {
+            #[inline]
+            fn random(rng: &mut ChaCha8Rand) -> Self {
+                <$ty>::from_le_bytes(rng.read_array())
+            }
+        }
+    )*};
+}
+
+impl_random_int!(u8, u16, i8, i16);
+
+impl Random for u32 {
+    #[inline]
+    fn random(rng: &mut ChaCha8Rand) -> Self {
+        rng.read_u32()
+    }
+}
+
+impl Random for u64 {
+    #[inline]
+    fn random(rng: &mut ChaCha8Rand) -> Self {
+        rng.read_u64()
+    }
+}
+
+impl Random for u128 {
+    #[inline]
+    fn random(rng: &mut ChaCha8Rand) -> Self {
+        rng.read_u128()
+    }
+}
+
+impl Random for i32 {
+    #[inline]
+    fn random(rng: &mut ChaCha8Rand) -> Self {
+        rng.read_u32() as i32
+    }
+}
+
+impl Random for i64 {
+    #[inline]
+    fn random(rng: &mut ChaCha8Rand) -> Self {
+        rng.read_u64() as i64
+    }
+}
+
+impl Random for i128 {
+    #[inline]
+    fn random(rng: &mut ChaCha8Rand) -> Self {
+        rng.read_u128() as i128
+    }
+}
+
+impl Random for bool {
+    #[inline]
+    fn random(rng: &mut ChaCha8Rand) -> Self {
+        u8::random(rng) & 1 != 0
+    }
+}
+
+impl Random for f32 {
+    #[inline]
+    fn random(rng: &mut ChaCha8Rand) -> Self {
+        rng.read_f32()
+    }
+}
+
+impl Random for f64 {
+    #[inline]
+    fn random(rng: &mut ChaCha8Rand) -> Self {
+        rng.read_f64()
+    }
+}
+
+impl Random for () {
+    #[inline]
+    fn random(_rng: &mut ChaCha8Rand) -> Self {}
+}
+
+impl<T: Random, const N: usize> Random for [T; N] {
+    #[inline]
+    fn random(rng: &mut ChaCha8Rand) -> Self {
+        // `array::from_fn` calls the closure for indices in increasing order.
+        core::array::from_fn(|_| T::random(rng))
+    }
+}
@@ -0,0 +1,157 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::fmt;
+
+use crate::{ChaCha8Rand, ChaCha8State, RestoreStateError, BUF_OUTPUT_LEN};
+
+/// A collection of generators derived from one root seed, each identified by a name.
+///
+/// Programs like games often want a separate generator for every aspect of their randomness
+/// (map generation, AI, random events, ...) so that consuming more or less randomness in one of
+/// them doesn't change what happens in the others. This type manages such a set of generators:
+/// [`RngSet::get`] gives access to the member with a given name, and [`RngSet::snapshot`] and
+/// [`RngSet::restore`] save and load the state of all members at once, e.g., for save games or
+/// for rolling back a simulation.
+///
+/// Members are created on first access. The seed of a member only depends on the root seed and
+/// the member's name, not on which other members exist or in which order they were created. It's
+/// derived by creating a generator from the root seed and mixing in the UTF-8 bytes of the name
+/// with [`ChaCha8Rand::mix_in`], which re-seeds it. This is a stable part of the API, and you can
+/// reproduce it yourself (see the example below). Like for [`ChaCha8Rand`], the `Debug` impl
+/// doesn't print any seeds.
+///
+/// Requires crate feature `alloc`.
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::{ChaCha8Rand, RngSet};
+///
+/// let root_seed = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+/// let mut rngs = RngSet::new(root_seed);
+/// let map_tile = rngs.get("map").read_u32();
+/// let save = rngs.snapshot();
+/// let ai_move = rngs.get("ai").read_u32();
+/// rngs.get("map").read_bytes(&mut [0; 100]);
+///
+/// rngs.restore(&save)?;
+/// assert_eq!(rngs.get("ai").read_u32(), ai_move);
+///
+/// let mut derive = ChaCha8Rand::new(root_seed);
+/// derive.mix_in(b"map");
+/// assert_eq!(ChaCha8Rand::new(&derive.initial_seed()).read_u32(), map_tile);
+/// # Ok::<(), chacha8rand::RestoreStateError>(())
+/// ```
+#[derive(Clone)]
+pub struct RngSet {
+    root_seed: [u8; 32],
+    members: BTreeMap<String, ChaCha8Rand>,
+}
+
+/// Snapshot of all members of an [`RngSet`], see [`RngSet::snapshot`].
+///
+/// Like [`ChaCha8State`], the fields are public so you can store it in whatever way you like.
+/// Snapshots only list the members that existed when the snapshot was taken. Members that are
+/// created later start from their initial state after restoring, just like they would have
+/// without the snapshot.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RngSetState {
+    /// The root seed of the set.
+    pub root_seed: [u8; 32],
+    /// The state of each member, sorted by name.
+    pub members: Vec<(String, ChaCha8State)>,
+}
+
+impl fmt::Debug for RngSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RngSet")
+            .field("members", &self.members.keys())
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Debug for RngSetState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<&String> = self.members.iter().map(|(name, _)| name).collect();
+        f.debug_struct("RngSetState")
+            .field("members", &names)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RngSet {
+    /// Create an empty set whose members will be derived from `root_seed`.
+    pub fn new(root_seed: &[u8; 32]) -> Self {
+        RngSet {
+            root_seed: *root_seed,
+            members: BTreeMap::new(),
+        }
+    }
+
+    /// The member with the given name, created from its derived seed if it doesn't exist yet.
+    pub fn get(&mut self, name: &str) -> &mut ChaCha8Rand {
+        if !self.members.contains_key(name) {
+            let rng = ChaCha8Rand::new(&self.member_seed(name));
+            self.members.insert(String::from(name), rng);
+        }
+        self.members
+            .get_mut(name)
+            .expect("member was just inserted")
+    }
+
+    /// The names of all members that exist so far, in sorted order.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.members.keys().map(String::as_str)
+    }
+
+    /// Take a snapshot of the state of all members.
+    pub fn snapshot(&self) -> RngSetState {
+        RngSetState {
+            root_seed: self.root_seed,
+            members: self
+                .members
+                .iter()
+                .map(|(name, rng)| (name.clone(), rng.clone_state()))
+                .collect(),
+        }
+    }
+
+    /// Restore all members from a snapshot, replacing the root seed with the snapshot's.
+    ///
+    /// Afterwards, the set has exactly the members listed in the snapshot. Members that didn't
+    /// exist when the snapshot was taken are removed, so they start over from their initial
+    /// state when they're accessed again.
+    ///
+    /// # Errors
+    ///
+    /// Fails if any member's state is invalid, as described for
+    /// [`ChaCha8Rand::try_restore_state`]. In that case, nothing is changed, so the set never ends
+    /// up with some members restored and others not.
+    pub fn restore(&mut self, state: &RngSetState) -> Result<(), RestoreStateError> {
+        let all_valid = state
+            .members
+            .iter()
+            .all(|(_, member)| usize::from(member.bytes_consumed) <= BUF_OUTPUT_LEN);
+        if !all_valid {
+            return Err(RestoreStateError { _private: () });
+        }
+        self.root_seed = state.root_seed;
+        self.members.retain(|name, _| {
+            state
+                .members
+                .iter()
+                .any(|(snapshot_name, _)| snapshot_name == name)
+        });
+        for (name, member) in &state.members {
+            let rng = self.get(name);
+            rng.try_restore_state(member)
+                .expect("member states were validated");
+        }
+        Ok(())
+    }
+
+    fn member_seed(&self, name: &str) -> [u8; 32] {
+        let mut rng = ChaCha8Rand::new(&self.root_seed);
+        rng.mix_in(name.as_bytes());
+        rng.initial_seed()
+    }
+}
@@ -0,0 +1,294 @@
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+use alloc::collections::BinaryHeap;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+use core::cmp::Ordering;
+use core::ops::Range;
+
+use crate::ChaCha8Rand;
+
+impl ChaCha8Rand {
+    /// Fill `out` with distinct values sampled uniformly from `range`, in ascending order.
+    ///
+    /// Every subset of `out.len()` values from the range is equally likely, and the work doesn't
+    /// depend on the size of the range, so it works just as well for ranges with billions of
+    /// elements. It uses Floyd's algorithm, which needs exactly one bounded random number per
+    /// sampled value. The output slice doubles as the set of values chosen so far, kept sorted so
+    /// that membership checks are binary searches, but insertions still take time linear in the
+    /// number of values. This is fine for up to thousands of values. For more than that, consider
+    /// [`ChaCha8Rand::sample_distinct_u64`] (with the `alloc` feature), which returns exactly the
+    /// same values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range contains fewer than `out.len()` values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let mut offsets = [0; 8];
+    /// rng.sample_distinct_u64_into(0..10_000_000_000, &mut offsets);
+    /// assert!(offsets.windows(2).all(|w| w[0] < w[1]));
+    /// ```
+    pub fn sample_distinct_u64_into(&mut self, range: Range<u64>, out: &mut [u64]) {
+        let mut len = 0;
+        self.floyd(range, out.len(), |value| {
+            match out[..len].binary_search(&value) {
+                Ok(_) => return false,
+                Err(idx) => {
+                    out.copy_within(idx..len, idx + 1);
+                    out[idx] = value;
+                }
+            }
+            len += 1;
+            true
+        });
+    }
+
+    /// Sample `k` distinct values uniformly from `range` and return them in ascending order.
+    ///
+    /// This is the same as [`ChaCha8Rand::sample_distinct_u64_into`] (and returns the same values
+    /// for the same generator state), but uses a B-tree to keep track of the chosen values, so it
+    /// scales to large `k`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range contains fewer than `k` values.
+    #[cfg(feature = "alloc")]
+    pub fn sample_distinct_u64(&mut self, range: Range<u64>, k: usize) -> Vec<u64> {
+        let mut chosen = alloc::collections::BTreeSet::new();
+        self.floyd(range, k, |value| chosen.insert(value));
+        chosen.into_iter().collect()
+    }
+
+    /// Sample up to `k` items from a stream of `(item, weight)` pairs, with probability
+    /// proportional to weight, in a single pass.
+    ///
+    /// This is weighted sampling *without* replacement: every item is chosen at most once, and
+    /// the first item of the result is picked with probability proportional to its weight among
+    /// all items, the second one proportional to its weight among the remaining items, and so on.
+    /// The result is in that order. Only `k` items are kept in memory at any time, so the stream
+    /// can be much larger than what fits in memory.
+    ///
+    /// This uses the A-ExpJ algorithm by Efraimidis and Spirakis. Each item conceptually gets a
+    /// random key that grows with its weight and the `k` items with the largest keys are the
+    /// sample. Rather than drawing a key for every item, it draws how much total weight it can
+    /// skip before the next item enters the reservoir, so it only needs random numbers for the
+    /// first `k` items and for those that actually enter the reservoir later (which happens
+    /// `O(k log(n / k))` times for `n` items with similar weights).
+    ///
+    /// Items with zero weight are never chosen. If there are `k` or fewer items with positive
+    /// weight, all of them are returned (still in a random, weight-dependent order).
+    ///
+    /// # Panics
+    ///
+    /// Panics if any weight is negative, infinite or NaN.
+    ///
+    /// Requires crate features `alloc` and `distributions`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let log_lines = ["GET /", "GET /favicon.ico", "POST /login", "GET /admin"];
+    /// // Sample by line length, just because.
+    /// let weighted = log_lines.iter().map(|line| (line, line.len() as f64));
+    /// let sample = rng.weighted_reservoir_sample(weighted, 2);
+    /// assert_eq!(sample.len(), 2);
+    /// ```
+    #[cfg(all(feature = "alloc", feature = "distributions"))]
+    pub fn weighted_reservoir_sample<T>(
+        &mut self,
+        items: impl IntoIterator<Item = (T, f64)>,
+        k: usize,
+    ) -> Vec<T> {
+        if k == 0 {
+            return Vec::new();
+        }
+        // Keys are stored as logarithms, i.e., `ln(u) / w` instead of `u^(1 / w)`, which avoids
+        // precision problems with tiny weights and saves some `pow` calls. `k` can be far larger
+        // than the number of items (e.g., `usize::MAX` to take all of them), so the reservoir
+        // isn't allocated up front.
+        let mut reservoir = BinaryHeap::new();
+        let mut items = items.into_iter().filter(|(_, weight)| {
+            assert!(
+                weight.is_finite() && *weight >= 0.0,
+                "weights must be finite and non-negative, got {weight}"
+            );
+            *weight > 0.0
+        });
+        for (item, weight) in items.by_ref() {
+            let key = libm::log(self.read_unit_f64_nonzero()) / weight;
+            reservoir.push(ReservoirEntry { key, item });
+            if reservoir.len() == k {
+                break;
+            }
+        }
+        if reservoir.len() == k {
+            let mut threshold = reservoir.peek().map_or(0.0, |entry| entry.key);
+            let mut skip = self.reservoir_skip(threshold);
+            for (item, weight) in items {
+                skip -= weight;
+                if skip > 0.0 {
+                    continue;
+                }
+                // The new item's key is uniformly distributed among those larger than the
+                // threshold key (in terms of `u^(1 / w)`, which is between `exp(threshold)` and 1).
+                let t = libm::exp(threshold * weight);
+                let u = t + (1.0 - t) * self.read_unit_f64_nonzero();
+                let key = libm::log(u) / weight;
+                *reservoir.peek_mut().expect("reservoir can't be empty") =
+                    ReservoirEntry { key, item };
+                threshold = reservoir.peek().map_or(0.0, |entry| entry.key);
+                skip = self.reservoir_skip(threshold);
+            }
+        }
+        reservoir
+            .into_sorted_vec()
+            .into_iter()
+            .map(|entry| entry.item)
+            .collect()
+    }
+
+    /// Shuffle `items` so that items with larger weights tend to come first.
+    ///
+    /// The first item of the result is picked with probability proportional to its weight among
+    /// all items, the second one proportional to its weight among the remaining items, and so on.
+    /// This is the same distribution that [`ChaCha8Rand::weighted_reservoir_sample`] samples from,
+    /// but for the entire slice and in place. `weights[i]` is the weight of `items[i]`.
+    ///
+    /// It uses the exponential keys method (Efraimidis and Spirakis): item `i` gets the key
+    /// `ln(U) / weights[i]` for a `U` uniformly distributed in `(0, 1]`, and the items are sorted
+    /// by descending key. One key is sampled per item, in order, consuming eight bytes each. Items
+    /// with zero weight get the key `-inf` (even for `U = 1`), so they end up at the end, in their
+    /// original relative order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `items` and `weights` have different lengths, or if any weight is negative,
+    /// infinite, or NaN.
+    ///
+    /// Requires crate features `alloc` and `distributions`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let mut ads = ["premium", "standard", "house ad"];
+    /// rng.shuffle_weighted(&mut ads, &[10u32, 3, 0]);
+    /// assert_eq!(ads[2], "house ad");
+    /// ```
+    #[cfg(all(feature = "alloc", feature = "distributions"))]
+    pub fn shuffle_weighted<T, W: Copy + Into<f64>>(&mut self, items: &mut [T], weights: &[W]) {
+        assert_eq!(
+            items.len(),
+            weights.len(),
+            "there must be exactly one weight per item"
+        );
+        let mut keys: Vec<(f64, usize)> = weights
+            .iter()
+            .enumerate()
+            .map(|(i, &weight)| {
+                let weight: f64 = weight.into();
+                assert!(
+                    weight.is_finite() && weight >= 0.0,
+                    "weights must be finite and non-negative, got {weight}"
+                );
+                (exponential_key(self.read_unit_f64_nonzero(), weight), i)
+            })
+            .collect();
+        // Sorting is stable, so items with zero weight (key -inf) keep their relative order.
+        keys.sort_by(|a, b| b.0.total_cmp(&a.0));
+        let mut order: Vec<usize> = keys.into_iter().map(|(_, i)| i).collect();
+        // Apply the permutation (`items[i]` becomes the old `items[order[i]]`) cycle by cycle.
+        // Every position that's already in its final place is marked with `order[j] = j`.
+        for start in 0..items.len() {
+            let mut j = start;
+            while order[j] != start && order[j] != j {
+                let next = order[j];
+                items.swap(j, next);
+                order[j] = j;
+                j = next;
+            }
+            order[j] = j;
+        }
+    }
+
+    /// How much weight A-ExpJ skips before the next item enters the reservoir, given the
+    /// logarithm of the smallest key currently in the reservoir.
+    #[cfg(all(feature = "alloc", feature = "distributions"))]
+    fn reservoir_skip(&mut self, threshold: f64) -> f64 {
+        let skip = libm::log(self.read_unit_f64_nonzero()) / threshold;
+        // If the threshold is zero (the largest possible key), nothing can ever replace it.
+        if skip >= 0.0 {
+            skip
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    /// Floyd's algorithm for sampling `k` distinct values from `range`. The `insert` callback
+    /// must add the value to the set of chosen values and return `false` if it was already in it.
+    fn floyd(&mut self, range: Range<u64>, k: usize, mut insert: impl FnMut(u64) -> bool) {
+        let n = range.end.saturating_sub(range.start);
+        let k = u64::try_from(k).ok().filter(|&k| k <= n);
+        let k = k.expect("can't sample more distinct values than the range contains");
+        for j in n - k..n {
+            let t = range.start + self.read_u64_below(j + 1);
+            if !insert(t) {
+                let inserted = insert(range.start + j);
+                debug_assert!(inserted);
+            }
+        }
+    }
+}
+
+/// Orders entries by *descending* key, so that `BinaryHeap` (a max-heap) keeps the entry with
+/// the smallest key at the top and `into_sorted_vec` puts the largest key first.
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+struct ReservoirEntry<T> {
+    key: f64,
+    item: T,
+}
+
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+impl<T> Ord for ReservoirEntry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key.total_cmp(&self.key)
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+impl<T> PartialOrd for ReservoirEntry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+impl<T> PartialEq for ReservoirEntry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+impl<T> Eq for ReservoirEntry<T> {}
+
+/// The key `ln(u) / weight` of the exponential keys method, for `u` in `(0, 1]`.
+///
+/// Zero weights get `-inf` explicitly. Computing it would give `0 / 0` for `u = 1`, and the sign
+/// of that NaN differs between platforms, so `total_cmp` could sort it first.
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+pub(crate) fn exponential_key(u: f64, weight: f64) -> f64 {
+    if weight == 0.0 {
+        f64::NEG_INFINITY
+    } else {
+        libm::log(u) / weight
+    }
+}
@@ -0,0 +1,154 @@
+use core::{fmt, str};
+
+/// A 32-byte seed for [`ChaCha8Rand`][crate::ChaCha8Rand].
+///
+/// The generator itself takes plain `[u8; 32]` arrays, so you don't *need* this type. It's a thin
+/// wrapper that adds some conveniences for the bytes you'll want to store, log, or print somewhere
+/// to reproduce a run later. In particular, it implements [`fmt::LowerHex`] and
+/// [`fmt::UpperHex`], which print the 32 bytes in order as 64 hex digits (with a `0x` prefix if
+/// you use the `#` flag). Like for [`ChaCha8State`][crate::ChaCha8State], the `Debug` impl doesn't
+/// print the bytes, so a seed only ends up in your logs if you ask for it explicitly.
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::{ChaCha8Rand, Seed};
+///
+/// let seed = Seed(*b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// let hex = format!("{seed:x}");
+/// assert_eq!(&hex[..8], "41424344");
+/// assert_eq!(hex.as_bytes(), seed.to_hex());
+/// let mut rng = ChaCha8Rand::new(&seed.0);
+/// # let _ = rng.read_u32();
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Seed(pub [u8; 32]);
+
+impl Seed {
+    /// Encode the seed as 64 lowercase ASCII hex digits, without allocating.
+    ///
+    /// The result is always valid UTF-8, so you can turn it into a `&str` with
+    /// [`core::str::from_utf8`] and unwrap the result.
+    pub const fn to_hex(&self) -> [u8; 64] {
+        hex_encode(&self.0, b"0123456789abcdef")
+    }
+
+    /// Like [`Seed::to_hex`] but with uppercase hex digits.
+    pub const fn to_hex_upper(&self) -> [u8; 64] {
+        hex_encode(&self.0, b"0123456789ABCDEF")
+    }
+
+    /// Decode 64 hex digits (upper- or lowercase) into a seed.
+    ///
+    /// This is the inverse of [`Seed::to_hex`] and [`Seed::to_hex_upper`]. It returns `None` if
+    /// any of the bytes isn't an ASCII hex digit.
+    pub const fn from_hex(hex: &[u8; 64]) -> Option<Self> {
+        let mut bytes = [0; 32];
+        let mut i = 0;
+        while i < bytes.len() {
+            let (Some(hi), Some(lo)) = (hex_digit(hex[2 * i]), hex_digit(hex[2 * i + 1])) else {
+                return None;
+            };
+            bytes[i] = (hi << 4) | lo;
+            i += 1;
+        }
+        Some(Seed(bytes))
+    }
+
+    /// Expand a 64-bit integer into a seed with SplitMix64.
+    ///
+    /// The seed consists of the first four outputs of SplitMix64 (as described by Steele, Lea, and
+    /// Flood) when its state is initialized to `x`, each encoded as eight little-endian bytes. This
+    /// mapping is a stable part of the API, so it's safe to store and share the `u64` instead of
+    /// the full seed. It's unrelated to what `rand_core`'s `SeedableRng::seed_from_u64` does.
+    ///
+    /// Keep in mind that there are only 2<sup>64</sup> such seeds, which is plenty to get distinct
+    /// streams for tests or simulations, but much too few for anything that needs to be
+    /// unpredictable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::Seed;
+    /// let Seed(seed) = Seed::from_u64(0);
+    /// // The first output of SplitMix64 with state 0 is 0xe220a8397b1dcdaf.
+    /// assert_eq!(seed[..8], 0xe220a8397b1dcdaf_u64.to_le_bytes());
+    /// ```
+    pub const fn from_u64(x: u64) -> Self {
+        let mut state = x;
+        let mut bytes = [0; 32];
+        let mut i = 0;
+        while i < bytes.len() {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^= z >> 31;
+            let word = z.to_le_bytes();
+            let mut j = 0;
+            while j < word.len() {
+                bytes[i + j] = word[j];
+                j += 1;
+            }
+            i += word.len();
+        }
+        Seed(bytes)
+    }
+}
+
+const fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+const fn hex_encode(bytes: &[u8; 32], digits: &[u8; 16]) -> [u8; 64] {
+    let mut hex = [0; 64];
+    let mut i = 0;
+    while i < bytes.len() {
+        hex[2 * i] = digits[(bytes[i] >> 4) as usize];
+        hex[2 * i + 1] = digits[(bytes[i] & 0xF) as usize];
+        i += 1;
+    }
+    hex
+}
+
+fn fmt_hex(hex: &[u8; 64], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let hex = str::from_utf8(hex).expect("hex digits are ASCII");
+    f.pad_integral(true, "0x", hex)
+}
+
+impl From<[u8; 32]> for Seed {
+    #[inline]
+    fn from(bytes: [u8; 32]) -> Self {
+        Seed(bytes)
+    }
+}
+
+impl From<Seed> for [u8; 32] {
+    #[inline]
+    fn from(seed: Seed) -> Self {
+        seed.0
+    }
+}
+
+impl fmt::Debug for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Seed { .. }")
+    }
+}
+
+impl fmt::LowerHex for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_hex(&self.to_hex(), f)
+    }
+}
+
+impl fmt::UpperHex for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_hex(&self.to_hex_upper(), f)
+    }
+}
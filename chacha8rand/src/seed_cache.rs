@@ -0,0 +1,128 @@
+use core::fmt;
+
+#[cfg(feature = "audit")]
+use crate::audit::AuditLog;
+#[cfg(feature = "stats")]
+use crate::stats::Counters;
+use crate::ChaCha8Rand;
+
+/// Speeds up creating generators from the same seed over and over again.
+///
+/// Every [`ChaCha8Rand::new`] and [`ChaCha8Rand::set_seed`] call eagerly computes the first
+/// iteration of output for the new seed. That's usually negligible, but some programs (e.g.,
+/// property tests that restart from a fixed seed for every test case) construct thousands of
+/// generators from one and the same seed. With the portable scalar backend in particular, the
+/// redundant work can then become a noticeable fraction of the total run time.
+///
+/// This cache remembers the most recently used seed together with a freshly seeded generator.
+/// When asked for the same seed again, it clones that generator, which is just a copy of about
+/// 1.1 KiB. Any other seed replaces the cached entry. The output is exactly the same as
+/// without the cache, so it's purely a performance optimization.
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::{ChaCha8Rand, SeedCache};
+///
+/// let seed = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+/// let mut cache = SeedCache::new();
+/// for _ in 0..1000 {
+///     let mut rng = cache.new_rng(seed);
+///     assert_eq!(rng.read_u64(), 0xb773b6063d4616a5);
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct SeedCache {
+    entry: Option<CacheEntry>,
+}
+
+#[derive(Clone)]
+struct CacheEntry {
+    seed: [u8; 32],
+    fresh: ChaCha8Rand,
+}
+
+impl fmt::Debug for SeedCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SeedCache { .. }")
+    }
+}
+
+impl SeedCache {
+    /// Create an empty cache.
+    pub const fn new() -> Self {
+        SeedCache { entry: None }
+    }
+
+    /// Equivalent to `ChaCha8Rand::new(seed)`, but reuses the cached work if possible.
+    pub fn new_rng(&mut self, seed: &[u8; 32]) -> ChaCha8Rand {
+        #[cfg_attr(not(feature = "stats"), allow(unused_mut))]
+        let mut rng = self.fresh_rng(seed).clone();
+        // Count the cached refill as if the new generator had done it itself.
+        #[cfg(feature = "stats")]
+        {
+            rng.counters = Counters::new(1);
+        }
+        rng
+    }
+
+    /// Equivalent to `rng.set_seed(seed)`, but reuses the cached work if possible.
+    ///
+    /// The generator keeps using the same SIMD backend it used before.
+    pub fn set_seed(&mut self, rng: &mut ChaCha8Rand, seed: &[u8; 32]) {
+        // The cached generator is on the spec's stream, so it's no use for other streams.
+        if rng.stream() != 0 {
+            rng.set_seed(seed);
+            return;
+        }
+        let backend = rng.backend;
+        #[cfg(feature = "stats")]
+        let counters = {
+            let mut counters = rng.counters.clone();
+            counters.reseed(rng.position());
+            counters.count_refill();
+            counters
+        };
+        #[cfg(feature = "output_digest")]
+        let digest = {
+            rng.digest_flush();
+            rng.digest.clone()
+        };
+        #[cfg(feature = "audit")]
+        let audit = core::mem::replace(&mut rng.audit, AuditLog::disabled());
+        rng.clone_from(self.fresh_rng(seed));
+        rng.backend = backend;
+        #[cfg(feature = "stats")]
+        {
+            rng.counters = counters;
+        }
+        #[cfg(feature = "output_digest")]
+        {
+            rng.digest = digest;
+        }
+        #[cfg(feature = "audit")]
+        {
+            rng.audit = audit;
+        }
+        digest!(rng.digest_resync());
+        trace_event!("set_seed");
+    }
+
+    fn fresh_rng(&mut self, seed: &[u8; 32]) -> &ChaCha8Rand {
+        match &mut self.entry {
+            Some(entry) if entry.seed == *seed => {}
+            Some(entry) => {
+                entry.seed = *seed;
+                entry.fresh.set_seed(seed);
+            }
+            None => {
+                self.entry = Some(CacheEntry {
+                    seed: *seed,
+                    fresh: ChaCha8Rand::new(seed),
+                })
+            }
+        }
+        let entry = self.entry.as_ref().expect("entry was just filled in");
+        &entry.fresh
+    }
+}
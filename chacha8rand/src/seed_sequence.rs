@@ -0,0 +1,153 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{ChaCha8Rand, StreamPosition};
+
+/// Derives seeds for a tree of independent generators, e.g., experiment → replicate → worker.
+///
+/// A sequence is identified by a root seed (the *entropy*) and the path of *spawn keys* leading
+/// to it from the root, like in NumPy's `SeedSequence`. The root's path is empty, and the child
+/// with index `i` of a sequence with path `p` has path `p` followed by `i`. Every sequence derives
+/// one [`seed`][SeedSequence::seed] for a generator of its own, and any number of children.
+/// Children can be derived by index with [`SeedSequence::child`], or handed out one after the
+/// other with [`SeedSequence::spawn`], which keeps track of how many were already spawned.
+///
+/// This is a more structured version of the pattern shown for [`ChaCha8Rand::read_seed`]: all
+/// seeds still come from ChaCha8Rand output, but their derivation doesn't depend on the order in
+/// which you derive them or on anything else you do with the generators. As long as you use the
+/// same entropy, the sequence with a given spawn key always gives the same seed, so you can hand
+/// the spawn key to another machine or store it in a log and recreate exactly that sequence later
+/// with [`SeedSequence::with_spawn_key`]. Seeds for different paths come from different parts
+/// of ChaCha8Rand streams, so their generators are independent for all practical purposes.
+///
+/// Precisely, every sequence has a 32-byte key: the entropy for the root, and for child `i`, the
+/// bytes `32 * (i + 1)` to `32 * (i + 2)` of the stream generated from its parent's key. The
+/// sequence's own seed is the first 32 bytes of the stream generated from its key. This is a
+/// stable part of the API, so seeds won't change in future versions of this crate. Deriving child
+/// `i` takes time proportional to `i`, because the generator has to skip over the keys of the
+/// earlier children, but that's only noticeable for indices in the hundreds of thousands.
+/// Like for [`Seed`][crate::Seed], the `Debug` impl doesn't print the entropy or keys.
+///
+/// Requires crate feature `alloc`.
+///
+/// # Examples
+///
+/// ```
+/// use chacha8rand::{ChaCha8Rand, SeedSequence};
+///
+/// let mut root = SeedSequence::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// for _experiment in 0..3 {
+///     let mut experiment = root.spawn();
+///     for _replicate in 0..10 {
+///         let replicate = experiment.spawn();
+///         let rng = replicate.new_rng();
+///         // ... run the replicate with `rng`, and record its spawn key, e.g., [2, 7] ...
+///     }
+/// }
+///
+/// // Reproduce the replicate with spawn key [2, 7] in isolation:
+/// let replicate = SeedSequence::with_spawn_key(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456", &[2, 7]);
+/// assert_eq!(replicate.seed(), root.child(2).child(7).seed());
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct SeedSequence {
+    key: [u8; 32],
+    spawn_key: Vec<u64>,
+    children_spawned: u64,
+}
+
+impl SeedSequence {
+    /// The root sequence for the given entropy, i.e., with an empty spawn key.
+    pub fn new(entropy: &[u8; 32]) -> Self {
+        SeedSequence {
+            key: *entropy,
+            spawn_key: Vec::new(),
+            children_spawned: 0,
+        }
+    }
+
+    /// The sequence with the given spawn key, derived from the root sequence for `entropy`.
+    ///
+    /// This is the same as starting from [`SeedSequence::new`] and calling
+    /// [`SeedSequence::child`] for each element of `spawn_key` in turn.
+    pub fn with_spawn_key(entropy: &[u8; 32], spawn_key: &[u64]) -> Self {
+        let mut seq = SeedSequence::new(entropy);
+        for &index in spawn_key {
+            seq = seq.child(index);
+        }
+        seq
+    }
+
+    /// The path of child indices from the root to this sequence.
+    pub fn spawn_key(&self) -> &[u64] {
+        &self.spawn_key
+    }
+
+    /// How many children [`SeedSequence::spawn`] has handed out so far.
+    pub fn children_spawned(&self) -> u64 {
+        self.children_spawned
+    }
+
+    /// The seed for this sequence's own generator.
+    pub fn seed(&self) -> [u8; 32] {
+        ChaCha8Rand::new(&self.key).read_seed()
+    }
+
+    /// Create a generator with this sequence's [`seed`][SeedSequence::seed].
+    pub fn new_rng(&self) -> ChaCha8Rand {
+        ChaCha8Rand::new(&self.seed())
+    }
+
+    /// The child with the given index.
+    ///
+    /// This doesn't change `self`, so asking for the same index twice gives the same child both
+    /// times. The child hasn't spawned any children of its own yet.
+    pub fn child(&self, index: u64) -> SeedSequence {
+        let offset = (u128::from(index) + 1) * 32;
+        let position = StreamPosition::from_byte_offset(offset).expect("offset is in range");
+        let mut rng = ChaCha8Rand::builder(&self.key).position(position).build();
+        self.child_with_key(index, rng.read_seed())
+    }
+
+    /// Hand out the next child, starting with index zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if all `2^64` children were already spawned.
+    pub fn spawn(&mut self) -> SeedSequence {
+        let index = self.children_spawned;
+        self.children_spawned = index.checked_add(1).expect("spawned too many children");
+        self.child(index)
+    }
+
+    /// All children of this sequence in order of their index, starting from zero.
+    ///
+    /// This is faster than calling [`SeedSequence::child`] with increasing indices, because it
+    /// reads all the children's keys from a single generator. It doesn't affect which children
+    /// [`SeedSequence::spawn`] hands out.
+    pub fn children(&self) -> impl Iterator<Item = SeedSequence> + '_ {
+        let mut rng = ChaCha8Rand::new(&self.key);
+        rng.skip_bytes(32);
+        (0..=u64::MAX).map(move |index| self.child_with_key(index, rng.read_seed()))
+    }
+
+    fn child_with_key(&self, index: u64, key: [u8; 32]) -> SeedSequence {
+        let mut spawn_key = Vec::with_capacity(self.spawn_key.len() + 1);
+        spawn_key.extend_from_slice(&self.spawn_key);
+        spawn_key.push(index);
+        SeedSequence {
+            key,
+            spawn_key,
+            children_spawned: 0,
+        }
+    }
+}
+
+impl fmt::Debug for SeedSequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SeedSequence")
+            .field("spawn_key", &self.spawn_key)
+            .field("children_spawned", &self.children_spawned)
+            .finish_non_exhaustive()
+    }
+}
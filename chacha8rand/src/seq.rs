@@ -0,0 +1,157 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::ChaCha8Rand;
+
+/// Randomized operations on slices.
+///
+/// Everything here picks indices with [`ChaCha8Rand::read_u32_below`] whenever the bound fits into
+/// a `u32`, and with [`ChaCha8Rand::read_u64_below`] otherwise. This only depends on the length of
+/// the slice, not on the pointer width of the target, so results are the same on all platforms.
+impl ChaCha8Rand {
+    /// Shuffle `slice` in place, so that all permutations are equally likely.
+    ///
+    /// This is the Fisher–Yates shuffle, in the following variant, which won't change between
+    /// releases: for each `i` from `slice.len() - 1` down to `1`, pick `j` uniformly from `0..=i`
+    /// (as described above) and swap `slice[i]` with `slice[j]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let mut deck: Vec<u8> = (0..52).collect();
+    /// rng.shuffle(&mut deck);
+    /// let hand = &deck[..5];
+    /// # let _ = hand;
+    /// ```
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.read_index(i + 1);
+            slice.swap(i, j);
+        }
+    }
+
+    /// Return a reference to a uniformly random element of `slice`, or `None` if it's empty.
+    ///
+    /// Nothing is consumed from the stream for empty slices. Otherwise the index is picked as
+    /// described above, even for slices of length one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let greeting = rng.choose(&["hello", "hi", "hey"]).unwrap();
+    /// # let _ = greeting;
+    /// assert_eq!(rng.choose::<u8>(&[]), None);
+    /// ```
+    pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+        if slice.is_empty() {
+            return None;
+        }
+        Some(&slice[self.read_index(slice.len())])
+    }
+
+    /// Like [`ChaCha8Rand::choose`], but returns a mutable reference.
+    pub fn choose_mut<'a, T>(&mut self, slice: &'a mut [T]) -> Option<&'a mut T> {
+        if slice.is_empty() {
+            return None;
+        }
+        let idx = self.read_index(slice.len());
+        Some(&mut slice[idx])
+    }
+
+    /// Pick an element of `items` with probability proportional to its (integer) weight.
+    ///
+    /// Returns `None` if `items` is empty or all weights are zero, without consuming anything from
+    /// the stream. Otherwise, the selection is exact and specified as follows: with `total` the
+    /// sum of all weights, a value `r` is drawn with [`ChaCha8Rand::read_u64_below`]`(total)`,
+    /// and the result is the first item whose cumulative weight (its own weight plus all weights
+    /// before it) is larger than `r`. Items with weight zero are never chosen.
+    ///
+    /// This takes two passes over `items` and calls `weight` twice for each item (except that the
+    /// second pass stops at the chosen item), so `weight` should be cheap and return the same
+    /// value every time. For many draws from the same weights, precompute a
+    /// [`WeightedAlias`][crate::WeightedAlias] table (requires the `alloc` feature) instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sum of all weights overflows `u64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let loot = [("gold", 50), ("potion", 30), ("sword", 15), ("dragon egg", 1)];
+    /// let (drop, _) = rng.choose_weighted(&loot, |&(_, weight)| weight).unwrap();
+    /// # let _ = drop;
+    /// ```
+    pub fn choose_weighted<'a, T>(
+        &mut self,
+        items: &'a [T],
+        weight: impl Fn(&T) -> u64,
+    ) -> Option<&'a T> {
+        let total = items
+            .iter()
+            .try_fold(0u64, |sum, item| sum.checked_add(weight(item)));
+        let total = total.expect("sum of weights overflows u64");
+        if total == 0 {
+            return None;
+        }
+        let mut r = self.read_u64_below(total);
+        for item in items {
+            let w = weight(item);
+            if r < w {
+                return Some(item);
+            }
+            r -= w;
+        }
+        unreachable!("weight function returned different weights in the second pass")
+    }
+
+    /// Sample `k` distinct indices from `0..n`, in ascending order.
+    ///
+    /// Every subset of `k` indices is equally likely. This is
+    /// [`ChaCha8Rand::sample_distinct_u64`] for the range `0..n`, so it uses Floyd's algorithm
+    /// and its running time only depends on `k`, not on `n`. The indices are sorted, which is
+    /// convenient for subsampling data in its original order; [shuffle][ChaCha8Rand::shuffle]
+    /// them if you need a random order.
+    ///
+    /// Without the `alloc` feature, use [`ChaCha8Rand::sample_distinct_u64_into`] instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is larger than `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let dataset: Vec<u32> = (0..1_000_000).collect();
+    /// let subsample: Vec<u32> = rng
+    ///     .sample_indices(dataset.len(), 100)
+    ///     .into_iter()
+    ///     .map(|i| dataset[i])
+    ///     .collect();
+    /// assert_eq!(subsample.len(), 100);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn sample_indices(&mut self, n: usize, k: usize) -> Vec<usize> {
+        self.sample_distinct_u64(0..n as u64, k)
+            .into_iter()
+            .map(|i| i as usize)
+            .collect()
+    }
+
+    /// Pick an index uniformly from `0..n`, as documented for this module.
+    #[inline]
+    pub(crate) fn read_index(&mut self, n: usize) -> usize {
+        match u32::try_from(n) {
+            Ok(n) => self.read_u32_below(n) as usize,
+            Err(_) => self.read_u64_below(n as u64) as usize,
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use core::{fmt, str};
+
+use serde::{
+    de::{self, MapAccess, SeqAccess, Unexpected, Visitor},
+    ser::{self, SerializeStruct},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{ChaCha8Rand, ChaCha8State, Seed, BUF_OUTPUT_LEN};
+
+const FIELDS: &[&str] = &["seed", "bytes_consumed", "iteration"];
+
+/// Serialization with serde. Requires crate feature `serde`.
+///
+/// The state is serialized as a struct with the fields `seed`, `bytes_consumed`, and `iteration`.
+/// In human-readable formats such as JSON, the seed is a string of 64 hex digits, in binary formats
+/// it's 32 raw bytes. Deserialization rejects out-of-range `bytes_consumed` values, just like
+/// [`ChaCha8Rand::try_restore_state`][crate::ChaCha8Rand::try_restore_state] would.
+///
+/// Unlike the `Debug` impl, this includes the seed, so anyone who gets the serialized state can
+/// reproduce the rest of the stream.
+impl Serialize for ChaCha8State {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("ChaCha8State", FIELDS.len())?;
+        state.serialize_field("seed", &SeedRepr(self.seed))?;
+        state.serialize_field("bytes_consumed", &self.bytes_consumed)?;
+        state.serialize_field("iteration", &self.iteration)?;
+        state.end()
+    }
+}
+
+/// Serialization with serde. Requires crate feature `serde`.
+///
+/// See the `Serialize` impl for details about the format.
+impl<'de> Deserialize<'de> for ChaCha8State {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("ChaCha8State", FIELDS, StateVisitor)
+    }
+}
+
+/// Serialization with serde. Requires crate feature `serde`.
+///
+/// The generator is serialized exactly like the [`ChaCha8State`] returned by
+/// [`ChaCha8Rand::clone_state`], so the buffered output isn't stored and the serialized form stays
+/// small. Deserializing re-computes the buffer from the seed, using the best backend for the
+/// current CPU, and results in a generator that continues exactly where the serialized one left
+/// off. Like with snapshots, keep in mind that the serialized form includes the seed.
+///
+/// Snapshots don't record the stream ID of a generator created with [`ChaCha8Rand::with_stream`],
+/// so serializing such a generator fails, unless its stream ID is zero.
+///
+/// # Examples
+///
+/// ```
+/// # use chacha8rand::ChaCha8Rand;
+/// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+/// rng.read_u64();
+/// let json = serde_json::to_string(&rng).unwrap();
+/// let mut loaded: ChaCha8Rand = serde_json::from_str(&json).unwrap();
+/// assert_eq!(loaded.read_u64(), rng.read_u64());
+/// ```
+impl Serialize for ChaCha8Rand {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.stream() != 0 {
+            return Err(ser::Error::custom(
+                "can't serialize a generator with a non-zero stream ID",
+            ));
+        }
+        self.clone_state().serialize(serializer)
+    }
+}
+
+/// Serialization with serde. Requires crate feature `serde`.
+///
+/// See the `Serialize` impl for details.
+impl<'de> Deserialize<'de> for ChaCha8Rand {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let state = ChaCha8State::deserialize(deserializer)?;
+        let mut rng = ChaCha8Rand::new(&state.seed);
+        rng.try_restore_state(&state).map_err(de::Error::custom)?;
+        Ok(rng)
+    }
+}
+
+fn check_bytes_consumed<E: de::Error>(bytes_consumed: u16) -> Result<u16, E> {
+    if usize::from(bytes_consumed) > BUF_OUTPUT_LEN {
+        return Err(E::invalid_value(
+            Unexpected::Unsigned(bytes_consumed.into()),
+            &"at most 992",
+        ));
+    }
+    Ok(bytes_consumed)
+}
+
+struct StateVisitor;
+
+impl<'de> Visitor<'de> for StateVisitor {
+    type Value = ChaCha8State;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("struct ChaCha8State")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let SeedRepr(seed) = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let bytes_consumed = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let bytes_consumed = check_bytes_consumed(bytes_consumed)?;
+        let iteration = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(2, &self))?;
+        Ok(ChaCha8State {
+            seed,
+            bytes_consumed,
+            iteration,
+        })
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut seed = None;
+        let mut bytes_consumed = None;
+        let mut iteration = None;
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Seed if seed.is_some() => return Err(de::Error::duplicate_field("seed")),
+                Field::Seed => seed = Some(map.next_value::<SeedRepr>()?.0),
+                Field::BytesConsumed if bytes_consumed.is_some() => {
+                    return Err(de::Error::duplicate_field("bytes_consumed"));
+                }
+                Field::BytesConsumed => bytes_consumed = Some(map.next_value()?),
+                Field::Iteration if iteration.is_some() => {
+                    return Err(de::Error::duplicate_field("iteration"));
+                }
+                Field::Iteration => iteration = Some(map.next_value()?),
+            }
+        }
+        let seed = seed.ok_or_else(|| de::Error::missing_field("seed"))?;
+        let bytes_consumed =
+            bytes_consumed.ok_or_else(|| de::Error::missing_field("bytes_consumed"))?;
+        let iteration = iteration.ok_or_else(|| de::Error::missing_field("iteration"))?;
+        Ok(ChaCha8State {
+            seed,
+            bytes_consumed: check_bytes_consumed(bytes_consumed)?,
+            iteration,
+        })
+    }
+}
+
+enum Field {
+    Seed,
+    BytesConsumed,
+    Iteration,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct FieldVisitor;
+
+impl<'de> Visitor<'de> for FieldVisitor {
+    type Value = Field;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a ChaCha8State field")
+    }
+
+    fn visit_u64<E: de::Error>(self, index: u64) -> Result<Self::Value, E> {
+        match index {
+            0 => Ok(Field::Seed),
+            1 => Ok(Field::BytesConsumed),
+            2 => Ok(Field::Iteration),
+            _ => Err(E::invalid_value(Unexpected::Unsigned(index), &self)),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, name: &str) -> Result<Self::Value, E> {
+        match name {
+            "seed" => Ok(Field::Seed),
+            "bytes_consumed" => Ok(Field::BytesConsumed),
+            "iteration" => Ok(Field::Iteration),
+            _ => Err(E::unknown_field(name, FIELDS)),
+        }
+    }
+
+    fn visit_bytes<E: de::Error>(self, name: &[u8]) -> Result<Self::Value, E> {
+        match str::from_utf8(name) {
+            Ok(name) => self.visit_str(name),
+            Err(_) => Err(E::invalid_value(Unexpected::Bytes(name), &self)),
+        }
+    }
+}
+
+/// A seed that's serialized as hex string or as raw bytes, depending on the format.
+struct SeedRepr([u8; 32]);
+
+impl Serialize for SeedRepr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let hex = Seed(self.0).to_hex();
+            // `to_hex` only produces ASCII hex digits.
+            serializer.serialize_str(str::from_utf8(&hex).unwrap())
+        } else {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SeedRepr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(SeedVisitor)
+        } else {
+            deserializer.deserialize_bytes(SeedVisitor)
+        }
+    }
+}
+
+struct SeedVisitor;
+
+impl<'de> Visitor<'de> for SeedVisitor {
+    type Value = SeedRepr;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a 32-byte seed as 64 hex digits or as raw bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, hex: &str) -> Result<Self::Value, E> {
+        <&[u8; 64]>::try_from(hex.as_bytes())
+            .ok()
+            .and_then(Seed::from_hex)
+            .map(|Seed(seed)| SeedRepr(seed))
+            .ok_or_else(|| E::invalid_value(Unexpected::Str(hex), &self))
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        <[u8; 32]>::try_from(bytes)
+            .map(SeedRepr)
+            .map_err(|_| E::invalid_length(bytes.len(), &self))
+    }
+
+    // Some binary formats don't distinguish byte strings from sequences of `u8`s.
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut seed = [0; 32];
+        for (i, byte) in seed.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(33, &self));
+        }
+        Ok(SeedRepr(seed))
+    }
+}
@@ -0,0 +1,42 @@
+use sha2::{Digest, Sha256};
+
+use crate::{ChaCha8Rand, Seed};
+
+/// Deriving seeds from strings. Requires crate feature `sha2`.
+impl Seed {
+    /// Derive a seed from a human-chosen string by hashing its UTF-8 bytes with SHA-256.
+    ///
+    /// The seed is exactly the SHA-256 digest of `s`, with no salt, prefix, or normalization. This
+    /// is a stable part of the API: the same string will always give the same seed in every
+    /// version of this crate, and any other tool that can compute SHA-256 (e.g., `printf %s
+    /// bananas | sha256sum`) can reproduce it. Note that strings that *look* the same can still
+    /// differ in their bytes, e.g., because of trailing newlines or Unicode normalization.
+    ///
+    /// This is meant for convenience, not for stretching passwords into keys. A short or guessable
+    /// string gives a short or guessable seed, regardless of how it's hashed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::Seed;
+    /// let seed = Seed::derive_from_str("bananas");
+    /// assert_eq!(
+    ///     format!("{seed:x}"),
+    ///     "e4ba5cbd251c98e6cd1c23f126a3b81d8d8328abc95387229850952b3ef9f904"
+    /// );
+    /// ```
+    pub fn derive_from_str(s: &str) -> Self {
+        Seed(Sha256::digest(s.as_bytes()).into())
+    }
+}
+
+/// Deriving seeds from strings. Requires crate feature `sha2`.
+impl ChaCha8Rand {
+    /// Create a new generator from a human-chosen seed string.
+    ///
+    /// This is a shorthand for `ChaCha8Rand::new(&Seed::derive_from_str(s).0)`. See
+    /// [`Seed::derive_from_str`] for how the string is turned into a seed.
+    pub fn new_from_str(s: &str) -> Self {
+        ChaCha8Rand::new(&Seed::derive_from_str(s).0)
+    }
+}
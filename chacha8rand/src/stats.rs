@@ -0,0 +1,119 @@
+use crate::ChaChaRand;
+
+/// Counters describing how much work a [`ChaCha8Rand`] has done, see [`ChaCha8Rand::stats`].
+///
+/// All counters saturate instead of wrapping around, though you're unlikely to ever see that.
+///
+/// Requires crate feature `stats`.
+///
+/// [`ChaCha8Rand`]: crate::ChaCha8Rand
+/// [`ChaCha8Rand::stats`]: crate::ChaCha8Rand::stats
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Stats {
+    /// How often the buffer was filled with a new iteration of output, over the generator's whole
+    /// lifetime. This includes the initial fill and the fills caused by re-seeding, restoring,
+    /// skipping, and seeking. Iterations that [`ChaCha8Rand::skip_bytes`] passes over without
+    /// filling the buffer don't count, and neither does [`ChaCha8Rand::peek_bytes`].
+    ///
+    /// [`ChaCha8Rand::skip_bytes`]: crate::ChaCha8Rand::skip_bytes
+    /// [`ChaCha8Rand::peek_bytes`]: crate::ChaCha8Rand::peek_bytes
+    pub refills: u64,
+    /// How many bytes of output were consumed over the generator's whole lifetime, by reading
+    /// or by [`ChaCha8Rand::skip_bytes`]. Jumping around in the stream with
+    /// [`ChaCha8Rand::seek_to`] or by restoring a snapshot doesn't count.
+    ///
+    /// [`ChaCha8Rand::skip_bytes`]: crate::ChaCha8Rand::skip_bytes
+    /// [`ChaCha8Rand::seek_to`]: crate::ChaCha8Rand::seek_to
+    pub bytes_produced: u64,
+    /// Like `bytes_produced`, but only counting since the generator was last re-seeded with
+    /// [`ChaCha8Rand::set_seed`] or [`ChaCha8Rand::try_restore_state`].
+    ///
+    /// [`ChaCha8Rand::set_seed`]: crate::ChaCha8Rand::set_seed
+    /// [`ChaCha8Rand::try_restore_state`]: crate::ChaCha8Rand::try_restore_state
+    pub bytes_produced_since_seed: u64,
+}
+
+/// The bookkeeping behind [`Stats`].
+///
+/// Consumed bytes aren't counted as they're consumed, because that would slow down every read.
+/// Instead, they're derived from the stream position, which only ever moves forward except when
+/// the generator jumps to another position. So we remember the position (`anchor`) right after
+/// each jump and add up the distance from the anchor whenever we're about to jump.
+#[derive(Clone)]
+pub(crate) struct Counters {
+    refills: u64,
+    anchor: u128,
+    produced: u64,
+    produced_at_seed: u64,
+}
+
+impl Counters {
+    pub(crate) fn new(refills: u64) -> Self {
+        Counters {
+            refills,
+            anchor: 0,
+            produced: 0,
+            produced_at_seed: 0,
+        }
+    }
+
+    pub(crate) fn count_refill(&mut self) {
+        self.refills = self.refills.saturating_add(1);
+    }
+
+    /// Record that the generator is about to leave `position` without consuming anything more.
+    pub(crate) fn jump_from(&mut self, position: u128) {
+        self.produced = self.produced_at(position);
+    }
+
+    /// Record that the generator arrived at `position` without consuming anything.
+    pub(crate) fn jump_to(&mut self, position: u128) {
+        self.anchor = position;
+    }
+
+    /// Record that the generator was re-seeded at `old_position`, which moves it to position zero.
+    pub(crate) fn reseed(&mut self, old_position: u128) {
+        self.jump_from(old_position);
+        self.produced_at_seed = self.produced;
+        self.jump_to(0);
+    }
+
+    fn produced_at(&self, position: u128) -> u64 {
+        let since_anchor = u64::try_from(position.saturating_sub(self.anchor)).unwrap_or(u64::MAX);
+        self.produced.saturating_add(since_anchor)
+    }
+}
+
+impl<const ROUNDS: usize> ChaChaRand<ROUNDS> {
+    /// Counters for how many refills and how many bytes of output this generator has produced.
+    ///
+    /// Keeping these counters up to date is practically free, because the byte counts are derived
+    /// from the position in the stream (see [`ChaCha8Rand::position`]) and refills are expensive
+    /// anyway. Cloning a generator also clones its counters. The counters don't affect the output
+    /// or comparisons between generators. Still, they add 48 bytes of bookkeeping to every
+    /// generator, so they're only kept with crate feature `stats`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// rng.read_bytes(&mut [0; 1000]);
+    /// rng.set_seed(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// rng.read_u64();
+    /// let stats = rng.stats();
+    /// assert_eq!(stats.refills, 3);
+    /// assert_eq!(stats.bytes_produced, 1008);
+    /// assert_eq!(stats.bytes_produced_since_seed, 8);
+    /// ```
+    ///
+    /// [`ChaCha8Rand::position`]: crate::ChaCha8Rand::position
+    pub fn stats(&self) -> Stats {
+        let produced = self.counters.produced_at(self.position());
+        Stats {
+            refills: self.counters.refills,
+            bytes_produced: produced,
+            bytes_produced_since_seed: produced - self.counters.produced_at_seed,
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use core::{
+    fmt,
+    mem::{self, MaybeUninit},
+};
+
+#[cfg(feature = "alloc")]
+use alloc::{
+    alloc::{handle_alloc_error, Layout},
+    boxed::Box,
+};
+
+use crate::{Backend, ChaCha8Rand};
+
+/// Caller-provided memory for a [`ChaCha8Rand`], see [`ChaCha8Rand::new_in`].
+///
+/// This is an uninitialized slot that's big enough and sufficiently aligned for a generator, plus a
+/// flag that records whether it holds one. It doesn't need any other initialization, so it can be
+/// placed in a `static` (e.g., in a `.bss` or `.uninit` section, or in DMA-capable RAM on
+/// microcontrollers) via the `const` constructor. Re-initializing the same storage with another
+/// seed drops the old generator first, and dropping the storage drops the generator in it, so it's
+/// wiped with crate feature `zeroize` and its audit log is freed with crate feature `audit`. A
+/// storage in a `static` is never dropped, so neither happens for the last generator in it.
+pub struct Storage {
+    slot: MaybeUninit<ChaCha8Rand>,
+    initialized: bool,
+}
+
+impl Storage {
+    /// Create storage for one generator, without initializing it.
+    pub const fn new() -> Self {
+        Storage {
+            slot: MaybeUninit::uninit(),
+            initialized: false,
+        }
+    }
+}
+
+impl Drop for Storage {
+    fn drop(&mut self) {
+        if self.initialized {
+            // SAFETY: `initialized` is only set after `init_in` initialized the slot.
+            unsafe { self.slot.assume_init_drop() };
+        }
+    }
+}
+
+impl Default for Storage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Storage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Storage { .. }")
+    }
+}
+
+impl ChaCha8Rand {
+    /// Create a new generator from the given seed inside caller-provided [`Storage`].
+    ///
+    /// The result is the same as `ChaCha8Rand::new(seed)`, but the generator (including its
+    /// kilobyte of buffered output) is initialized directly in `storage` instead of being
+    /// returned by value. This lets you decide exactly where that memory lives, and the handle you
+    /// pass around is a single pointer. Since it's an ordinary `&mut ChaCha8Rand`, all methods
+    /// are available and produce exactly the same output as an owned generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chacha8rand::{ChaCha8Rand, Storage};
+    ///
+    /// let mut storage = Storage::new();
+    /// let rng = ChaCha8Rand::new_in(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456", &mut storage);
+    /// assert_eq!(rng.read_u64(), 0xb773b6063d4616a5);
+    /// ```
+    pub fn new_in<'a>(seed: &[u8; 32], storage: &'a mut Storage) -> &'a mut ChaCha8Rand {
+        // Clear the flag first, so a panic while dropping doesn't lead to a second drop later.
+        if mem::replace(&mut storage.initialized, false) {
+            // SAFETY: `initialized` is only set after `init_in` initialized the slot.
+            unsafe { storage.slot.assume_init_drop() };
+        }
+        let rng = Self::init_in(&mut storage.slot, seed, Backend::detect_best(), false);
+        storage.initialized = true;
+        rng
+    }
+
+    /// Create a new generator from the given seed directly on the heap.
+    ///
+    /// The result is the same as `Box::new(ChaCha8Rand::new(seed))`, but the generator is
+    /// initialized in place after allocating the memory. The latter may build the whole generator
+    /// (over a kilobyte) on the stack first and then copy it into the box, depending on how well
+    /// the optimizer does its job. That's a problem if you're short on stack space, e.g., in
+    /// threads or async tasks with small stacks. Requires crate feature `alloc`.
+    ///
+    /// A boxed generator is also the way to go if you hold so many generators that their size
+    /// matters, e.g., as fields of thousands of structs. The box itself is a single pointer, and
+    /// since `Box<ChaCha8Rand>` derefs to `ChaCha8Rand`, all methods are available and produce
+    /// exactly the same output. The price is one pointer indirection on every method call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new_boxed(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// assert_eq!(rng.read_u64(), 0xb773b6063d4616a5);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn new_boxed(seed: &[u8; 32]) -> Box<ChaCha8Rand> {
+        let layout = Layout::new::<ChaCha8Rand>();
+        // SAFETY: `ChaCha8Rand` isn't zero-sized.
+        let ptr = unsafe { alloc::alloc::alloc(layout) }.cast::<MaybeUninit<ChaCha8Rand>>();
+        if ptr.is_null() {
+            handle_alloc_error(layout);
+        }
+        // SAFETY: `ptr` is non-null and was just allocated with the layout of `ChaCha8Rand`, so
+        // it's valid for writes and properly aligned, and nobody else has access to it.
+        Self::init_in(unsafe { &mut *ptr }, seed, Backend::detect_best(), false);
+        // SAFETY: the memory was allocated by the global allocator with the layout of
+        // `ChaCha8Rand` and `init_in` initialized it, so the `Box` can take ownership.
+        unsafe { Box::from_raw(ptr.cast::<ChaCha8Rand>()) }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::ChaCha8Rand;
+
+/// Jittered stratified sampling, e.g., for Monte Carlo integration and anti-aliasing.
+///
+/// Instead of drawing independent uniform samples, the unit interval (or square) is divided into
+/// equally sized strata and every stratum gets exactly one sample at a uniformly random position
+/// within it. This keeps the samples from clumping together and usually reduces the variance of
+/// estimates considerably, while each individual sample is still uniformly distributed.
+///
+/// The strata are visited in a fixed order (documented for each method) and every sample
+/// consumes eight bytes per coordinate, so the output is reproducible from the seed.
+impl ChaCha8Rand {
+    /// Fill `out` with one jittered sample from each of `out.len()` equal strata of `[0, 1)`.
+    ///
+    /// `out[i]` is uniformly distributed in `[i / n, (i + 1) / n)` for `n = out.len()`, so the
+    /// result is sorted. Shuffle it if you need the samples in random order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let mut xs = [0.0; 4];
+    /// rng.fill_stratified_f64(&mut xs);
+    /// for (i, x) in xs.into_iter().enumerate() {
+    ///     assert!(i as f64 / 4.0 <= x && x < (i + 1) as f64 / 4.0);
+    /// }
+    /// ```
+    pub fn fill_stratified_f64(&mut self, out: &mut [f64]) {
+        let n = out.len() as f64;
+        for (i, x) in out.iter_mut().enumerate() {
+            *x = jitter(i, self.read_f64(), n);
+        }
+    }
+
+    /// Fill `out` with one jittered sample from each cell of a grid over `[0, 1)²` with `columns`
+    /// cells per row.
+    ///
+    /// The grid has `out.len() / columns` rows. Cells are visited in row-major order: `out[i]` is
+    /// the `[x, y]` point in column `i % columns` and row `i / columns`, and for each point the
+    /// `x` coordinate is sampled first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `columns` is zero or doesn't divide `out.len()`.
+    ///
+    /// # Examples
+    ///
+    /// Sixteen samples per pixel for anti-aliasing, reproducible from the scene seed:
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let mut offsets = [[0.0; 2]; 16];
+    /// rng.fill_stratified_2d(4, &mut offsets);
+    /// assert!(offsets[5][0] >= 0.25 && offsets[5][0] < 0.5); // column 1
+    /// assert!(offsets[5][1] >= 0.25 && offsets[5][1] < 0.5); // row 1
+    /// ```
+    pub fn fill_stratified_2d(&mut self, columns: usize, out: &mut [[f64; 2]]) {
+        assert!(
+            columns != 0 && out.len() % columns == 0,
+            "number of samples must be a multiple of the number of columns"
+        );
+        let rows = (out.len() / columns) as f64;
+        for (i, point) in out.iter_mut().enumerate() {
+            let x = jitter(i % columns, self.read_f64(), columns as f64);
+            let y = jitter(i / columns, self.read_f64(), rows);
+            *point = [x, y];
+        }
+    }
+}
+
+/// Map `u` in `[0, 1)` into stratum `i` out of `n`.
+pub(crate) fn jitter(i: usize, u: f64, n: f64) -> f64 {
+    let x = (i as f64 + u) / n;
+    // Rounding can push the largest values of any stratum up to exactly `(i + 1) / n`, so clamp
+    // them to the largest float below that. It's positive, so the next float down is one bit less.
+    let end = (i + 1) as f64 / n;
+    x.min(f64::from_bits(end.to_bits() - 1))
+}
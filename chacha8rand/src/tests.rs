@@ -1,9 +1,13 @@
 extern crate std;
-use core::iter;
+use core::{cmp, iter, mem::MaybeUninit, ops::Bound};
 use std::prelude::rust_2021::*;
 use std::vec;
 
-use crate::{Backend, ChaCha8Rand, ChaCha8State};
+use crate::{
+    seed_from_bytes, Backend, Buffer, ChaCha12Rand, ChaCha20Rand, ChaCha8Hasher, ChaCha8Rand,
+    ChaCha8RandomState, ChaCha8State, ErasedRng, LazyPermutation, Seed, SeedCache, Storage,
+    StreamPosition, BUF_OUTPUT_LEN,
+};
 
 macro_rules! test_backends {
     (
@@ -24,25 +28,35 @@ macro_rules! test_backends {
                 fn sample_output_u64s() {
                     super::sample_output_u64s($ctor);
                 }
+
+                #[test]
+                fn sample_output_u128s() {
+                    super::sample_output_u128s($ctor);
+                }
+
             }
         )+
     };
 }
 
 test_backends! {
-    scalar => crate::scalar::backend();
+    scalar => crate::Backend::scalar();
     #[cfg(any(
         target_arch = "x86_64",
         // because we have no runtime detection for sse2
         all(target_arch = "x86", target_feature = "sse2"),
     ))]
-    sse2 => crate::sse2::detect().expect("this test requires sse2");
+    sse2 => crate::Backend::x86_sse2().expect("this test requires sse2");
     #[cfg(all(any(target_arch = "x86_64", target_arch = "x86"), feature = "std"))]
-    avx2 => crate::avx2::detect().expect("this test requires avx2");
+    ssse3 => crate::Backend::x86_ssse3().expect("this test requires ssse3");
+    #[cfg(all(any(target_arch = "x86_64", target_arch = "x86"), feature = "std"))]
+    avx2 => crate::Backend::x86_avx2().expect("this test requires avx2");
     #[cfg(target_arch = "aarch64")]
-    neon => crate::neon::detect().expect("this test requires neon");
+    neon => crate::Backend::aarch64_neon().expect("this test requires neon");
     #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
-    simd128 => crate::simd128::detect().expect("this test requires simd128");
+    simd128 => crate::Backend::wasm32_simd128().expect("this test requires simd128");
+    #[cfg(feature = "unstable_portable_simd")]
+    portable_simd => crate::Backend::portable_simd().expect("this test requires portable_simd");
 }
 
 #[test]
@@ -97,6 +111,7 @@ fn restore_rejects_slighty_too_large_count() {
     let bogus_state = ChaCha8State {
         seed: [0xCC; 32],
         bytes_consumed: 993,
+        iteration: 0,
     };
     assert!(rng.try_restore_state(&bogus_state).is_err());
     // Also, the error should be detected before the RNG state is altered:
@@ -109,6 +124,7 @@ fn restore_rejects_excessive_count() {
     let bogus_state = ChaCha8State {
         seed: [0xCC; 32],
         bytes_consumed: u16::MAX,
+        iteration: 0,
     };
     assert!(rng.try_restore_state(&bogus_state).is_err());
     // Also, the error should be detected before the RNG state is altered:
@@ -147,6 +163,21 @@ fn set_seed_after_some_output(advance_bytes: usize) {
     check_byte_output(iter::repeat_with(|| rng.read_u32()).flat_map(u32::to_le_bytes));
 }
 
+#[test]
+fn initial_seed_survives_refills() {
+    let tweaked_seed = SAMPLE_SEED.map(|byte| byte ^ 3);
+    let mut rng = ChaCha8Rand::new(&tweaked_seed);
+    rng.read_bytes(&mut [0; 2000]);
+    assert_eq!(rng.initial_seed(), tweaked_seed);
+    rng.set_seed(SAMPLE_SEED);
+    rng.read_bytes(&mut [0; 2000]);
+    assert_eq!(rng.initial_seed(), *SAMPLE_SEED);
+
+    let state = rng.clone_state();
+    rng.try_restore_state(&state).unwrap();
+    assert_eq!(rng.initial_seed(), state.seed);
+}
+
 fn sample_output_u32s(backend: Backend) {
     let mut rng = ChaCha8Rand::with_backend_impl(SAMPLE_SEED, backend);
     let u32s = iter::repeat_with(move || rng.read_u32());
@@ -159,6 +190,41 @@ fn sample_output_u64s(backend: Backend) {
     check_byte_output(u64s.flat_map(u64::to_le_bytes));
 }
 
+fn sample_output_u128s(backend: Backend) {
+    let mut rng = ChaCha8Rand::with_backend_impl(SAMPLE_SEED, backend);
+    let u128s = iter::repeat_with(move || rng.read_u128());
+    check_byte_output(u128s.flat_map(u128::to_le_bytes));
+}
+
+#[test]
+fn read_u128_straddles_buffer_end() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    // 992 is a multiple of 16, so shift everything by four bytes to hit the slow path.
+    let first = rng.read_u32().to_le_bytes();
+    let rest = iter::repeat_with(|| rng.read_u128()).flat_map(u128::to_le_bytes);
+    check_byte_output(first.into_iter().chain(rest));
+}
+
+#[test]
+fn expand_seeds_matches_new() {
+    fn check<const N: usize>() {
+        let seeds: Vec<[u8; 32]> = (0..5u8).map(|i| SAMPLE_SEED.map(|b| b ^ i)).collect();
+        let mut out = vec![[0; N]; seeds.len()];
+        ChaCha8Rand::expand_seeds(&seeds, &mut out);
+        for (seed, out) in iter::zip(&seeds, &out) {
+            let mut expected = [0; N];
+            ChaCha8Rand::new(seed).read_bytes(&mut expected);
+            assert_eq!(*out, expected);
+        }
+    }
+    check::<0>();
+    check::<32>();
+    check::<256>();
+    check::<300>();
+    check::<992>();
+    check::<1000>();
+}
+
 #[test]
 fn read_single_byte_at_a_time() {
     read_n_bytes_at_a_time::<1>();
@@ -231,147 +297,2891 @@ fn read_u32s_and_bytes_interleaved(read_size: usize) {
     check_byte_output(output.iter().copied());
 }
 
-#[cfg(feature = "rand_core_0_6")]
-mod rand06 {
-    use core::iter;
+#[test]
+fn fill_buf_and_consume() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut output: Vec<u8> = Vec::new();
+    let mut take = 0;
+    while output.len() < size_of_val::<[u64]>(SAMPLE_OUTPUT_U64LE) {
+        let buf = rng.fill_buf();
+        assert!(!buf.is_empty());
+        // Take 0, 1, ..., 12 bytes or whatever is left in the buffer.
+        let n = cmp::min(take % 13, buf.len());
+        output.extend_from_slice(&buf[..n]);
+        rng.consume(n);
+        take += 1;
+    }
+    check_byte_output(output.iter().copied());
+}
 
-    use rand_core::{RngCore, SeedableRng};
+#[test]
+fn consume_after_buffer_is_used_up() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut output = [0; 3000];
+    rng.read_bytes(&mut output[..992]);
+    // The next `fill_buf` would refill the buffer, so these are the first bytes of the refill.
+    rng.consume(8);
+    output[992..1000].copy_from_slice(&SAMPLE_OUTPUT_U64LE[124].to_le_bytes());
+    rng.read_bytes(&mut output[1000..]);
+    check_byte_output(output.iter().copied());
+}
 
-    use crate::ChaCha8Rand;
+#[test]
+#[should_panic]
+fn consume_more_than_buffered() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    rng.read_u32();
+    rng.consume(989);
+}
 
-    use super::{check_byte_output, SAMPLE_OUTPUT_U64LE, SAMPLE_SEED};
+#[test]
+fn peek_bytes_does_not_consume() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut output = [0; 3000];
+    // Peek within the buffer, across one boundary, and across two boundaries.
+    for (start, end) in [(0, 10), (10, 1500), (1500, 3000)] {
+        let mut peeked = [0; 3000];
+        rng.peek_bytes(&mut peeked[..end - start]);
+        rng.read_bytes(&mut output[start..end]);
+        assert_eq!(peeked[..end - start], output[start..end]);
+    }
+    check_byte_output(output.iter().copied());
+}
 
-    #[test]
-    fn next_u32() {
-        let mut rng = ChaCha8Rand::from_seed(*SAMPLE_SEED);
-        let u32s = iter::repeat_with(|| RngCore::next_u32(&mut rng));
-        check_byte_output(u32s.flat_map(u32::to_le_bytes));
+#[test]
+fn peek_bytes_after_buffer_is_used_up() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    rng.read_bytes(&mut [0; 992]);
+    let mut peeked = [0; 8];
+    rng.peek_bytes(&mut peeked);
+    assert_eq!(peeked, SAMPLE_OUTPUT_U64LE[124].to_le_bytes());
+    assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[124]);
+}
+
+#[test]
+fn skip_bytes_like_read_bytes() {
+    for skip in [0, 1, 987, 988, 989, 1980, 2000] {
+        let mut skipped = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut read = ChaCha8Rand::new(SAMPLE_SEED);
+        assert_eq!(skipped.read_u32(), read.read_u32());
+        skipped.skip_bytes(skip);
+        read.read_bytes(&mut vec![0; skip as usize]);
+        for _ in 0..300 {
+            assert_eq!(skipped.read_u32(), read.read_u32(), "skipped {skip} bytes");
+        }
     }
+}
 
-    #[test]
-    fn next_u64() {
-        let mut rng = ChaCha8Rand::from_seed(*SAMPLE_SEED);
-        let u64s = iter::repeat_with(|| RngCore::next_u64(&mut rng));
-        check_byte_output(u64s.flat_map(u64::to_le_bytes));
+#[test]
+fn position_counts_all_output() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    assert_eq!(rng.position(), 0);
+    rng.read_bytes(&mut [0; 992]);
+    assert_eq!(rng.position(), 992);
+    rng.read_u64();
+    assert_eq!(rng.position(), 1000);
+    rng.skip_bytes(5000);
+    assert_eq!(rng.position(), 6000);
+    rng.consume(10);
+    rng.read_u128();
+    rng.read_u32();
+    assert_eq!(rng.position(), 6030);
+
+    let state = rng.clone_state();
+    assert_eq!(state.position(), 6030);
+    rng.set_seed(SAMPLE_SEED);
+    assert_eq!(rng.position(), 0);
+    rng.try_restore_state(&state).unwrap();
+    assert_eq!(rng.position(), 6030);
+}
+
+#[test]
+fn seek_after_restore() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    rng.seek_to(8 * 130).unwrap();
+    let state = rng.clone_state();
+    assert_eq!((state.iteration, state.bytes_consumed), (1, 48));
+
+    let mut restored = ChaCha8Rand::new(&[0; 32]);
+    restored.try_restore_state(&state).unwrap();
+    restored.seek_to(8 * 300).unwrap();
+    assert_eq!(restored.read_u64(), SAMPLE_OUTPUT_U64LE[300]);
+    // Going back to the start of the restored iteration works, but not any further.
+    restored.seek_to(992).unwrap();
+    assert_eq!(restored.read_u64(), SAMPLE_OUTPUT_U64LE[124]);
+    assert!(restored.seek_to(991).is_err());
+    assert_eq!(restored.position(), 1000);
+}
+
+#[test]
+fn seek_to_matches_sample_output() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    // Forward across iterations, backward within one, backward across several, and to the end of
+    // an iteration.
+    for index in [100, 5, 300, 2, 371, 123, 124, 0] {
+        rng.seek_to(8 * index as u128).unwrap();
+        assert_eq!(rng.position(), 8 * index as u128);
+        assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[index]);
     }
+    rng.seek_to(992).unwrap();
+    assert_eq!(rng.position(), 992);
+    assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[124]);
+}
 
-    #[test]
-    fn fill_bytes() {
-        let mut rng = ChaCha8Rand::from_seed(*SAMPLE_SEED);
-        let mut bytes = [0; SAMPLE_OUTPUT_U64LE.len() * 8];
-        rng.fill_bytes(&mut bytes);
-        check_byte_output(bytes.iter().copied());
+#[test]
+fn seek_to_rejects_unreachable_positions() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    rng.read_u32();
+    let too_far = StreamPosition::MAX.to_byte_offset() + 1;
+    assert!(rng.seek_to(too_far).is_err());
+    assert_eq!(rng.position(), 4);
+}
+
+#[test]
+fn seek_to_fills_lazy_generator_first() {
+    // Before the first refill, a lazy generator's iteration field is `u64::MAX`, so seeking into
+    // the last iteration used to be mistaken for a seek within the (unfilled) buffer. Actually
+    // getting there takes 2^64 refills, so check that the marker is gone after any seek.
+    for (position, index) in [(0, 0), (8 * 50, 50), (992, 124)] {
+        let mut rng = ChaCha8Rand::new_lazy(SAMPLE_SEED);
+        assert!(rng.is_unfilled());
+        rng.seek_to(position).unwrap();
+        assert!(!rng.is_unfilled());
+        assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[index]);
     }
+    // A seek that fails doesn't change anything, including the lazy state.
+    let mut rng = ChaCha8Rand::new_lazy(SAMPLE_SEED);
+    assert!(rng
+        .seek_to(StreamPosition::MAX.to_byte_offset() + 1)
+        .is_err());
+    assert!(rng.is_unfilled());
 }
 
-fn expected_bytes() -> impl Iterator<Item = u8> {
-    SAMPLE_OUTPUT_U64LE.iter().flat_map(|n| n.to_le_bytes())
+#[test]
+fn const_bytes_matches_sample_output() {
+    const OUTPUT: [u8; 2976] = ChaCha8Rand::const_bytes(SAMPLE_SEED);
+    check_byte_output(OUTPUT);
+    assert_eq!(ChaCha8Rand::const_bytes::<0>(SAMPLE_SEED), [0u8; 0]);
+    let short: [u8; 8] = ChaCha8Rand::const_bytes(SAMPLE_SEED);
+    assert_eq!(u64::from_le_bytes(short), SAMPLE_OUTPUT_U64LE[0]);
 }
 
-fn check_byte_output(output: impl IntoIterator<Item = u8>) {
-    let mut i = 0;
-    for (output_byte, expected_byte) in iter::zip(output, expected_bytes()) {
-        assert_eq!(output_byte, expected_byte, "byte {i} differs");
-        i += 1;
+#[test]
+fn lazy_builder_matches_new() {
+    let lazy = || ChaCha8Rand::builder(SAMPLE_SEED).lazy(true).build();
+    let eager = ChaCha8Rand::new(SAMPLE_SEED);
+    assert_eq!(lazy().position(), 0);
+    assert_eq!(lazy().clone_state(), eager.clone_state());
+    assert!(lazy() == eager);
+    assert!(ChaCha8Rand::new_lazy(SAMPLE_SEED) == eager);
+
+    let mut rng = lazy();
+    check_byte_output(iter::repeat_with(|| rng.read_u32()).flat_map(u32::to_le_bytes));
+
+    let mut peeked = [0; 8];
+    lazy().peek_bytes(&mut peeked);
+    assert_eq!(u64::from_le_bytes(peeked), SAMPLE_OUTPUT_U64LE[0]);
+
+    for index in [0, 3, 124, 200] {
+        let mut rng = lazy();
+        rng.skip_bytes(8 * index as u64);
+        assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[index]);
+        let mut rng = lazy();
+        rng.seek_to(8 * index as u128).unwrap();
+        assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[index]);
     }
-    assert_eq!(i, SAMPLE_OUTPUT_U64LE.len() * 8, "output ended prematurely");
 }
 
-pub const SAMPLE_SEED: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+#[test]
+fn builder_position_matches_seek_to() {
+    for index in [0, 1, 123, 124, 125, 371] {
+        let position = StreamPosition::from_byte_offset(8 * index as u128).unwrap();
+        for lazy in [false, true] {
+            let mut rng = ChaCha8Rand::builder(SAMPLE_SEED)
+                .backend(Backend::scalar())
+                .position(position)
+                .lazy(lazy)
+                .build();
+            let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+            expected.seek_to(position.to_byte_offset()).unwrap();
+            assert_eq!(rng.position(), position.to_byte_offset());
+            assert_eq!(rng.clone_state(), expected.clone_state());
+            assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[index]);
+            rng.seek_to(0).unwrap();
+            assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[0]);
+        }
+    }
+}
 
-#[rustfmt::skip]
-const SAMPLE_OUTPUT_U64LE: &[u64] = &[
-    0xb773b6063d4616a5, 0x1160af22a66abc3c, 0x8c2599d9418d287c, 0x7ee07e037edc5cd6,
-    0xcfaa9ee02d1c16ad, 0x0e090eef8febea79, 0x3c82d271128b5b3e, 0x9c5addc11252a34f,
-    0xdf79bb617d6ceea6, 0x36d553591f9d736a, 0xeef0d14e181ee01f, 0x089bfc760ae58436,
-    0xd9e52b59cc2ad268, 0xeb2fb4444b1b8aba, 0x4f95c8a692c46661, 0xc3c6323217cae62c,
-    0x91ebb4367f4e2e7e, 0x784cf2c6a0ec9bc6, 0x5c34ec5c34eabe20, 0x4f0a8f515570daa8,
-    0xfc35dcb4113d6bf2, 0x5b0da44c645554bc, 0x6d963da3db21d9e1, 0xeeaefc3150e500f3,
-    0x2d37923dda3750a5, 0x380d7a626d4bc8b0, 0xeeaf68ede3d7ee49, 0xf4356695883b717c,
-    0x846a9021392495a4, 0x8e8510549630a61b, 0x18dc02545dbae493, 0x0f8f9ff0a65a3d43,
-    0xccf065f7190ff080, 0xfd76d1aa39673330, 0x95d232936cba6433, 0x6c7456d1070cbd17,
-    0x462acfdaff8c6562, 0x5bafab866d34fc6a, 0x0c862f78030a2988, 0xd39a83e407c3163d,
-    0xc00a2b7b45f22ebf, 0x564307c62466b1a9, 0x257e0424b0c072d4, 0x6fb55e99496c28fe,
-    0xae9873a88f5cd4e0, 0x4657362ac60d3773, 0x1c83f91ecdf23e8e, 0x6fdc0792c15387c0,
-    0x36dad2a30dfd2b5c, 0xa4b593290595bdb7, 0x4de18934e4cc02c5, 0xcdc0d604f015e3a7,
-    0xfba0dbf69ad80321, 0x60e8bea3d139de87, 0xd18a4d851ef48756, 0x6366447c2215f34a,
-    0x05682e97d3d007ee, 0x4c0e8978c6d54ab2, 0xcf1e9f6a6712edc2, 0x061439414c80cfd3,
-    0xd1a8b6e2745c0ead, 0x31a7918d45c410e8, 0xabcc61ad90216eec, 0x4040d92d2032a71a,
-    0x3cd2f66ffb40cd68, 0xdcd051c07295857a, 0xeab55cbcd9ab527e, 0x18471dce781bdaac,
-    0xf7f08cd144dc7252, 0x5804e0b13d7f40d1, 0x5cb1a446e4b2d35b, 0xe6d4a728d2138a06,
-    0x05223e40ca60dad8, 0x2d61ec3206ac6a68, 0xab692356874c17b8, 0xc30954417676de1c,
-    0x4f1ace3732225624, 0xfba9510813988338, 0x997f200f52752e11, 0x1116aaafe86221fa,
-    0x07ce3b5cb2a13519, 0x2956bc72bc458314, 0x4188b7926140eb78, 0x56ca6dbfd4adea4d,
-    0x7fe3c22349340ce5, 0x35c08f9c37675f8a, 0x11e1c7fbef5ed521, 0x98adc8464ec1bc75,
-    0xd163b2c73d1203f8, 0x8c761ee043a2f3f3, 0x24b99d6accecd7b7, 0x793e31aa112f0370,
-    0x8e87dc2a19285139, 0x4247ae04f7096e25, 0x514f3122926fe20f, 0xdc6fb3f045d2a7e9,
-    0x15cb30cecdd18eba, 0xcbc7fdecf6900274, 0x3fb5c696dc8ba021, 0xd1664417c8d274e6,
-    0x05f7e445ea457278, 0xf920bbca1b9db657, 0x0c1950b4da22cb99, 0xf875baf1af09e292,
-    0xbed3d7b84250f838, 0xf198e8080fd74160, 0xc9eda51d9b7ea703, 0xf709ef55439bf8f6,
-    0xd20c74feebf116fc, 0x305668eb146d7546, 0x829af3ec10d89787, 0x15b8f9697b551dbc,
-    0xfc823c6c8e64b8c9, 0x345585e8183b40bc, 0x674b4171d6581368, 0x1234d81cd670e9f7,
-    0x0e505210d8a55e19, 0xe8258d69eeeca0dc, 0x05d4c452e8baf67e, 0xe8dbe30116a45599,
-    0x1cf08ce1b1176f00, 0xccf7d0a4b81ecb49, 0x303fea136b2c430e, 0x861d6c139c06c871,
-    0x5f41df72e05e0487, 0x25bd7e1e1ae26b1d, 0xbe9f4004d662a41d, 0x65bf58d483188546,
-    0xd1b27cff69db13cc, 0x01a6663372c1bb36, 0x578dd7577b727f4d, 0x19c78f066c083cf6,
-    0xdbe014d4f9c391bb, 0x97fbb2dd1d13ffb3, 0x31c91e0af9ef8d4f, 0x094dfc98402a43ba,
-    0x069bd61bea37b752, 0x5b72d762e8d986ca, 0x72ee31865904bc85, 0xd1f5fdc5cd36c33e,
-    0xba9b4980a8947cad, 0xece8f05eac49ab43, 0x65fe1184abae38e7, 0x2d7cb9dea5d31452,
-    0xcc71489476e467e3, 0x4c03a258a578c68c, 0x00efdf9ecb0fd8fc, 0x9924cad471e2666d,
-    0x87f8668318f765e9, 0xcb4dc57c1b55f5d8, 0xd373835a86604859, 0xe526568b5540e482,
-    0x1f39040f08586fec, 0xb764f3f00293f8e6, 0x049443a2f6bd50a8, 0x76fec88697d3941a,
-    0x3efb70d039bae7a2, 0xe2f4611368eca8a8, 0x7c007a96e01d2425, 0xbbcce5768e69c5bf,
-    0x784fb4985c42aac3, 0xf72b5091aa223874, 0x3630333fb1e62e07, 0x8e7319ebdebbb8de,
-    0x2a3982bca959fa00, 0xb2b98b9f964ba9b3, 0xf7e31014adb71951, 0xebd0fca3703acc82,
-    0xec654e2a2fe6419a, 0xb326132d55a52e2c, 0x2248c57f44502978, 0x32710c2f342daf16,
-    0x0517b47b5acb2bec, 0x4c7a718fca270937, 0xd69142bed0bcc541, 0xe40ebcb8ff52ce88,
-    0x3e44a2dbc9f828d4, 0xc74c2f4f8f873f58, 0x3dbf648eb799e45b, 0x33f22475ee0e86f8,
-    0x1eb4f9ee16d47f65, 0x40f8d2b8712744e3, 0xb886b4da3cb14572, 0x2086326fbdd6f64d,
-    0xcc3de5907dd882b9, 0xa2e8b49a5ee909df, 0xdbfb8e7823964c10, 0x70dd6089ef0df8d5,
-    0x30141663cdd9c99f, 0x04b805325c240365, 0x7483d80314ac12d6, 0x2b271cb91aa7f5f9,
-    0x97e2245362abddf0, 0x5a84f614232a9fab, 0xf71125fcda4b7fa2, 0x1ca5a61d74b27267,
-    0x38cc6a9b3adbcb45, 0xdde1bb85dc653e39, 0xe9d0c8fa64f89fd4, 0x02c5fb1ecd2b4188,
-    0xf2bd137bca5756e5, 0xadefe25d121be155, 0x56cd1c3c5d893a8e, 0x4c50d337beb65bb9,
-    0x918c5151675cf567, 0xaba649ffcfb56a1e, 0x20c74ab26a2247cd, 0x71166bac853c08da,
-    0xb07befe2e584fc5d, 0xda45ff2a588dbf32, 0xdb98b03c4d75095e, 0x60285ae1aaa65a4c,
-    0xf93b686a263140b8, 0xde469752ee1c180e, 0xcec232dc04129aae, 0xeb916baa1835ea04,
-    0xd49c21c8b64388ff, 0x72a82d9658864888, 0x003348ef7eac66a8, 0x7f6f67e655b209eb,
-    0x532ffb0b7a941b25, 0xd940ade6128deede, 0xdf24f2a1af89fe23, 0x95aa3b4988195ae0,
-    0x3da649404f94be4a, 0x692dad132c3f7e27, 0x40aee76ecaaa9eb8, 0x1294a01e09655024,
-    0x6df797abdba4e4f5, 0xea2fb6024c1d7032, 0x5f4e0492295489fc, 0x57972914ea22e06a,
-    0x9a8137d133aad473, 0xa2e6dd6ae7cdf2f3, 0x9f42644f18086647, 0x16d03301c170bd3e,
-    0x908c416fa546656d, 0xe081503be22e123e, 0x077cf09116c4cc72, 0xcbd25cd264b7f229,
-    0x3db2f468ec594031, 0x46c00e734c9badd5, 0xd0ec0ac72075d861, 0x3037cb3cf80b7630,
-    0x574c3d7b3a2721c6, 0xae99906a0076824b, 0xb175a5418b532e70, 0xd8b3e251ee231ddd,
-    0xb433eec25dca1966, 0x530f30dc5cff9a93, 0x9ff03d98b53cd335, 0xafc4225076558cdf,
-    0xef81d3a28284402a, 0x110bdbf51c110a28, 0x9ae1b255d027e8f6, 0x7de3e0aa24688332,
-    0xe483c3ecd2067ee2, 0xf829328b276137e6, 0xa413ccad57562cad, 0xe6118e8b496acb1f,
-    0x8288dca6da5ec01f, 0xa53777dc88c17255, 0x8a00f1e0d5716eda, 0x618e6f47b7a720a8,
-    0x9e3907b0c692a841, 0x978b42ca963f34f3, 0x75e4b0cd98a7d7ef, 0xde4dbd6e0b5f4752,
-    0x0252e4153f34493f, 0x50f0e7d803734ef9, 0x237766a38ed167ee, 0x4124414001ee39a0,
-    0xd08df643e535bb21, 0x34f575b5a9a80b74, 0x2c343af87297f755, 0xcd8b6d99d821f7cb,
-    0xe376fd7256fc48ae, 0xe1b06e7334352885, 0xfa87b26f86c169eb, 0x36c1604665a971de,
-    0xdba147c2239c8e80, 0x6b208e69fc7f0e24, 0x8795395b6f2b60c3, 0x05dabee9194907f4,
-    0xb98175142f5ed902, 0x5e1701e2021ddc81, 0x0875aba2755eed08, 0x778d83289251de95,
-    0x3bfbe46a039ecb31, 0xb24704fce4cbd7f9, 0x6985ffe9a7c91e3d, 0xc8efb13df249dabb,
-    0xb1037e64b0f4c9f6, 0x55f69fd197d6b7c3, 0x672589d71d68a90c, 0xbebdb8224f50a77e,
-    0x3f589f80007374a7, 0xd307f4635954182a, 0xcff5850c10d4fd90, 0xc6da02dfb6408e15,
-    0x93daeef1e2b1a485, 0x65d833208aeea625, 0xe2b13fa13ed3b5fa, 0x67053538130fb68e,
-    0xc1042f6598218fa9, 0xee5badca749b8a2e, 0x6d22a3f947dae37d, 0xb62c6d1657f4dbaf,
-    0x6e007de69704c20b, 0x1af2b913fc3841d8, 0xdc0e47348e2e8e22, 0x9b1ddef1cf958b22,
-    0x632ed6b0233066b8, 0xddd02d3311bed8f2, 0xf147cfe1834656e9, 0x399aaa49d511597a,
-    0x6b14886979ec0309, 0x64fc4ac36b5afb97, 0xb82f78e07f7cf081, 0x10925c9a323d0e1b,
-    0xf451c79ee13c63f6, 0x7c2fc180317876c7, 0x35a12bd9eecb7d22, 0x335654a539621f90,
-    0xcc32a3f35db581f0, 0xc60748a80b2369cb, 0x7c4dd3b08591156b, 0xac1ced4b6de22291,
-    0xa32cfa2df134def5, 0x627108918dea2a53, 0x0555b1608fcb4ff4, 0x143ee7ac43aaa33c,
-    0xdae90ce7cf4fc218, 0x4d68fc2582bcf4b5, 0x37094e1849135d71, 0xf7857e09f3d49fd8,
-    0x007538c503768be7, 0xedf648ba2f6be601, 0xaa347664dd72513e, 0xbe63893c6ef23b86,
-    0x130b85710605af97, 0xdd765c6b1ef6ab56, 0xf3249a629a97dc6b, 0x2a114f9020fab8e5,
-    0x5a69e027cfc6ad08, 0x3c4ccb36f1a5e050, 0x2e9e7d596834f0a5, 0x2430be6858fce789,
-    0xe90b862f2466e597, 0x895e2884f159a9ec, 0x26ab8fa4902fcb57, 0xa6efff5c54e1fa50,
-    0x333ac4e5811a8255, 0xa58d515f02498611, 0xfe5a09dcb25c6ef4, 0x03898988ab5f5818,
-    0x289ff6242af6c617, 0x3d9dd59fd381ea23, 0x52d7d93d8a8aae51, 0xc76a123d511f786f,
-    0xf68901edaf00c46c, 0x8c630871b590de80, 0x05209c308991e091, 0x1f809f99b4788177,
-    0x11170c2eb6c19fd8, 0x44433c779062ba58, 0xc0acb51af1874c45, 0x9f2e134284809fa1,
-    0xedb523bd15c619fa, 0x02d97fd53ecc23c0, 0xacaf05a34462374c, 0xddd9c6d34bffa11f
-];
+#[test]
+fn streams_are_separate_and_sticky() {
+    let backend = Backend::scalar().with_stream(1);
+    let mut buf = Buffer::new();
+    backend.refill(&seed_from_bytes(SAMPLE_SEED), &mut buf);
+    let mut next = Buffer::new();
+    backend.refill(&seed_from_bytes(buf.new_key()), &mut next);
+    let mut expected = [0; 2 * BUF_OUTPUT_LEN];
+    expected[..BUF_OUTPUT_LEN].copy_from_slice(buf.output());
+    expected[BUF_OUTPUT_LEN..].copy_from_slice(next.output());
+
+    let mut rng = ChaCha8Rand::with_stream(SAMPLE_SEED, 1);
+    let mut output = [0; 2 * BUF_OUTPUT_LEN];
+    rng.read_bytes(&mut output);
+    assert_eq!(output, expected);
+    rng.seek_to(1000).unwrap();
+    assert_eq!(rng.read_u64().to_le_bytes(), expected[1000..1008]);
+
+    assert_eq!(
+        ChaCha8Rand::with_stream(SAMPLE_SEED, 0),
+        ChaCha8Rand::new(SAMPLE_SEED)
+    );
+    assert_ne!(
+        ChaCha8Rand::with_stream(SAMPLE_SEED, 1),
+        ChaCha8Rand::new(SAMPLE_SEED)
+    );
+    let built = ChaCha8Rand::builder(SAMPLE_SEED).stream(1).build();
+    assert_eq!(built, ChaCha8Rand::with_stream(SAMPLE_SEED, 1));
+
+    rng.set_backend(Backend::detect_best());
+    rng.set_seed(SAMPLE_SEED);
+    assert_eq!(rng.read_u64().to_le_bytes(), expected[..8]);
+    SeedCache::new().set_seed(&mut rng, SAMPLE_SEED);
+    assert_eq!(rng.stream(), 1);
+    assert_eq!(rng.read_u64().to_le_bytes(), expected[..8]);
+}
+
+#[test]
+fn other_round_counts_follow_the_same_stream_structure() {
+    fn check<const ROUNDS: usize>() {
+        let backend = Backend::scalar().with_stream(7);
+        let mut buf = Buffer::new();
+        backend.refill_with_rounds::<ROUNDS>(&seed_from_bytes(SAMPLE_SEED), &mut buf);
+        let mut next = Buffer::new();
+        backend.refill_with_rounds::<ROUNDS>(&seed_from_bytes(buf.new_key()), &mut next);
+        let mut expected = [0; 2 * BUF_OUTPUT_LEN];
+        expected[..BUF_OUTPUT_LEN].copy_from_slice(buf.output());
+        expected[BUF_OUTPUT_LEN..].copy_from_slice(next.output());
+
+        let mut rng = crate::ChaChaRand::<ROUNDS>::with_stream(SAMPLE_SEED, 7);
+        let mut output = [0; 2 * BUF_OUTPUT_LEN];
+        rng.read_bytes(&mut output);
+        assert_eq!(output, expected);
+        rng.seek_to(1000).unwrap();
+        assert_eq!(rng.read_u64().to_le_bytes(), expected[1000..1008]);
+        let state = rng.clone_state();
+        rng.skip_bytes(5000);
+        let after_skip = rng.read_u32();
+        rng.try_restore_state(&state).unwrap();
+        rng.read_bytes(&mut [0; 5000]);
+        assert_eq!(rng.read_u32(), after_skip);
+    }
+    check::<8>();
+    check::<12>();
+    check::<20>();
+
+    let mut rng8 = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut rng12 = ChaCha12Rand::new(SAMPLE_SEED);
+    let mut rng20 = ChaCha20Rand::new(SAMPLE_SEED);
+    let outputs = [rng8.read_u64(), rng12.read_u64(), rng20.read_u64()];
+    assert_ne!(outputs[0], outputs[1]);
+    assert_ne!(outputs[0], outputs[2]);
+    assert_ne!(outputs[1], outputs[2]);
+    assert_eq!(format!("{rng20:?}"), "ChaCha20Rand { .. }");
+}
+
+#[cfg(feature = "stats")]
+#[test]
+fn stats_count_consumption_but_not_jumps() {
+    use crate::Stats;
+
+    let mut rng = ChaCha8Rand::new_lazy(SAMPLE_SEED);
+    assert_eq!(rng.stats(), Stats::default());
+    rng.read_bytes(&mut [0; 1000]);
+    rng.skip_bytes(3000);
+    let stats = rng.stats();
+    assert_eq!((stats.refills, stats.bytes_produced), (3, 4000));
+
+    rng.seek_to(8).unwrap();
+    rng.read_u64();
+    rng.seek_to(5000).unwrap();
+    let stats = rng.stats();
+    assert_eq!((stats.refills, stats.bytes_produced), (5, 4008));
+
+    let state = ChaCha8Rand::new(SAMPLE_SEED).clone_state();
+    rng.try_restore_state(&state).unwrap();
+    rng.read_u32();
+    assert_eq!(
+        rng.stats(),
+        Stats {
+            refills: 6,
+            bytes_produced: 4012,
+            bytes_produced_since_seed: 4,
+        }
+    );
+
+    let mut cache = SeedCache::new();
+    cache.set_seed(&mut rng, SAMPLE_SEED);
+    rng.read_u32();
+    let stats = rng.stats();
+    assert_eq!(stats.refills, 7);
+    assert_eq!(stats.bytes_produced, 4016);
+    assert_eq!(stats.bytes_produced_since_seed, 4);
+    assert_eq!(cache.new_rng(SAMPLE_SEED).stats().refills, 1);
+
+    let rng = ChaCha8Rand::builder(SAMPLE_SEED)
+        .position(StreamPosition::new(5, 0).unwrap())
+        .build();
+    assert_eq!(rng.stats().bytes_produced, 0);
+}
+
+#[cfg(feature = "output_digest")]
+#[test]
+fn output_digest_is_fnv1a_of_bytes_read() {
+    fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+        for &b in bytes {
+            hash = (hash ^ u64::from(b)).wrapping_mul(0x100000001b3);
+        }
+        hash
+    }
+    let mut reference = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut expected = 0xcbf29ce484222325;
+    let mut buf = [0; 3000];
+
+    let mut rng = ChaCha8Rand::new_lazy(SAMPLE_SEED);
+    assert_eq!(rng.output_digest(), None);
+    rng.enable_output_digest();
+    assert_eq!(rng.output_digest(), Some(expected));
+    rng.read_u32();
+    rng.read_bytes(&mut [0; 2500]);
+    reference.read_bytes(&mut buf[..2504]);
+    expected = fnv1a(expected, &buf[..2504]);
+    assert_eq!(rng.output_digest(), Some(expected));
+
+    // Skipped bytes aren't hashed, neither within the buffer nor across refills.
+    rng.skip_bytes(10);
+    rng.read_u64();
+    rng.skip_bytes(1500);
+    let n = rng.fill_buf().len();
+    rng.consume(n);
+    reference.skip_bytes(10);
+    expected = fnv1a(expected, &reference.read_u64().to_le_bytes());
+    reference.skip_bytes(1500);
+    reference.read_bytes(&mut buf[..n]);
+    expected = fnv1a(expected, &buf[..n]);
+    assert_eq!(rng.output_digest(), Some(expected));
+
+    // The digest carries on across seeking, restoring, and re-seeding.
+    let state = rng.clone_state();
+    rng.seek_to(100).unwrap();
+    let x = rng.read_u128();
+    rng.try_restore_state(&state).unwrap();
+    let y = rng.read_u128();
+    SeedCache::new().set_seed(&mut rng, SAMPLE_SEED);
+    let z = rng.read_u64();
+    reference.seek_to(100).unwrap();
+    assert_eq!(reference.read_u128(), x);
+    expected = fnv1a(expected, &x.to_le_bytes());
+    expected = fnv1a(expected, &y.to_le_bytes());
+    expected = fnv1a(expected, &z.to_le_bytes());
+    assert_eq!(z, SAMPLE_OUTPUT_U64LE[0]);
+    assert_eq!(rng.output_digest(), Some(expected));
+    assert_eq!(rng.clone().output_digest(), Some(expected));
+}
+
+#[test]
+fn stream_position_around_iteration_boundary() {
+    for (bytes, iteration, offset) in [(0, 0, 0), (991, 0, 991), (992, 1, 0), (993, 1, 1)] {
+        let pos = StreamPosition::from_byte_offset(bytes).unwrap();
+        assert_eq!((pos.iteration(), pos.offset()), (iteration, offset));
+        assert_eq!(pos.to_byte_offset(), bytes);
+    }
+    assert_eq!(StreamPosition::new(0, 992), None);
+}
+
+#[test]
+fn stream_position_arithmetic() {
+    let pos = StreamPosition::new(3, 990).unwrap();
+    let later = pos.checked_add(5).unwrap();
+    assert_eq!((later.iteration(), later.offset()), (4, 3));
+    assert_eq!(later.checked_sub(5), Some(pos));
+    assert_eq!(pos.distance(later), 5);
+    assert_eq!(later.distance(pos), 5);
+    assert_eq!(pos.bytes_until(later), Some(5));
+    assert_eq!(later.bytes_until(pos), None);
+    assert!(pos < later);
+    assert_eq!(StreamPosition::START.checked_sub(1), None);
+}
+
+#[test]
+fn stream_position_extremes() {
+    let max = StreamPosition::MAX;
+    assert_eq!(
+        StreamPosition::from_byte_offset(max.to_byte_offset()),
+        Some(max)
+    );
+    assert_eq!(max.checked_add(1), None);
+    assert_eq!(StreamPosition::from_byte_offset(u128::MAX), None);
+}
+
+#[test]
+fn snapshot_stream_position() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    rng.read_bytes(&mut [0; 992]);
+    // The buffer is used up but not refilled yet, which is the start of the next iteration.
+    let state = rng.clone_state();
+    assert_eq!((state.iteration, state.bytes_consumed), (0, 992));
+    assert_eq!(state.stream_position(), StreamPosition::new(1, 0));
+    assert_eq!(state.position(), 992);
+
+    let mut state = ChaCha8State {
+        seed: *SAMPLE_SEED,
+        bytes_consumed: 992,
+        iteration: u64::MAX,
+    };
+    assert_eq!(state.stream_position(), None);
+    state.bytes_consumed = 991;
+    assert_eq!(state.stream_position(), Some(StreamPosition::MAX));
+    state.bytes_consumed = 993;
+    assert_eq!(state.stream_position(), None);
+}
+
+#[test]
+fn seed_cache_gives_same_output() {
+    let mut cache = SeedCache::new();
+    let other_seed = SAMPLE_SEED.map(|byte| byte ^ 3);
+    for seed in [SAMPLE_SEED, SAMPLE_SEED, &other_seed, SAMPLE_SEED] {
+        let mut rng = cache.new_rng(seed);
+        let mut expected = ChaCha8Rand::new(seed);
+        for _ in 0..300 {
+            assert_eq!(rng.read_u32(), expected.read_u32());
+        }
+    }
+}
+
+#[test]
+fn seed_cache_set_seed() {
+    let mut cache = SeedCache::new();
+    let mut rng = ChaCha8Rand::with_backend_impl(&[7; 32], Backend::scalar());
+    rng.read_u64();
+    cache.set_seed(&mut rng, SAMPLE_SEED);
+    check_byte_output(iter::repeat_with(|| rng.read_u32()).flat_map(u32::to_le_bytes));
+}
+
+#[test]
+fn manual_refill_loop_matches_stream() {
+    let backend = Backend::detect_best();
+    let mut key = crate::seed_from_bytes(SAMPLE_SEED);
+    let mut buf = Buffer::new();
+    let mut output = vec![];
+    for _ in 0..3 {
+        backend.refill(&key, &mut buf);
+        output.extend_from_slice(buf.output());
+        key = crate::seed_from_bytes(buf.new_key());
+    }
+    check_byte_output(output);
+}
+
+#[test]
+fn erased_rng_reads_same_stream() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let erased: &mut dyn ErasedRng = &mut rng;
+    let mut output = vec![];
+    for i in 0..600 {
+        match i % 3 {
+            0 => output.extend(erased.read_u32().to_le_bytes()),
+            1 => output.extend(erased.read_u64().to_le_bytes()),
+            _ => {
+                let mut bytes = [0; 5];
+                erased.read_bytes(&mut bytes);
+                output.extend(bytes);
+            }
+        }
+    }
+    check_byte_output(output);
+}
+
+#[test]
+fn new_in_storage() {
+    let mut storage = Storage::new();
+    let rng = ChaCha8Rand::new_in(SAMPLE_SEED, &mut storage);
+    check_byte_output(iter::repeat_with(|| rng.read_u64().to_le_bytes()).flatten());
+    // Re-initializing the same storage starts over from the new seed.
+    let rng = ChaCha8Rand::new_in(SAMPLE_SEED, &mut storage);
+    assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[0]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn new_boxed_matches_new() {
+    let mut rng = ChaCha8Rand::new_boxed(SAMPLE_SEED);
+    check_byte_output(iter::repeat_with(|| rng.read_u64().to_le_bytes()).flatten());
+}
+
+#[test]
+fn iter_with_is_lazy_and_in_order() {
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let items: Vec<(u32, u64)> = rng
+        .iter_with(|rng| (rng.read_u32(), rng.read_u64()))
+        .take(100)
+        .collect();
+    for item in items {
+        assert_eq!(item, (expected.read_u32(), expected.read_u64()));
+    }
+    // Nothing beyond the items taken above may have been consumed.
+    assert_eq!(rng.read_u64(), expected.read_u64());
+}
+
+#[test]
+fn state_text_roundtrip() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    for _ in 0..300 {
+        let state = rng.clone_state();
+        let parsed: ChaCha8State = state.to_string().parse().unwrap();
+        assert_eq!(parsed, state);
+        rng.read_bytes(&mut [0; 7]);
+    }
+}
+
+#[test]
+fn state_bytes_roundtrip() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    for _ in 0..300 {
+        let state = rng.clone_state();
+        let parsed = ChaCha8State::from_bytes(&state.to_bytes()).unwrap();
+        assert_eq!(parsed, state);
+        rng.read_bytes(&mut [0; 7]);
+    }
+}
+
+#[test]
+fn state_bytes_rejects_excessive_count() {
+    let mut bytes = [0xFF; ChaCha8State::ENCODED_LEN];
+    bytes[32..34].copy_from_slice(&992u16.to_le_bytes());
+    assert!(ChaCha8State::from_bytes(&bytes).is_ok());
+    bytes[32..34].copy_from_slice(&993u16.to_le_bytes());
+    assert!(ChaCha8State::from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn state_equality() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let a = rng.clone_state();
+    assert_eq!(a, rng.clone_state());
+    rng.read_u32();
+    let b = rng.clone_state();
+    assert_ne!(a, b);
+    let states: std::collections::HashSet<_> = [a, b, a].into_iter().collect();
+    assert_eq!(states.len(), 2);
+}
+
+#[test]
+fn rng_equality_ignores_refill_timing() {
+    let mut a = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut b = ChaCha8Rand::with_backend(SAMPLE_SEED, Backend::scalar());
+    assert_eq!(a, b);
+    a.read_bytes(&mut [0; 992]);
+    assert_ne!(a, b);
+    b.skip_bytes(992);
+    assert_eq!(a, b);
+    // Refilling early doesn't change the logical state.
+    b.fill_buf();
+    assert_eq!(a, b);
+    assert_eq!(a.read_u64(), b.read_u64());
+    assert_eq!(a, b);
+
+    let mut restored = ChaCha8Rand::new(&[0; 32]);
+    restored.try_restore_state(&a.clone_state()).unwrap();
+    assert_eq!(restored, a);
+    // Same output from here on, but a different position.
+    restored
+        .try_restore_state(&ChaCha8State {
+            iteration: 0,
+            ..a.clone_state()
+        })
+        .unwrap();
+    assert_ne!(restored, a);
+}
+
+#[test]
+fn state_text_rejects_malformed_input() {
+    let hex = "4142434445464748494a4b4c4d4e4f505152535455565758595a313233343536";
+    let upper = hex.to_uppercase();
+    assert!(format!("chacha8rand:v1:{hex}:992")
+        .parse::<ChaCha8State>()
+        .is_ok());
+    assert!(format!("chacha8rand:v1:{upper}:0")
+        .parse::<ChaCha8State>()
+        .is_ok());
+    let state = format!("chacha8rand:v2:{hex}:{}:992", u64::MAX)
+        .parse::<ChaCha8State>()
+        .unwrap();
+    assert_eq!((state.iteration, state.bytes_consumed), (u64::MAX, 992));
+    let state = format!("chacha8rand:v1:{hex}:5")
+        .parse::<ChaCha8State>()
+        .unwrap();
+    assert_eq!((state.iteration, state.bytes_consumed), (0, 5));
+    for bad in [
+        format!("chacha8rand:v1:{hex}:993"),
+        format!("chacha8rand:v1:{hex}:+1"),
+        format!("chacha8rand:v1:{hex}:"),
+        format!("chacha8rand:v1:{hex}"),
+        format!("chacha8rand:v2:{hex}:0"),
+        format!("chacha8rand:v2:{hex}:+1:0"),
+        format!("chacha8rand:v2:{hex}::0"),
+        format!("chacha8rand:v2:{hex}:18446744073709551616:0"),
+        format!("chacha8rand:v2:{hex}:0:993"),
+        format!("chacha8rand:v1:{hex}:0:0"),
+        format!("chacha8rand:v1:{}:0", &hex[1..]),
+        format!("chacha8rand:v1:{}g:0", &hex[1..]),
+        format!(" chacha8rand:v1:{hex}:0"),
+    ] {
+        assert!(bad.parse::<ChaCha8State>().is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn seed_hex_roundtrip() {
+    let seed = Seed(*SAMPLE_SEED);
+    assert_eq!(Seed::from_hex(&seed.to_hex()), Some(seed));
+    assert_eq!(Seed::from_hex(&seed.to_hex_upper()), Some(seed));
+    assert_eq!(Seed::from_hex(&[b'x'; 64]), None);
+}
+
+#[test]
+fn seed_hex_formatting() {
+    let mut bytes = [0; 32];
+    bytes[0] = 0xAB;
+    bytes[1] = 0x09;
+    bytes[31] = 0xF0;
+    let seed = Seed(bytes);
+    let lower = format!("{seed:x}");
+    assert_eq!(lower.len(), 64);
+    assert!(lower.starts_with("ab09000000"));
+    assert!(lower.ends_with("00f0"));
+    assert_eq!(lower.as_bytes(), seed.to_hex());
+    assert_eq!(format!("{seed:X}"), lower.to_uppercase());
+    assert_eq!(format!("{seed:#x}"), format!("0x{lower}"));
+}
+
+#[test]
+fn seed_from_u64_is_splitmix64() {
+    // Computed with the reference implementation of SplitMix64.
+    let expected = [
+        (
+            0,
+            b"afcd1d7b39a820e2f465b9a16a9e786e4f450980185dc406ec814c72a8b88bf8",
+        ),
+        (
+            1234567,
+            b"85fc08fb17d09e59a50f545884f0732c777cf2a3e5bc3e883f7b17e940f7be3f",
+        ),
+    ];
+    for (x, hex) in expected {
+        let seed = Seed::from_hex(hex).unwrap();
+        assert_eq!(Seed::from_u64(x), seed);
+        let mut rng = ChaCha8Rand::new_from_u64(x);
+        assert_eq!(rng.read_u64(), ChaCha8Rand::new(&seed.0).read_u64());
+    }
+}
+
+#[test]
+fn ip_addresses_in_subnet() {
+    use core::net::{Ipv4Addr, Ipv6Addr};
+
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut reference = ChaCha8Rand::new(SAMPLE_SEED);
+    let net4 = Ipv4Addr::new(198, 51, 100, 77);
+    for prefix_len in 0..=32 {
+        let addr = u32::from(rng.read_ipv4_addr_in(net4, prefix_len));
+        let random = u32::from(reference.read_ipv4_addr());
+        let mask = (u64::from(u32::MAX) << (32 - prefix_len)) as u32;
+        assert_eq!(addr & mask, u32::from(net4) & mask);
+        assert_eq!(addr & !mask, random & !mask);
+    }
+    let net6 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+    assert_eq!(rng.read_ipv6_addr_in(net6, 128), net6);
+    reference.read_ipv6_addr();
+    assert_eq!(
+        rng.read_ipv6_addr_in(net6, 0),
+        reference.read_ipv6_addr_in(net6, 0)
+    );
+    // Full-width reads regardless of prefix length keep the streams in sync.
+    assert_eq!(rng.read_u64(), reference.read_u64());
+}
+
+#[test]
+fn dynamic_ports_stay_in_range() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut seen_low_bits = 0u16;
+    for _ in 0..1000 {
+        let port = rng.read_dynamic_port();
+        assert!(port >= 49152);
+        seen_low_bits |= port & 0x3FFF;
+    }
+    assert_eq!(seen_low_bits, 0x3FFF);
+}
+
+#[test]
+fn set_backend_preserves_stream() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let backends = [
+        Some(Backend::scalar()),
+        Backend::x86_sse2(),
+        Backend::x86_ssse3(),
+        Backend::x86_avx2(),
+        Backend::aarch64_neon(),
+        Backend::wasm32_simd128(),
+        Backend::portable_simd(),
+    ];
+    let mut backends = backends.into_iter().flatten().cycle();
+    let output = iter::from_fn(|| {
+        // Switch backends before every read, so some switches happen in the middle of a buffer and
+        // some right before a refill.
+        rng.set_backend(backends.next().unwrap());
+        Some(rng.read_u64().to_le_bytes())
+    });
+    check_byte_output(output.flatten());
+}
+
+#[test]
+fn mix_in_matches_specification() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let data: Vec<u8> = (0..40).collect();
+    rng.mix_in(&data);
+
+    let mut reference = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut key = reference.read_seed();
+    let mut last_chunk = [0; 32];
+    last_chunk[..8].copy_from_slice(&data[32..]);
+    let mut len_chunk = [0; 32];
+    len_chunk[..8].copy_from_slice(&40u64.to_le_bytes());
+    for chunk in [&data[..32], &last_chunk[..], &len_chunk[..]] {
+        for (k, m) in key.iter_mut().zip(chunk) {
+            *k ^= m;
+        }
+        key = ChaCha8Rand::new(&key).read_seed();
+    }
+    reference.set_seed(&key);
+    for _ in 0..200 {
+        assert_eq!(rng.read_u64(), reference.read_u64());
+    }
+}
+
+#[test]
+fn mix_in_absorbs_with_plain_chacha8rand() {
+    let data = b"sensor noise";
+    let mut rng = ChaCha12Rand::with_stream(SAMPLE_SEED, 5);
+    rng.mix_in(data);
+
+    // Only taking the chaining key and re-seeding use the generator's own stream and rounds.
+    let mut reference = ChaCha12Rand::with_stream(SAMPLE_SEED, 5);
+    let mut key = reference.read_seed();
+    let mut chunk = [0; 32];
+    chunk[..data.len()].copy_from_slice(data);
+    let mut len_chunk = [0; 32];
+    len_chunk[..8].copy_from_slice(&(data.len() as u64).to_le_bytes());
+    for chunk in [chunk, len_chunk] {
+        for (k, m) in key.iter_mut().zip(chunk) {
+            *k ^= m;
+        }
+        key = ChaCha8Rand::new(&key).read_seed();
+    }
+    reference.set_seed(&key);
+    assert_eq!(rng.stream(), 5);
+    for _ in 0..200 {
+        assert_eq!(rng.read_u64(), reference.read_u64());
+    }
+}
+
+#[test]
+fn mix_in_depends_on_length() {
+    let mut a = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut b = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut c = ChaCha8Rand::new(SAMPLE_SEED);
+    a.mix_in(b"");
+    b.mix_in(b"\0");
+    c.mix_in(b"\0\0");
+    let outputs = [a.read_u64(), b.read_u64(), c.read_u64()];
+    assert_ne!(outputs[0], outputs[1]);
+    assert_ne!(outputs[1], outputs[2]);
+    assert_ne!(outputs[0], outputs[2]);
+}
+
+#[test]
+fn read_array_sizes() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let chunks = iter::repeat_with(|| {
+        let mut chunk = Vec::new();
+        chunk.extend(rng.read_array::<0>());
+        chunk.extend(rng.read_array::<1>());
+        chunk.extend(rng.read_array::<7>());
+        chunk.extend(rng.read_array::<100>());
+        chunk
+    });
+    check_byte_output(chunks.flatten());
+}
+
+#[test]
+fn read_floats_use_high_bits() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    for _ in 0..300 {
+        assert_eq!(
+            rng.read_f64(),
+            (expected.read_u64() >> 11) as f64 / (1u64 << 53) as f64
+        );
+        assert_eq!(
+            rng.read_f32(),
+            (expected.read_u32() >> 8) as f32 / (1u32 << 24) as f32
+        );
+    }
+}
+
+#[test]
+fn fill_f64_unit_matches_read_u64() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    // Start at an odd offset so some values straddle the end of the buffer.
+    let first = rng.read_array::<3>();
+    let mut xs = vec![0.0; SAMPLE_OUTPUT_U64LE.len() - 1];
+    rng.fill_f64_unit(&mut xs[..100]);
+    rng.fill_f64_unit(&mut xs[100..]);
+
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    assert_eq!(expected.read_array::<3>(), first);
+    for x in xs {
+        assert_eq!(x, (expected.read_u64() >> 11) as f64 / (1u64 << 53) as f64);
+    }
+}
+
+#[test]
+fn fill_f32_unit_matches_read_u32() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let first = rng.read_array::<1>();
+    let mut xs = vec![0.0f32; 2 * SAMPLE_OUTPUT_U64LE.len() - 1];
+    rng.fill_f32_unit(&mut xs[..7]);
+    rng.fill_f32_unit(&mut xs[7..]);
+
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    assert_eq!(expected.read_array::<1>(), first);
+    for x in xs {
+        assert_eq!(x, (expected.read_u32() >> 8) as f32 / (1u32 << 24) as f32);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn read_vec_and_extend_vec() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut bytes = rng.read_vec(5);
+    rng.extend_vec(&mut bytes, 0);
+    rng.extend_vec(&mut bytes, 1000);
+    bytes.reserve(5000);
+    rng.extend_vec(&mut bytes, 2000);
+    assert_eq!(bytes.len(), 3005);
+    check_byte_output(bytes);
+}
+
+#[test]
+fn read_bytes_vectored_is_contiguous() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let (mut a, mut b, mut c, mut d) = ([0; 3], [0; 0], vec![0; 1200], [0; 2000]);
+    rng.read_bytes_vectored(&mut [&mut a, &mut b, &mut c, &mut d]);
+    check_byte_output(a.into_iter().chain(b).chain(c).chain(d));
+}
+
+#[test]
+fn read_bytes_uninit_matches_read_bytes() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut output = Vec::new();
+    for len in [0, 1, 500, 1500, 1000] {
+        let mut storage = vec![MaybeUninit::uninit(); len];
+        output.extend_from_slice(rng.read_bytes_uninit(&mut storage));
+    }
+    check_byte_output(output);
+}
+
+#[test]
+fn seeds_match_read_seed() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    check_byte_output(rng.seeds().take(100).flatten());
+}
+
+#[test]
+fn read_u32_below_covers_range() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut counts = [0u32; 7];
+    for _ in 0..7000 {
+        counts[rng.read_u32_below(7) as usize] += 1;
+    }
+    assert!(
+        counts.iter().all(|&c| (850..1150).contains(&c)),
+        "{counts:?}"
+    );
+    // Smallest and largest ranges: no rejections possible for n = 1, and the high half of the
+    // product is the sample itself for n = 2^32 - 1 (barring the rare rejection).
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    assert_eq!(rng.read_u32_below(1), 0);
+    expected.read_u32();
+    assert_eq!(rng.read_u32_below(u32::MAX), expected.read_u32() - 1);
+}
+
+#[test]
+fn read_u64_below_matches_specification() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    for n in [1, 3, 1000, 1 << 40, u64::MAX / 3 * 2, u64::MAX] {
+        for _ in 0..20 {
+            let threshold = n.wrapping_neg() % n;
+            let m = loop {
+                let m = u128::from(expected.read_u64()) * u128::from(n);
+                if (m as u64) >= threshold {
+                    break m;
+                }
+            };
+            assert_eq!(rng.read_u64_below(n), (m >> 64) as u64);
+        }
+    }
+}
+
+#[test]
+fn read_range_pointer_sized_like_64_bit() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    for _ in 0..100 {
+        assert_eq!(
+            rng.read_range(3..1000usize) as u64,
+            expected.read_range(3..1000u64)
+        );
+        assert_eq!(
+            rng.read_range(-5..=5isize) as i64,
+            expected.read_range(-5..=5i64)
+        );
+    }
+    let mut counts = [0u32; 11];
+    for _ in 0..11_000 {
+        counts[(rng.read_range(-5..=5isize) + 5) as usize] += 1;
+    }
+    assert!(counts.iter().all(|c| c.abs_diff(1000) < 150), "{counts:?}");
+}
+
+#[test]
+fn bernoulli_helpers() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let (mut bools, mut ratios) = (0, 0);
+    for _ in 0..10_000 {
+        bools += u32::from(rng.read_bool(0.3));
+        ratios += u32::from(rng.read_ratio(2, 3));
+        assert!(!rng.read_bool(0.0) && rng.read_bool(1.0));
+        assert!(!rng.read_ratio(0, 5) && rng.read_ratio(5, 5));
+    }
+    assert!(bools.abs_diff(3000) < 200, "{bools}");
+    assert!(ratios.abs_diff(6667) < 200, "{ratios}");
+}
+
+#[test]
+#[should_panic]
+fn read_bool_rejects_nan() {
+    ChaCha8Rand::new(SAMPLE_SEED).read_bool(f64::NAN);
+}
+
+#[test]
+fn shorthands_follow_building_blocks() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    for _ in 0..100 {
+        assert_eq!(rng.u32(1..=6), expected.read_range(1..=6u32));
+        assert_eq!(rng.i8(-3..3), expected.read_range(-3..=2i8));
+        assert_eq!(rng.usize(..10), expected.read_range(0..=9usize));
+        assert_eq!(rng.u16(65_000..), expected.read_range(65_000..=u16::MAX));
+        assert_eq!(rng.u64(..), expected.read_u64());
+        assert_eq!(
+            rng.i32((Bound::Excluded(0), Bound::Included(5))),
+            expected.read_range(1..=5i32)
+        );
+        assert_eq!(rng.bool(), expected.read_array::<1>()[0] & 1 == 1);
+        assert_eq!(rng.f64(), expected.read_f64());
+        let letters = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+        assert_eq!(
+            rng.alphanumeric(),
+            char::from(letters[expected.read_u32_below(62) as usize])
+        );
+        assert_eq!(
+            rng.lowercase(),
+            char::from(b'a' + expected.read_u32_below(26) as u8)
+        );
+        assert_eq!(
+            rng.digit(16),
+            char::from_digit(expected.read_u32_below(16), 16).unwrap()
+        );
+        assert_eq!(
+            rng.choice((10..20).rev()),
+            Some(19 - expected.read_u32_below(10))
+        );
+        assert_eq!(rng.choice(iter::empty::<u8>()), None);
+    }
+    assert_eq!(rng, expected);
+}
+
+#[test]
+#[should_panic]
+fn shorthands_reject_empty_ranges() {
+    ChaCha8Rand::new(SAMPLE_SEED).u8(..0);
+}
+
+#[test]
+fn shuffle_matches_specification() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut expected_rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut items: Vec<u32> = (0..100).collect();
+    let mut expected = items.clone();
+    rng.shuffle(&mut items);
+    for i in (1..expected.len()).rev() {
+        let j = expected_rng.read_u32_below(i as u32 + 1) as usize;
+        expected.swap(i, j);
+    }
+    assert_eq!(items, expected);
+    rng.shuffle::<u32>(&mut []);
+    rng.shuffle(&mut [1]);
+    assert_eq!(rng.read_u64(), expected_rng.read_u64());
+}
+
+#[test]
+fn shuffle_is_uniform() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut counts = [0u32; 6];
+    for _ in 0..6000 {
+        let mut items = [0, 1, 2];
+        rng.shuffle(&mut items);
+        let idx = match items {
+            [0, 1, 2] => 0,
+            [0, 2, 1] => 1,
+            [1, 0, 2] => 2,
+            [1, 2, 0] => 3,
+            [2, 0, 1] => 4,
+            _ => 5,
+        };
+        counts[idx] += 1;
+    }
+    assert!(counts.iter().all(|c| c.abs_diff(1000) < 120), "{counts:?}");
+}
+
+#[test]
+fn choose_picks_every_element() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut counts = [0u32; 5];
+    for _ in 0..5000 {
+        let idx = *rng.choose(&[0, 1, 2, 3, 4]).unwrap();
+        *rng.choose_mut(&mut counts[idx..=idx]).unwrap() += 1;
+    }
+    assert!(counts.iter().all(|c| c.abs_diff(1000) < 120), "{counts:?}");
+
+    let mut expected = rng.clone();
+    assert_eq!(rng.choose::<u8>(&[]), None);
+    assert_eq!(rng.choose_mut::<u8>(&mut []), None);
+    assert_eq!(rng.choose(&[(); 7]), Some(&()));
+    expected.read_u32_below(7);
+    assert_eq!(rng.read_u64(), expected.read_u64());
+}
+
+#[test]
+fn choose_weighted_proportions() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let items = [(0, 0), (1, 1), (2, 0), (3, 3), (4, 6)];
+    let mut counts = [0u32; 5];
+    for _ in 0..10_000 {
+        let &(idx, _) = rng.choose_weighted(&items, |&(_, w)| w).unwrap();
+        counts[idx] += 1;
+    }
+    assert_eq!([counts[0], counts[2]], [0, 0]);
+    for (count, expected) in [(counts[1], 1000), (counts[3], 3000), (counts[4], 6000)] {
+        assert!(count.abs_diff(expected) < 200, "{counts:?}");
+    }
+    let mut expected = rng.clone();
+    assert_eq!(rng.choose_weighted(&[1, 2], |_| 0), None);
+    assert_eq!(rng.choose_weighted::<u8>(&[], |_| 1), None);
+    assert_eq!(rng.read_u64(), expected.read_u64());
+}
+
+#[test]
+#[should_panic]
+fn choose_weighted_overflow() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    rng.choose_weighted(&[u64::MAX, 1], |&w| w);
+}
+
+#[test]
+fn hasher_is_siphash13_and_platform_independent() {
+    use core::hash::{BuildHasher, Hash, Hasher};
+    use std::collections::{hash_map::DefaultHasher, HashMap};
+
+    // `DefaultHasher::new()` is SipHash-1-3 with both keys zero. That's not guaranteed, but it's
+    // been true for years, and byte writes are hashed the same way on all platforms.
+    let bytes: Vec<u8> = (0..100).collect();
+    for len in 0..bytes.len() {
+        for split in [0, len / 3, len / 2] {
+            let mut ours = ChaCha8Hasher::new_with_keys(0, 0);
+            let mut theirs = DefaultHasher::new();
+            for part in [&bytes[..split], &bytes[split..len]] {
+                ours.write(part);
+                theirs.write(part);
+            }
+            assert_eq!(ours.finish(), theirs.finish(), "len {len}, split {split}");
+        }
+    }
+
+    let mut ints = ChaCha8Hasher::new_with_keys(1, 2);
+    ints.write_u32(0x0403_0201);
+    ints.write_usize(5);
+    let mut le_bytes = ChaCha8Hasher::new_with_keys(1, 2);
+    le_bytes.write(&[1, 2, 3, 4, 5, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(ints.finish(), le_bytes.finish());
+
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let state = ChaCha8RandomState::new(&mut rng);
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    let (k0, k1) = (expected.read_u64(), expected.read_u64());
+    let mut hasher = ChaCha8Hasher::new_with_keys(k0, k1);
+    "key".hash(&mut hasher);
+    assert_eq!(state.hash_one("key"), hasher.finish());
+    assert_ne!(state, ChaCha8RandomState::new(&mut rng));
+
+    let keys = |seed| {
+        let mut rng = ChaCha8Rand::new(seed);
+        let mut map = HashMap::with_hasher(ChaCha8RandomState::new(&mut rng));
+        map.extend((0..1000).map(|k| (k, ())));
+        map.into_keys().collect::<Vec<u32>>()
+    };
+    assert_eq!(keys(SAMPLE_SEED), keys(SAMPLE_SEED));
+    assert_ne!(keys(SAMPLE_SEED), keys(&[0; 32]));
+}
+
+#[test]
+fn read_char_skips_surrogates() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut planes = [0u32; 17];
+    for _ in 0..17_000 {
+        let c = u32::from(rng.read_char());
+        let x = expected.read_u32_below(1_112_064);
+        assert_eq!(c, if x < 0xD800 { x } else { x + 0x800 });
+        planes[(c >> 16) as usize] += 1;
+    }
+    // Plane 0 is missing the surrogates, but otherwise all planes are equally likely.
+    assert!(planes.iter().all(|p| p.abs_diff(1000) < 120), "{planes:?}");
+}
+
+#[test]
+fn read_char_from_charset() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    let charset = ['a', 'ß', '💖'];
+    for _ in 0..100 {
+        assert_eq!(
+            rng.read_char_from(&charset),
+            charset[expected.read_u32_below(3) as usize]
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn read_string_from_charset() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    let charset = ['x', 'ÿ'];
+    let s = rng.read_string_from(&charset, 50);
+    assert_eq!(s.chars().count(), 50);
+    assert!(s.chars().all(|c| c == expected.read_char_from(&charset)));
+    assert_eq!(rng.read_string_from(&[], 0), "");
+}
+
+#[test]
+fn sample_distinct_small_ranges() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut out = [0; 5];
+    rng.sample_distinct_u64_into(10..15, &mut out);
+    assert_eq!(out, [10, 11, 12, 13, 14]);
+
+    let mut counts = [0u32; 5];
+    for _ in 0..5000 {
+        let mut pair = [0; 2];
+        rng.sample_distinct_u64_into(0..5, &mut pair);
+        assert!(pair[0] < pair[1]);
+        for x in pair {
+            counts[x as usize] += 1;
+        }
+    }
+    assert!(
+        counts.iter().all(|&c| (1800..2200).contains(&c)),
+        "{counts:?}"
+    );
+
+    rng.sample_distinct_u64_into(7..7, &mut []);
+    let mut top = [0; 3];
+    rng.sample_distinct_u64_into(u64::MAX - 3..u64::MAX, &mut top);
+    assert_eq!(top, [u64::MAX - 3, u64::MAX - 2, u64::MAX - 1]);
+}
+
+#[test]
+#[should_panic]
+fn sample_distinct_too_many() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    rng.sample_distinct_u64_into(0..3, &mut [0; 4]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn sample_distinct_alloc_matches_buffer() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    for k in [0, 1, 10, 500] {
+        let mut out = vec![0; k];
+        expected.sample_distinct_u64_into(1000..10_000_000_000, &mut out);
+        assert_eq!(rng.sample_distinct_u64(1000..10_000_000_000, k), out);
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+#[test]
+fn weighted_reservoir_sample_frequencies() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let weights = [1.0, 0.0, 2.0, 3.0, 4.0];
+    let mut counts = [0u32; 5];
+    let trials = 20_000;
+    for _ in 0..trials {
+        let items = weights.iter().copied().enumerate();
+        let sample = rng.weighted_reservoir_sample(items, 1);
+        counts[sample[0]] += 1;
+    }
+    assert_eq!(counts[1], 0);
+    // Expected counts are 2000, 4000, 6000, 8000; allow a generous margin.
+    for (count, weight) in counts.iter().zip(weights) {
+        let expected = f64::from(trials) * weight / 10.0;
+        assert!((f64::from(*count) - expected).abs() < 400.0, "{counts:?}");
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+#[test]
+fn weighted_reservoir_sample_small_streams() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let items = || [('a', 1.0), ('b', 0.0), ('c', 1e-300), ('d', 5.0)];
+    assert!(rng.weighted_reservoir_sample(items(), 0).is_empty());
+    for k in [3, 4, 10, usize::MAX] {
+        let mut sample = rng.weighted_reservoir_sample(items(), k);
+        sample.sort();
+        assert_eq!(sample, ['a', 'c', 'd']);
+    }
+    let sample = rng.weighted_reservoir_sample(items(), 2);
+    assert_eq!(sample.len(), 2);
+    assert!(!sample.contains(&'b'));
+}
+
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+#[test]
+#[should_panic]
+fn weighted_reservoir_sample_negative_weight() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    rng.weighted_reservoir_sample([(1, 1.0), (2, -1.0)], 1);
+}
+
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+#[test]
+fn shuffle_weighted_orders_by_key() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let weights = [0.5, 0.0, 3.0, 1.0, 0.0, 2.0, 7.5];
+    let mut items: Vec<usize> = (0..weights.len()).collect();
+    let mut expected_rng = ChaCha8Rand::new(SAMPLE_SEED);
+    rng.shuffle_weighted(&mut items, &weights);
+
+    let mut expected: Vec<usize> = (0..weights.len()).collect();
+    let keys: Vec<f64> = weights
+        .iter()
+        .map(|w| (1.0 - (expected_rng.read_u64() >> 11) as f64 / (1u64 << 53) as f64).ln() / w)
+        .collect();
+    expected.sort_by(|&a, &b| keys[b].total_cmp(&keys[a]));
+    assert_eq!(items, expected);
+    assert_eq!(items[5..], [1, 4]);
+}
+
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+#[test]
+fn shuffle_weighted_zero_weight_key_is_negative_infinity() {
+    // `ln(1) / 0` would be a NaN whose sign depends on the platform.
+    assert_eq!(crate::sample::exponential_key(1.0, 0.0), f64::NEG_INFINITY);
+    assert_eq!(crate::sample::exponential_key(0.5, 0.0), f64::NEG_INFINITY);
+    assert_eq!(crate::sample::exponential_key(1.0, 2.0), 0.0);
+}
+
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+#[test]
+fn shuffle_weighted_first_item_frequencies() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut counts = [0u32; 3];
+    for _ in 0..10_000 {
+        let mut items = [0, 1, 2];
+        rng.shuffle_weighted(&mut items, &[1u8, 3, 6]);
+        counts[items[0]] += 1;
+    }
+    for (count, expected) in counts.into_iter().zip([1000, 3000, 6000]) {
+        assert!(count.abs_diff(expected) < 250, "{counts:?}");
+    }
+}
+
+#[test]
+fn stratified_samples_stay_in_their_strata() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut xs = [0.0; 100];
+    rng.fill_stratified_f64(&mut xs);
+    for (i, x) in xs.iter().enumerate() {
+        assert!((i as f64 / 100.0..(i + 1) as f64 / 100.0).contains(x));
+    }
+    let mut points = [[0.0; 2]; 12];
+    rng.fill_stratified_2d(4, &mut points);
+    for (i, [x, y]) in points.into_iter().enumerate() {
+        assert_eq!((x * 4.0) as usize, i % 4);
+        assert_eq!((y * 3.0) as usize, i / 4);
+    }
+
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    let u = (expected.read_u64() >> 11) as f64 / (1u64 << 53) as f64;
+    assert_eq!(xs[0], u / 100.0);
+}
+
+#[test]
+fn stratified_jitter_rounding_stays_below_stratum_end() {
+    // The largest value `read_f64` can return.
+    let u_max = 1.0 - f64::EPSILON / 2.0;
+    // `(1 + u_max) / 4` rounds to exactly 0.5, the start of the next stratum.
+    assert_eq!((1.0 + u_max) / 4.0, 0.5);
+    assert!(crate::stratified::jitter(1, u_max, 4.0) < 0.5);
+    for n in [3, 4, 7, 10, 100, 1000] {
+        for i in 0..n {
+            let x = crate::stratified::jitter(i, u_max, n as f64);
+            assert!(x >= i as f64 / n as f64);
+            assert!(x < (i + 1) as f64 / n as f64, "stratum {i} of {n}: {x}");
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn stratified_2d_uneven_grid() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    rng.fill_stratified_2d(5, &mut [[0.0; 2]; 12]);
+}
+
+#[cfg(all(feature = "alloc", feature = "distributions"))]
+#[test]
+fn poisson_disk_is_valid_and_dense() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let (width, height, radius) = (40.0, 25.0, 2.0);
+    let points = rng.poisson_disk_2d(width, height, radius);
+    for (i, &[x1, y1]) in points.iter().enumerate() {
+        assert!((0.0..width).contains(&x1) && (0.0..height).contains(&y1));
+        for &[x2, y2] in &points[..i] {
+            assert!(f64::hypot(x1 - x2, y1 - y2) >= radius);
+        }
+    }
+    // Maximal Poisson-disk sets cover roughly 55-70% of the area with disks of radius r/2.
+    let coverage = points.len() as f64 * core::f64::consts::PI * radius * radius / 4.0;
+    assert!(coverage > 0.5 * width * height, "{} points", points.len());
+
+    let mut again = ChaCha8Rand::new(SAMPLE_SEED);
+    assert_eq!(again.poisson_disk_2d(width, height, radius), points);
+    assert!(again.poisson_disk_2d(0.0, 10.0, 1.0).is_empty());
+}
+
+#[cfg(feature = "graphgen")]
+#[test]
+fn erdos_renyi_edges() {
+    use crate::graphgen::erdos_renyi;
+
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let edges: Vec<_> = erdos_renyi(&mut rng, 300, 0.1).collect();
+    assert!(edges.windows(2).all(|w| w[0] < w[1]));
+    assert!(edges.iter().all(|&(u, v)| v < u && u < 300));
+    // 44850 possible edges, so we expect about 4485 with a standard deviation of about 64.
+    assert!(edges.len().abs_diff(4485) < 300, "{}", edges.len());
+
+    let complete: Vec<_> = erdos_renyi(&mut rng, 5, 1.0).collect();
+    let expected: Vec<_> = (0..5).flat_map(|u| (0..u).map(move |v| (u, v))).collect();
+    assert_eq!(complete, expected);
+    assert_eq!(erdos_renyi(&mut rng, 1000, 0.0).next(), None);
+    assert_eq!(erdos_renyi(&mut rng, 1, 1.0).next(), None);
+}
+
+#[cfg(all(feature = "graphgen", feature = "alloc"))]
+#[test]
+fn barabasi_albert_edges() {
+    use crate::graphgen::barabasi_albert;
+
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let (n, m) = (2000, 3);
+    let mut edges = barabasi_albert(&mut rng, n, m);
+    assert_eq!(edges.len(), (n - m) * m);
+    let mut degree = vec![0; n];
+    for &(u, v) in &edges {
+        assert!(v < u && u < n);
+        degree[u] += 1;
+        degree[v] += 1;
+    }
+    edges.sort();
+    edges.dedup();
+    assert_eq!(edges.len(), (n - m) * m);
+    // Preferential attachment produces hubs with far more than the average degree of 6.
+    assert!(degree.iter().any(|&d| d > 50));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn sample_indices_distinct_and_uniform() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut counts = [0u32; 10];
+    for _ in 0..3000 {
+        let indices = rng.sample_indices(10, 3);
+        assert!(indices.windows(2).all(|w| w[0] < w[1]));
+        for i in indices {
+            counts[i] += 1;
+        }
+    }
+    assert!(counts.iter().all(|c| c.abs_diff(900) < 100), "{counts:?}");
+    assert_eq!(rng.sample_indices(4, 4), [0, 1, 2, 3]);
+    assert!(rng.sample_indices(0, 0).is_empty());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn weighted_alias_exact_probabilities() {
+    use crate::WeightedAlias;
+
+    // Enumerate every (bucket, r) pair to check that the probabilities are exact.
+    let weights = [3, 0, 1, 7, 2, 0, 5];
+    let table = WeightedAlias::new(&weights).unwrap();
+    let total: u64 = weights.iter().sum();
+    let mut mass = [0u64; 7];
+    for &(threshold, alias) in &table.buckets {
+        assert!(threshold <= total);
+        mass[alias] += total - threshold;
+    }
+    for (i, &(threshold, _)) in table.buckets.iter().enumerate() {
+        mass[i] += threshold;
+    }
+    let n = weights.len() as u64;
+    assert_eq!(mass, weights.map(|w| w * n));
+
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut counts = [0u32; 7];
+    for _ in 0..18_000 {
+        counts[table.sample(&mut rng)] += 1;
+    }
+    assert_eq!([counts[1], counts[5]], [0, 0]);
+    assert!(counts[3].abs_diff(7000) < 300, "{counts:?}");
+
+    assert_eq!(WeightedAlias::new(&[]), None);
+    assert_eq!(WeightedAlias::new(&[0, 0]), None);
+    assert_eq!(WeightedAlias::new(&[u64::MAX, 1]), None);
+    let big = WeightedAlias::new(&[u64::MAX - 1, 1]).unwrap();
+    assert_eq!(big.len(), 2);
+}
+
+#[test]
+fn lazy_permutation_is_bijection() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    for n in 0..70 {
+        let perm = LazyPermutation::new(&mut rng, n);
+        let mut values: Vec<u64> = perm.iter().collect();
+        for (i, &x) in values.iter().enumerate() {
+            assert_eq!(perm.inverse(x), i as u64);
+        }
+        values.sort();
+        assert!(values.iter().copied().eq(0..n));
+    }
+}
+
+#[test]
+fn lazy_permutation_huge_domains() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    for n in [10_000_000_000, 1 << 63, (1 << 63) + 1, u64::MAX] {
+        let perm = LazyPermutation::new(&mut rng, n);
+        for i in [0, 1, 12345, n - 1] {
+            let x = perm.get(i);
+            assert!(x < n);
+            assert_eq!(perm.inverse(x), i);
+        }
+    }
+    // The permutation is fully determined by 64 bytes of output.
+    let mut a = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut b = ChaCha8Rand::new(SAMPLE_SEED);
+    let (pa, pb) = (
+        LazyPermutation::new(&mut a, 1000),
+        LazyPermutation::new(&mut b, 1000),
+    );
+    assert!(pa.iter().eq(pb.iter()));
+    assert_eq!(a.clone_state().bytes_consumed, 64);
+}
+
+#[cfg(feature = "macros")]
+mod macros {
+    use crate::{ChaCha8Rand, Random, UniformEnum};
+
+    use super::SAMPLE_SEED;
+
+    #[derive(UniformEnum, Debug, Clone, Copy, PartialEq)]
+    enum Direction {
+        North,
+        East = 5,
+        South,
+        West,
+    }
+
+    #[derive(UniformEnum, Debug, PartialEq)]
+    enum Single {
+        Only,
+    }
+
+    #[test]
+    fn uniform_enum_variants_in_declaration_order() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+        let variants = [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ];
+        let mut seen = [false; 4];
+        for _ in 0..100 {
+            let dir: Direction = rng.sample();
+            let idx = expected.read_u32_below(4) as usize;
+            assert_eq!(dir, variants[idx]);
+            seen[idx] = true;
+        }
+        assert_eq!(seen, [true; 4]);
+    }
+
+    #[test]
+    fn uniform_enum_single_variant() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        assert_eq!(rng.sample::<Single>(), Single::Only);
+    }
+
+    #[derive(Random, Debug, PartialEq)]
+    struct Fixture {
+        id: u64,
+        flags: [bool; 3],
+        #[random(range = 1..=6)]
+        die: u8,
+        #[random(range = -5..5)]
+        offset: i32,
+        dir: Direction,
+        nested: Pair<u16>,
+        unit: Unit,
+    }
+
+    #[derive(Random, Debug, PartialEq)]
+    struct Pair<T>(T, #[random(range = 10..20)] u64);
+
+    #[derive(Random, Debug, PartialEq)]
+    struct Unit;
+
+    #[test]
+    fn derived_random_samples_fields_in_order() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+        for _ in 0..50 {
+            let fixture: Fixture = rng.sample();
+            let manual = Fixture {
+                id: expected.read_u64(),
+                flags: [expected.sample(), expected.sample(), expected.sample()],
+                die: 1 + expected.read_u32_below(6) as u8,
+                offset: -5 + expected.read_u32_below(10) as i32,
+                dir: expected.sample(),
+                nested: Pair(expected.sample(), 10 + expected.read_u64_below(10)),
+                unit: Unit,
+            };
+            assert_eq!(fixture, manual);
+        }
+        assert_eq!(rng.read_u64(), expected.read_u64());
+    }
+}
+
+#[test]
+fn random_primitives_consume_their_width() {
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut bytes = [0; 16];
+    expected.read_bytes(&mut bytes[..1]);
+    assert_eq!(rng.sample::<i8>(), bytes[0] as i8);
+    expected.read_bytes(&mut bytes[..2]);
+    assert_eq!(
+        rng.sample::<u16>(),
+        u16::from_le_bytes([bytes[0], bytes[1]])
+    );
+    expected.read_bytes(&mut bytes);
+    assert_eq!(rng.sample::<u128>(), u128::from_le_bytes(bytes));
+    assert_eq!(rng.sample::<i32>(), expected.read_u32() as i32);
+    assert_eq!(rng.sample::<bool>(), expected.sample::<u8>() & 1 == 1);
+    assert_eq!(
+        rng.sample::<[u64; 2]>(),
+        [expected.read_u64(), expected.read_u64()]
+    );
+    let x: f64 = rng.sample();
+    assert_eq!(x, (expected.read_u64() >> 11) as f64 / (1u64 << 53) as f64);
+    let x: f32 = rng.sample();
+    assert_eq!(x, (expected.read_u32() >> 8) as f32 / (1u32 << 24) as f32);
+    assert_eq!(rng.read_u32(), expected.read_u32());
+}
+
+#[test]
+fn sample_range_extremes() {
+    use crate::__private::sample_range;
+
+    let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+    let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+    // Full inclusive ranges of 32 and 64 bit types don't need rejection sampling.
+    assert_eq!(
+        sample_range(&mut rng, i32::MIN..=i32::MAX),
+        expected.read_u32() as i32
+    );
+    assert_eq!(sample_range(&mut rng, 0..=u64::MAX), expected.read_u64());
+    assert_eq!(sample_range(&mut rng, 7u8..=7), 7);
+    expected.read_u32();
+    for _ in 0..1000 {
+        let x = sample_range(&mut rng, i8::MIN..i8::MAX);
+        assert_ne!(x, i8::MAX);
+        let y = sample_range(&mut rng, -3i64..=3);
+        assert!((-3..=3).contains(&y));
+    }
+}
+
+#[cfg(feature = "distributions")]
+mod distributions {
+    use crate::{
+        distributions::{Dirichlet, Gamma, Levy, Pareto},
+        ChaCha8Rand,
+    };
+
+    use super::SAMPLE_SEED;
+
+    fn mean_and_variance(samples: impl Iterator<Item = f64>) -> (f64, f64) {
+        let (mut n, mut sum, mut sum_sq) = (0.0, 0.0, 0.0);
+        for x in samples {
+            n += 1.0;
+            sum += x;
+            sum_sq += x * x;
+        }
+        let mean = sum / n;
+        (mean, sum_sq / n - mean * mean)
+    }
+
+    #[test]
+    fn gamma_moments() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        for (shape, scale) in [(0.3, 1.0), (1.0, 2.0), (2.5, 0.5), (40.0, 1.5)] {
+            let gamma = Gamma::new(shape, scale).unwrap();
+            let samples = (0..20_000).map(|_| gamma.sample(&mut rng));
+            let (mean, var) = mean_and_variance(samples);
+            let (expected_mean, expected_var) = (shape * scale, shape * scale * scale);
+            assert!(
+                (mean / expected_mean - 1.0).abs() < 0.05,
+                "{shape} {scale}: {mean}"
+            );
+            assert!(
+                (var / expected_var - 1.0).abs() < 0.1,
+                "{shape} {scale}: {var}"
+            );
+        }
+    }
+
+    #[test]
+    fn dirichlet_means() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let alpha = [0.5, 1.0, 2.0, 4.5];
+        let dirichlet = Dirichlet::new(&alpha).unwrap();
+        let mut sums = [0.0; 4];
+        let mut out = [0.0; 4];
+        for _ in 0..10_000 {
+            dirichlet.sample_into(&mut rng, &mut out);
+            assert!((out.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+            for (sum, x) in sums.iter_mut().zip(out) {
+                assert!(x >= 0.0);
+                *sum += x;
+            }
+        }
+        for (sum, a) in sums.iter().zip(alpha) {
+            assert!((sum / 10_000.0 - a / 8.0).abs() < 0.01, "{sums:?}");
+        }
+    }
+
+    #[test]
+    fn dirichlet_tiny_concentrations() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let dirichlet = Dirichlet::new(&[1e-4, 1e-4]).unwrap();
+        let mut out = [0.0; 2];
+        for _ in 0..100 {
+            dirichlet.sample_into(&mut rng, &mut out);
+            assert_eq!(out.iter().sum::<f64>(), 1.0);
+        }
+        assert_eq!(Dirichlet::new(&[]), None);
+        assert_eq!(Dirichlet::new(&[1.0, 0.0]), None);
+    }
+
+    #[test]
+    fn pareto_tail() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let pareto = Pareto::new(2.0, 1.5).unwrap();
+        let mut above = 0;
+        for _ in 0..20_000 {
+            let x = pareto.sample(&mut rng);
+            assert!(x >= 2.0);
+            // P(X > 8) = (2 / 8)^1.5 = 1/8
+            above += u32::from(x > 8.0);
+        }
+        assert!(above.abs_diff(2500) < 200, "{above}");
+    }
+
+    #[test]
+    fn levy_median() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let levy = Levy::new(1.0, 2.0).unwrap();
+        // The median is μ + c / (2 * erfcinv(1/2)^2) ≈ μ + 2.198 * c
+        let median = 1.0 + 2.0 * 2.198;
+        let mut below = 0;
+        for _ in 0..20_000 {
+            let x = levy.sample(&mut rng);
+            assert!(x >= 1.0);
+            below += u32::from(x < median);
+        }
+        assert!(below.abs_diff(10_000) < 300, "{below}");
+    }
+
+    #[test]
+    fn normal_matches_box_muller() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+        for _ in 0..100 {
+            let u1 = 1.0 - (expected.read_u64() >> 11) as f64 / (1u64 << 53) as f64;
+            let u2 = (expected.read_u64() >> 11) as f64 / (1u64 << 53) as f64;
+            let z = (-2.0 * u1.ln()).sqrt() * (core::f64::consts::TAU * u2).cos();
+            let x = rng.read_normal(5.0, 2.0);
+            // The platform's `ln` and `cos` may differ from libm in the last bit.
+            assert!((x - (5.0 + 2.0 * z)).abs() < 1e-12, "{x} vs {z}");
+        }
+        let (mean, var) = mean_and_variance((0..20_000).map(|_| rng.read_normal(-3.0, 0.5)));
+        assert!((mean + 3.0).abs() < 0.02, "{mean}");
+        assert!((var / 0.25 - 1.0).abs() < 0.05, "{var}");
+    }
+
+    #[test]
+    fn poisson_moments() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        for lambda in [0.1, 1.0, 4.5, 9.99, 10.0, 37.0, 1e6] {
+            let samples = (0..20_000).map(|_| rng.read_poisson(lambda) as f64);
+            let (mean, var) = mean_and_variance(samples);
+            assert!((mean / lambda - 1.0).abs() < 0.05, "{lambda}: {mean}");
+            assert!((var / lambda - 1.0).abs() < 0.1, "{lambda}: {var}");
+        }
+        assert_eq!(rng.read_poisson(0.0), 0);
+    }
+
+    #[test]
+    fn poisson_inversion_consumes_eight_bytes() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+        for _ in 0..100 {
+            rng.read_poisson(9.0);
+            expected.read_u64();
+        }
+        assert_eq!(rng.read_u64(), expected.read_u64());
+    }
+
+    #[test]
+    fn binomial_moments() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        for (n, p) in [
+            (10, 0.3),
+            (100, 0.05),
+            (100, 0.5),
+            (1000, 0.9),
+            (5_000_000, 0.01),
+        ] {
+            let samples = (0..20_000).map(|_| rng.read_binomial(n, p) as f64);
+            let (mean, var) = mean_and_variance(samples);
+            let n = n as f64;
+            assert!((mean / (n * p) - 1.0).abs() < 0.02, "{n} {p}: {mean}");
+            assert!(
+                (var / (n * p * (1.0 - p)) - 1.0).abs() < 0.1,
+                "{n} {p}: {var}"
+            );
+        }
+        assert_eq!(rng.read_binomial(0, 0.5), 0);
+        assert_eq!(rng.read_binomial(17, 0.0), 0);
+        assert_eq!(rng.read_binomial(17, 1.0), 17);
+        assert!((0..1000).all(|_| rng.read_binomial(3, 0.7) <= 3));
+    }
+
+    #[test]
+    fn read_gamma_matches_gamma() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+        let gamma = Gamma::new(0.7, 3.0).unwrap();
+        for _ in 0..100 {
+            assert_eq!(rng.read_gamma(0.7, 3.0), gamma.sample(&mut expected));
+        }
+    }
+
+    #[test]
+    fn log_normal_is_exp_of_normal() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+        for _ in 0..100 {
+            let x = rng.read_log_normal(1.5, 0.25);
+            assert_eq!(x, libm::exp(expected.read_normal(1.5, 0.25)));
+        }
+    }
+
+    #[test]
+    fn gamma_rejects_bad_parameters() {
+        for (shape, scale) in [(0.0, 1.0), (1.0, 0.0), (-1.0, 1.0), (f64::NAN, 1.0)] {
+            assert_eq!(Gamma::new(shape, scale), None);
+        }
+        assert_eq!(Gamma::new(1.0, f64::INFINITY), None);
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+mod bytemuck {
+    use crate::ChaCha8Rand;
+
+    use super::{check_byte_output, SAMPLE_SEED};
+
+    #[test]
+    fn fill_pod_fills_memory_in_order() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut arrays = [[0u8; 7]; 500];
+        rng.fill_pod(&mut arrays[..1]);
+        rng.fill_pod(&mut arrays[1..]);
+        check_byte_output(arrays.into_iter().flatten());
+
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut words = [0u64; 300];
+        rng.fill_pod(&mut words);
+        if cfg!(target_endian = "little") {
+            assert!(words.iter().all(|&w| w == expected.read_u64()));
+        }
+    }
+}
+
+#[cfg(feature = "nanorand")]
+mod nanorand {
+    use nanorand::{Rng, SeedableRng};
+
+    use crate::ChaCha8Rand;
+
+    use super::{check_byte_output, SAMPLE_OUTPUT_U64LE, SAMPLE_SEED};
+
+    #[test]
+    fn rand_and_generate_follow_the_stream() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut bytes = [0; SAMPLE_OUTPUT_U64LE.len() * 8];
+        for chunk in bytes.chunks_mut(16) {
+            chunk[..8].copy_from_slice(&rng.rand());
+            chunk[8..12].copy_from_slice(&rng.generate::<u32>().to_ne_bytes());
+            rng.fill_bytes(&mut chunk[12..]);
+        }
+        check_byte_output(bytes.iter().copied());
+    }
+
+    #[test]
+    fn reseed() {
+        let mut rng = ChaCha8Rand::new(&[0; 32]);
+        SeedableRng::reseed(&mut rng, *SAMPLE_SEED);
+        assert_eq!(rng, ChaCha8Rand::new(SAMPLE_SEED));
+    }
+}
+
+#[cfg(feature = "ndarray")]
+mod ndarray {
+    use ndarray::{s, Array2, Array3, ShapeBuilder};
+
+    use crate::ChaCha8Rand;
+
+    use super::{check_byte_output, SAMPLE_SEED};
+
+    #[test]
+    fn fill_u64_logical_order() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        // Fortran order and a bunch of elements so we cross a refill boundary.
+        let mut arr = Array3::zeros((4, 8, 13).f());
+        rng.fill_ndarray_u64(arr.view_mut());
+        check_byte_output(arr.iter().flat_map(|x: &u64| x.to_le_bytes()));
+    }
+
+    #[test]
+    fn fill_strided_view_only_touches_view() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut arr = Array2::from_elem((6, 6), -1.0);
+        rng.fill_ndarray_f64_unit(arr.slice_mut(s![..;2, 1..]));
+        for ((row, col), &x) in arr.indexed_iter() {
+            if row % 2 == 0 && col >= 1 {
+                assert!((0.0..1.0).contains(&x));
+            } else {
+                assert_eq!(x, -1.0);
+            }
+        }
+        let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+        assert_eq!(
+            arr[(0, 1)],
+            (expected.read_u64() >> 11) as f64 / (1u64 << 53) as f64
+        );
+    }
+
+    #[cfg(feature = "distributions")]
+    #[test]
+    fn fill_normal_matches_read_normal() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        // Transposed, so logical order differs from memory order, and enough elements (16 bytes
+        // each) to cross a refill boundary.
+        let mut arr = Array2::zeros((7, 11));
+        rng.fill_ndarray_normal(arr.view_mut().reversed_axes(), 3.0, 0.5);
+        let mut expected = ChaCha8Rand::new(SAMPLE_SEED);
+        for &x in arr.t().iter() {
+            assert_eq!(x, expected.read_normal(3.0, 0.5));
+        }
+        assert_eq!(rng.read_u64(), expected.read_u64());
+    }
+
+    #[cfg(feature = "distributions")]
+    #[test]
+    #[should_panic]
+    fn fill_normal_checks_parameters_for_empty_views() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        rng.fill_ndarray_normal(Array2::zeros((0, 3)).view_mut(), 0.0, -1.0);
+    }
+}
+
+#[cfg(feature = "uuid")]
+mod uuid {
+    use uuid::{Uuid, Variant};
+
+    use crate::ChaCha8Rand;
+
+    use super::SAMPLE_SEED;
+
+    #[test]
+    fn v4_uses_all_random_bits() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut expected = ChaCha8Rand::new(SAMPLE_SEED).read_seed();
+        let uuid = rng.read_uuid_v4();
+        assert_eq!(uuid.get_version_num(), 4);
+        assert_eq!(uuid.get_variant(), Variant::RFC4122);
+        expected[6] = (expected[6] & 0x0F) | 0x40;
+        expected[8] = (expected[8] & 0x3F) | 0x80;
+        assert_eq!(uuid, Uuid::from_slice(&expected[..16]).unwrap());
+        // 16 bytes consumed, not more or less.
+        let mut next = [0; 16];
+        rng.read_bytes(&mut next);
+        assert_eq!(next, expected[16..]);
+    }
+
+    #[test]
+    fn v7_layout() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let random = ChaCha8Rand::new(SAMPLE_SEED).read_seed();
+        let uuid = rng.read_uuid_v7(0x0123_4567_89AB);
+        let bytes = uuid.as_bytes();
+        assert_eq!(uuid.get_version_num(), 7);
+        assert_eq!(uuid.get_variant(), Variant::RFC4122);
+        assert_eq!(bytes[..6], [0x01, 0x23, 0x45, 0x67, 0x89, 0xAB]);
+        assert_eq!(bytes[7], random[1]);
+        assert_eq!(bytes[9..], random[3..10]);
+        // Only ten bytes are consumed.
+        let mut next = [0; 22];
+        rng.read_bytes(&mut next);
+        assert_eq!(next, random[10..]);
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck {
+    use quickcheck::{Arbitrary, Gen, QuickCheck};
+
+    use crate::{ChaCha8Rand, ChaCha8State, Seed, BUF_OUTPUT_LEN};
+
+    #[test]
+    fn arbitrary_states_can_be_restored() {
+        fn prop(seed: Seed, state: ChaCha8State) -> bool {
+            let mut rng = ChaCha8Rand::new(&seed.0);
+            rng.try_restore_state(&state).is_ok() && rng.position() == state.position()
+        }
+        QuickCheck::new().quickcheck(prop as fn(Seed, ChaCha8State) -> bool);
+    }
+
+    #[test]
+    fn shrunk_states_are_valid_and_smaller() {
+        let mut g = Gen::new(100);
+        for _ in 0..100 {
+            let state = ChaCha8State::arbitrary(&mut g);
+            for shrunk in state.shrink() {
+                assert!(usize::from(shrunk.bytes_consumed) <= BUF_OUTPUT_LEN);
+                let changed = [
+                    shrunk.seed != state.seed,
+                    shrunk.iteration != state.iteration,
+                    shrunk.bytes_consumed != state.bytes_consumed,
+                ];
+                assert_eq!(changed.iter().filter(|&&c| c).count(), 1);
+                assert!(shrunk.seed == state.seed || shrunk.seed == [0; 32]);
+                assert!(shrunk.iteration <= state.iteration);
+                assert!(shrunk.bytes_consumed <= state.bytes_consumed);
+            }
+        }
+        assert_eq!(Seed([0; 32]).shrink().count(), 0);
+    }
+}
+
+#[cfg(feature = "rand_core_0_5")]
+mod rand05 {
+    use core::iter;
+
+    use rand_core_0_5::{RngCore, SeedableRng};
+
+    use crate::ChaCha8Rand;
+
+    use super::{check_byte_output, SAMPLE_OUTPUT_U64LE, SAMPLE_SEED};
+
+    #[test]
+    fn next_u32() {
+        let mut rng = ChaCha8Rand::from_seed(*SAMPLE_SEED);
+        let u32s = iter::repeat_with(|| RngCore::next_u32(&mut rng));
+        check_byte_output(u32s.flat_map(u32::to_le_bytes));
+    }
+
+    #[test]
+    fn fill_bytes() {
+        let mut rng = ChaCha8Rand::from_seed(*SAMPLE_SEED);
+        let mut bytes = [0; SAMPLE_OUTPUT_U64LE.len() * 8];
+        rng.try_fill_bytes(&mut bytes).unwrap();
+        check_byte_output(bytes.iter().copied());
+    }
+
+    #[cfg(feature = "rand_core_0_6")]
+    #[test]
+    fn seed_from_u64_matches_rand_core_0_6() {
+        let mut old = <ChaCha8Rand as SeedableRng>::seed_from_u64(12345);
+        let mut new = <ChaCha8Rand as rand_core::SeedableRng>::seed_from_u64(12345);
+        assert_eq!(old.read_u64(), new.read_u64());
+    }
+}
+
+#[cfg(feature = "rand_core_0_6")]
+mod rand06 {
+    use core::iter;
+
+    use rand_core::{RngCore, SeedableRng};
+
+    use crate::ChaCha8Rand;
+
+    use super::{check_byte_output, SAMPLE_OUTPUT_U64LE, SAMPLE_SEED};
+
+    #[test]
+    fn next_u32() {
+        let mut rng = ChaCha8Rand::from_seed(*SAMPLE_SEED);
+        let u32s = iter::repeat_with(|| RngCore::next_u32(&mut rng));
+        check_byte_output(u32s.flat_map(u32::to_le_bytes));
+    }
+
+    #[test]
+    fn next_u64() {
+        let mut rng = ChaCha8Rand::from_seed(*SAMPLE_SEED);
+        let u64s = iter::repeat_with(|| RngCore::next_u64(&mut rng));
+        check_byte_output(u64s.flat_map(u64::to_le_bytes));
+    }
+
+    #[test]
+    fn fill_bytes() {
+        let mut rng = ChaCha8Rand::from_seed(*SAMPLE_SEED);
+        let mut bytes = [0; SAMPLE_OUTPUT_U64LE.len() * 8];
+        rng.fill_bytes(&mut bytes);
+        check_byte_output(bytes.iter().copied());
+    }
+}
+
+fn expected_bytes() -> impl Iterator<Item = u8> {
+    SAMPLE_OUTPUT_U64LE.iter().flat_map(|n| n.to_le_bytes())
+}
+
+fn check_byte_output(output: impl IntoIterator<Item = u8>) {
+    let mut i = 0;
+    for (output_byte, expected_byte) in iter::zip(output, expected_bytes()) {
+        assert_eq!(output_byte, expected_byte, "byte {i} differs");
+        i += 1;
+    }
+    assert_eq!(i, SAMPLE_OUTPUT_U64LE.len() * 8, "output ended prematurely");
+}
+
+pub const SAMPLE_SEED: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456";
+
+#[rustfmt::skip]
+const SAMPLE_OUTPUT_U64LE: &[u64] = &[
+    0xb773b6063d4616a5, 0x1160af22a66abc3c, 0x8c2599d9418d287c, 0x7ee07e037edc5cd6,
+    0xcfaa9ee02d1c16ad, 0x0e090eef8febea79, 0x3c82d271128b5b3e, 0x9c5addc11252a34f,
+    0xdf79bb617d6ceea6, 0x36d553591f9d736a, 0xeef0d14e181ee01f, 0x089bfc760ae58436,
+    0xd9e52b59cc2ad268, 0xeb2fb4444b1b8aba, 0x4f95c8a692c46661, 0xc3c6323217cae62c,
+    0x91ebb4367f4e2e7e, 0x784cf2c6a0ec9bc6, 0x5c34ec5c34eabe20, 0x4f0a8f515570daa8,
+    0xfc35dcb4113d6bf2, 0x5b0da44c645554bc, 0x6d963da3db21d9e1, 0xeeaefc3150e500f3,
+    0x2d37923dda3750a5, 0x380d7a626d4bc8b0, 0xeeaf68ede3d7ee49, 0xf4356695883b717c,
+    0x846a9021392495a4, 0x8e8510549630a61b, 0x18dc02545dbae493, 0x0f8f9ff0a65a3d43,
+    0xccf065f7190ff080, 0xfd76d1aa39673330, 0x95d232936cba6433, 0x6c7456d1070cbd17,
+    0x462acfdaff8c6562, 0x5bafab866d34fc6a, 0x0c862f78030a2988, 0xd39a83e407c3163d,
+    0xc00a2b7b45f22ebf, 0x564307c62466b1a9, 0x257e0424b0c072d4, 0x6fb55e99496c28fe,
+    0xae9873a88f5cd4e0, 0x4657362ac60d3773, 0x1c83f91ecdf23e8e, 0x6fdc0792c15387c0,
+    0x36dad2a30dfd2b5c, 0xa4b593290595bdb7, 0x4de18934e4cc02c5, 0xcdc0d604f015e3a7,
+    0xfba0dbf69ad80321, 0x60e8bea3d139de87, 0xd18a4d851ef48756, 0x6366447c2215f34a,
+    0x05682e97d3d007ee, 0x4c0e8978c6d54ab2, 0xcf1e9f6a6712edc2, 0x061439414c80cfd3,
+    0xd1a8b6e2745c0ead, 0x31a7918d45c410e8, 0xabcc61ad90216eec, 0x4040d92d2032a71a,
+    0x3cd2f66ffb40cd68, 0xdcd051c07295857a, 0xeab55cbcd9ab527e, 0x18471dce781bdaac,
+    0xf7f08cd144dc7252, 0x5804e0b13d7f40d1, 0x5cb1a446e4b2d35b, 0xe6d4a728d2138a06,
+    0x05223e40ca60dad8, 0x2d61ec3206ac6a68, 0xab692356874c17b8, 0xc30954417676de1c,
+    0x4f1ace3732225624, 0xfba9510813988338, 0x997f200f52752e11, 0x1116aaafe86221fa,
+    0x07ce3b5cb2a13519, 0x2956bc72bc458314, 0x4188b7926140eb78, 0x56ca6dbfd4adea4d,
+    0x7fe3c22349340ce5, 0x35c08f9c37675f8a, 0x11e1c7fbef5ed521, 0x98adc8464ec1bc75,
+    0xd163b2c73d1203f8, 0x8c761ee043a2f3f3, 0x24b99d6accecd7b7, 0x793e31aa112f0370,
+    0x8e87dc2a19285139, 0x4247ae04f7096e25, 0x514f3122926fe20f, 0xdc6fb3f045d2a7e9,
+    0x15cb30cecdd18eba, 0xcbc7fdecf6900274, 0x3fb5c696dc8ba021, 0xd1664417c8d274e6,
+    0x05f7e445ea457278, 0xf920bbca1b9db657, 0x0c1950b4da22cb99, 0xf875baf1af09e292,
+    0xbed3d7b84250f838, 0xf198e8080fd74160, 0xc9eda51d9b7ea703, 0xf709ef55439bf8f6,
+    0xd20c74feebf116fc, 0x305668eb146d7546, 0x829af3ec10d89787, 0x15b8f9697b551dbc,
+    0xfc823c6c8e64b8c9, 0x345585e8183b40bc, 0x674b4171d6581368, 0x1234d81cd670e9f7,
+    0x0e505210d8a55e19, 0xe8258d69eeeca0dc, 0x05d4c452e8baf67e, 0xe8dbe30116a45599,
+    0x1cf08ce1b1176f00, 0xccf7d0a4b81ecb49, 0x303fea136b2c430e, 0x861d6c139c06c871,
+    0x5f41df72e05e0487, 0x25bd7e1e1ae26b1d, 0xbe9f4004d662a41d, 0x65bf58d483188546,
+    0xd1b27cff69db13cc, 0x01a6663372c1bb36, 0x578dd7577b727f4d, 0x19c78f066c083cf6,
+    0xdbe014d4f9c391bb, 0x97fbb2dd1d13ffb3, 0x31c91e0af9ef8d4f, 0x094dfc98402a43ba,
+    0x069bd61bea37b752, 0x5b72d762e8d986ca, 0x72ee31865904bc85, 0xd1f5fdc5cd36c33e,
+    0xba9b4980a8947cad, 0xece8f05eac49ab43, 0x65fe1184abae38e7, 0x2d7cb9dea5d31452,
+    0xcc71489476e467e3, 0x4c03a258a578c68c, 0x00efdf9ecb0fd8fc, 0x9924cad471e2666d,
+    0x87f8668318f765e9, 0xcb4dc57c1b55f5d8, 0xd373835a86604859, 0xe526568b5540e482,
+    0x1f39040f08586fec, 0xb764f3f00293f8e6, 0x049443a2f6bd50a8, 0x76fec88697d3941a,
+    0x3efb70d039bae7a2, 0xe2f4611368eca8a8, 0x7c007a96e01d2425, 0xbbcce5768e69c5bf,
+    0x784fb4985c42aac3, 0xf72b5091aa223874, 0x3630333fb1e62e07, 0x8e7319ebdebbb8de,
+    0x2a3982bca959fa00, 0xb2b98b9f964ba9b3, 0xf7e31014adb71951, 0xebd0fca3703acc82,
+    0xec654e2a2fe6419a, 0xb326132d55a52e2c, 0x2248c57f44502978, 0x32710c2f342daf16,
+    0x0517b47b5acb2bec, 0x4c7a718fca270937, 0xd69142bed0bcc541, 0xe40ebcb8ff52ce88,
+    0x3e44a2dbc9f828d4, 0xc74c2f4f8f873f58, 0x3dbf648eb799e45b, 0x33f22475ee0e86f8,
+    0x1eb4f9ee16d47f65, 0x40f8d2b8712744e3, 0xb886b4da3cb14572, 0x2086326fbdd6f64d,
+    0xcc3de5907dd882b9, 0xa2e8b49a5ee909df, 0xdbfb8e7823964c10, 0x70dd6089ef0df8d5,
+    0x30141663cdd9c99f, 0x04b805325c240365, 0x7483d80314ac12d6, 0x2b271cb91aa7f5f9,
+    0x97e2245362abddf0, 0x5a84f614232a9fab, 0xf71125fcda4b7fa2, 0x1ca5a61d74b27267,
+    0x38cc6a9b3adbcb45, 0xdde1bb85dc653e39, 0xe9d0c8fa64f89fd4, 0x02c5fb1ecd2b4188,
+    0xf2bd137bca5756e5, 0xadefe25d121be155, 0x56cd1c3c5d893a8e, 0x4c50d337beb65bb9,
+    0x918c5151675cf567, 0xaba649ffcfb56a1e, 0x20c74ab26a2247cd, 0x71166bac853c08da,
+    0xb07befe2e584fc5d, 0xda45ff2a588dbf32, 0xdb98b03c4d75095e, 0x60285ae1aaa65a4c,
+    0xf93b686a263140b8, 0xde469752ee1c180e, 0xcec232dc04129aae, 0xeb916baa1835ea04,
+    0xd49c21c8b64388ff, 0x72a82d9658864888, 0x003348ef7eac66a8, 0x7f6f67e655b209eb,
+    0x532ffb0b7a941b25, 0xd940ade6128deede, 0xdf24f2a1af89fe23, 0x95aa3b4988195ae0,
+    0x3da649404f94be4a, 0x692dad132c3f7e27, 0x40aee76ecaaa9eb8, 0x1294a01e09655024,
+    0x6df797abdba4e4f5, 0xea2fb6024c1d7032, 0x5f4e0492295489fc, 0x57972914ea22e06a,
+    0x9a8137d133aad473, 0xa2e6dd6ae7cdf2f3, 0x9f42644f18086647, 0x16d03301c170bd3e,
+    0x908c416fa546656d, 0xe081503be22e123e, 0x077cf09116c4cc72, 0xcbd25cd264b7f229,
+    0x3db2f468ec594031, 0x46c00e734c9badd5, 0xd0ec0ac72075d861, 0x3037cb3cf80b7630,
+    0x574c3d7b3a2721c6, 0xae99906a0076824b, 0xb175a5418b532e70, 0xd8b3e251ee231ddd,
+    0xb433eec25dca1966, 0x530f30dc5cff9a93, 0x9ff03d98b53cd335, 0xafc4225076558cdf,
+    0xef81d3a28284402a, 0x110bdbf51c110a28, 0x9ae1b255d027e8f6, 0x7de3e0aa24688332,
+    0xe483c3ecd2067ee2, 0xf829328b276137e6, 0xa413ccad57562cad, 0xe6118e8b496acb1f,
+    0x8288dca6da5ec01f, 0xa53777dc88c17255, 0x8a00f1e0d5716eda, 0x618e6f47b7a720a8,
+    0x9e3907b0c692a841, 0x978b42ca963f34f3, 0x75e4b0cd98a7d7ef, 0xde4dbd6e0b5f4752,
+    0x0252e4153f34493f, 0x50f0e7d803734ef9, 0x237766a38ed167ee, 0x4124414001ee39a0,
+    0xd08df643e535bb21, 0x34f575b5a9a80b74, 0x2c343af87297f755, 0xcd8b6d99d821f7cb,
+    0xe376fd7256fc48ae, 0xe1b06e7334352885, 0xfa87b26f86c169eb, 0x36c1604665a971de,
+    0xdba147c2239c8e80, 0x6b208e69fc7f0e24, 0x8795395b6f2b60c3, 0x05dabee9194907f4,
+    0xb98175142f5ed902, 0x5e1701e2021ddc81, 0x0875aba2755eed08, 0x778d83289251de95,
+    0x3bfbe46a039ecb31, 0xb24704fce4cbd7f9, 0x6985ffe9a7c91e3d, 0xc8efb13df249dabb,
+    0xb1037e64b0f4c9f6, 0x55f69fd197d6b7c3, 0x672589d71d68a90c, 0xbebdb8224f50a77e,
+    0x3f589f80007374a7, 0xd307f4635954182a, 0xcff5850c10d4fd90, 0xc6da02dfb6408e15,
+    0x93daeef1e2b1a485, 0x65d833208aeea625, 0xe2b13fa13ed3b5fa, 0x67053538130fb68e,
+    0xc1042f6598218fa9, 0xee5badca749b8a2e, 0x6d22a3f947dae37d, 0xb62c6d1657f4dbaf,
+    0x6e007de69704c20b, 0x1af2b913fc3841d8, 0xdc0e47348e2e8e22, 0x9b1ddef1cf958b22,
+    0x632ed6b0233066b8, 0xddd02d3311bed8f2, 0xf147cfe1834656e9, 0x399aaa49d511597a,
+    0x6b14886979ec0309, 0x64fc4ac36b5afb97, 0xb82f78e07f7cf081, 0x10925c9a323d0e1b,
+    0xf451c79ee13c63f6, 0x7c2fc180317876c7, 0x35a12bd9eecb7d22, 0x335654a539621f90,
+    0xcc32a3f35db581f0, 0xc60748a80b2369cb, 0x7c4dd3b08591156b, 0xac1ced4b6de22291,
+    0xa32cfa2df134def5, 0x627108918dea2a53, 0x0555b1608fcb4ff4, 0x143ee7ac43aaa33c,
+    0xdae90ce7cf4fc218, 0x4d68fc2582bcf4b5, 0x37094e1849135d71, 0xf7857e09f3d49fd8,
+    0x007538c503768be7, 0xedf648ba2f6be601, 0xaa347664dd72513e, 0xbe63893c6ef23b86,
+    0x130b85710605af97, 0xdd765c6b1ef6ab56, 0xf3249a629a97dc6b, 0x2a114f9020fab8e5,
+    0x5a69e027cfc6ad08, 0x3c4ccb36f1a5e050, 0x2e9e7d596834f0a5, 0x2430be6858fce789,
+    0xe90b862f2466e597, 0x895e2884f159a9ec, 0x26ab8fa4902fcb57, 0xa6efff5c54e1fa50,
+    0x333ac4e5811a8255, 0xa58d515f02498611, 0xfe5a09dcb25c6ef4, 0x03898988ab5f5818,
+    0x289ff6242af6c617, 0x3d9dd59fd381ea23, 0x52d7d93d8a8aae51, 0xc76a123d511f786f,
+    0xf68901edaf00c46c, 0x8c630871b590de80, 0x05209c308991e091, 0x1f809f99b4788177,
+    0x11170c2eb6c19fd8, 0x44433c779062ba58, 0xc0acb51af1874c45, 0x9f2e134284809fa1,
+    0xedb523bd15c619fa, 0x02d97fd53ecc23c0, 0xacaf05a34462374c, 0xddd9c6d34bffa11f
+];
+
+#[cfg(all(feature = "distributions", feature = "alloc"))]
+mod histogram {
+    use crate::{distributions::Histogram, ChaCha8Rand};
+
+    use super::SAMPLE_SEED;
+
+    #[test]
+    fn histogram_frequencies() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let histogram = Histogram::new(&[0, 5, 0, 1, 4, 0]).unwrap();
+        assert_eq!((histogram.len(), histogram.total()), (6, 10));
+        let mut counts = [0u32; 6];
+        for _ in 0..10_000 {
+            counts[histogram.sample(&mut rng)] += 1;
+        }
+        assert_eq!([counts[0], counts[2], counts[5]], [0, 0, 0]);
+        for (count, expected) in [(counts[1], 5000), (counts[3], 1000), (counts[4], 4000)] {
+            assert!(count.abs_diff(expected) < 200, "{counts:?}");
+        }
+    }
+
+    #[test]
+    fn histogram_rejects_empty_and_overflow() {
+        assert_eq!(Histogram::new(&[]), None);
+        assert_eq!(Histogram::new(&[0, 0]), None);
+        assert_eq!(Histogram::new(&[u64::MAX, 1]), None);
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let histogram = Histogram::new(&[u64::MAX - 1, 0, 1]).unwrap();
+        assert_eq!(histogram.sample(&mut rng), 0);
+    }
+}
+
+#[cfg(feature = "zerocopy")]
+mod zerocopy {
+    use zerocopy::{FromBytes, FromZeros, Immutable, IntoBytes, KnownLayout};
+
+    use crate::ChaCha8Rand;
+
+    use super::{check_byte_output, SAMPLE_SEED};
+
+    #[derive(FromBytes, IntoBytes, KnownLayout, Immutable, Clone, Copy)]
+    #[repr(C)]
+    struct Record {
+        tag: [u8; 4],
+        value: u32,
+        extra: [u8; 3],
+        // Explicit instead of implicit padding, so we can look at the bytes.
+        _pad: u8,
+    }
+
+    #[test]
+    fn read_and_fill_from_bytes() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let first: Record = rng.read_from_bytes();
+        let mut rest = [Record::new_zeroed(); 300];
+        rng.fill_from_bytes(&mut rest[..1]);
+        rng.fill_from_bytes(&mut rest[1..]);
+        let bytes = first.as_bytes().iter().chain(rest.as_bytes());
+        check_byte_output(bytes.copied());
+    }
+}
+
+#[cfg(feature = "std")]
+mod io {
+    use std::io::{IoSliceMut, Read, Seek, SeekFrom};
+    use std::vec::Vec;
+
+    use crate::ChaCha8Rand;
+
+    use super::{check_byte_output, SAMPLE_OUTPUT_U64LE, SAMPLE_SEED};
+
+    #[test]
+    fn read_impl() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut output = [0; 3000];
+        let (head, tail) = output.split_at_mut(7);
+        assert_eq!(rng.read(head).unwrap(), 7);
+        let (mid, tail) = tail.split_at_mut(1000);
+        let mut bufs = [IoSliceMut::new(mid), IoSliceMut::new(&mut [])];
+        assert_eq!(rng.read_vectored(&mut bufs).unwrap(), 1000);
+        rng.read_exact(tail).unwrap();
+        check_byte_output(output.iter().copied());
+    }
+
+    #[test]
+    fn seek_impl() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        assert_eq!(rng.seek(SeekFrom::Start(800)).unwrap(), 800);
+        assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[100]);
+        assert_eq!(rng.seek(SeekFrom::Current(-808)).unwrap(), 0);
+        assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[0]);
+        assert_eq!(rng.seek(SeekFrom::Current(1592)).unwrap(), 1600);
+        assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[200]);
+        assert!(rng.seek(SeekFrom::Current(-2000)).is_err());
+        assert!(rng.seek(SeekFrom::End(0)).is_err());
+        assert_eq!(rng.stream_position().unwrap(), 1608);
+    }
+
+    #[test]
+    fn write_to() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        let mut output = Vec::new();
+        rng.write_to(&mut output, 5).unwrap();
+        rng.write_to(&mut output, 0).unwrap();
+        rng.write_to(&mut output, 2995).unwrap();
+        assert_eq!(output.len(), 3000);
+        check_byte_output(output.iter().copied());
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Compact, Configure, Readable, Token};
+
+    use crate::{ChaCha8Rand, ChaCha8State};
+
+    use super::{SAMPLE_OUTPUT_U64LE, SAMPLE_SEED};
+
+    const SAMPLE_SEED_HEX: &str =
+        "4142434445464748494a4b4c4d4e4f505152535455565758595a313233343536";
+
+    fn sample_state() -> ChaCha8State {
+        ChaCha8State {
+            seed: *SAMPLE_SEED,
+            bytes_consumed: 8,
+            iteration: 1,
+        }
+    }
+
+    #[test]
+    fn state_readable() {
+        assert_tokens(
+            &sample_state().readable(),
+            &[
+                Token::Struct {
+                    name: "ChaCha8State",
+                    len: 3,
+                },
+                Token::Str("seed"),
+                Token::Str(SAMPLE_SEED_HEX),
+                Token::Str("bytes_consumed"),
+                Token::U16(8),
+                Token::Str("iteration"),
+                Token::U64(1),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn state_compact() {
+        assert_tokens(
+            &sample_state().compact(),
+            &[
+                Token::Struct {
+                    name: "ChaCha8State",
+                    len: 3,
+                },
+                Token::Str("seed"),
+                Token::Bytes(SAMPLE_SEED),
+                Token::Str("bytes_consumed"),
+                Token::U16(8),
+                Token::Str("iteration"),
+                Token::U64(1),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn state_rejects_excessive_count() {
+        assert_de_tokens_error::<Compact<ChaCha8State>>(
+            &[
+                Token::Seq { len: Some(3) },
+                Token::Bytes(&[0; 32]),
+                Token::U16(993),
+            ],
+            "invalid value: integer `993`, expected at most 992",
+        );
+    }
+
+    #[test]
+    fn rng_roundtrip() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        rng.read_bytes(&mut [0; 1000]);
+        let json = serde_json::to_string(&rng).unwrap();
+        assert_eq!(json, serde_json::to_string(&rng.clone_state()).unwrap());
+        let mut loaded: ChaCha8Rand = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.position(), 1000);
+        assert_eq!(loaded.read_u64(), SAMPLE_OUTPUT_U64LE[125]);
+    }
+
+    #[test]
+    fn state_rejects_bad_seed() {
+        assert_de_tokens_error::<Readable<ChaCha8State>>(
+            &[Token::Seq { len: Some(3) }, Token::Str("0123")],
+            "invalid value: string \"0123\", expected a 32-byte seed as 64 hex digits or as raw bytes",
+        );
+    }
+
+    #[test]
+    fn generator_with_stream_id_is_not_serialized() {
+        serde_test::assert_ser_tokens_error(
+            &ChaCha8Rand::with_stream(SAMPLE_SEED, 1),
+            &[],
+            "can't serialize a generator with a non-zero stream ID",
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod go {
+    use alloc::vec::Vec;
+
+    use crate::{ChaCha8Rand, ChaCha8State};
+
+    use super::{SAMPLE_OUTPUT_U64LE, SAMPLE_SEED};
+
+    fn go_state(words_used: u64, seed: &[u8; 32]) -> Vec<u8> {
+        let mut data = Vec::from(*b"chacha8:");
+        data.extend_from_slice(&words_used.to_be_bytes());
+        data.extend_from_slice(seed);
+        data
+    }
+
+    #[test]
+    fn word_aligned_roundtrip() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        rng.read_u64();
+        let data = rng.clone_state().to_go_binary();
+        assert_eq!(data, go_state(1, SAMPLE_SEED));
+
+        let state = ChaCha8State::from_go_binary(&data).unwrap();
+        rng.try_restore_state(&state).unwrap();
+        assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[1]);
+    }
+
+    #[test]
+    fn read_buffer_roundtrip() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        rng.read_bytes(&mut [0; 11]);
+        let data = rng.clone_state().to_go_binary();
+        // Go's `Read` consumed two words and buffered the last five bytes of the second one.
+        let mut expected = Vec::from(*b"readbuf:\x05");
+        expected.extend_from_slice(&SAMPLE_OUTPUT_U64LE[1].to_le_bytes()[3..]);
+        expected.extend_from_slice(&go_state(2, SAMPLE_SEED));
+        assert_eq!(data, expected);
+
+        let state = ChaCha8State::from_go_binary(&data).unwrap();
+        assert_eq!(state.bytes_consumed, 11);
+        rng.try_restore_state(&state).unwrap();
+        assert_eq!(
+            rng.read_u64(),
+            SAMPLE_OUTPUT_U64LE[1] >> 24 | SAMPLE_OUTPUT_U64LE[2] << 40
+        );
+    }
+
+    #[test]
+    fn end_of_iteration() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        rng.read_bytes(&mut [0; 992]);
+        let state = ChaCha8State::from_go_binary(&rng.clone_state().to_go_binary()).unwrap();
+        assert_eq!(state.bytes_consumed, 992);
+        rng.try_restore_state(&state).unwrap();
+        assert_eq!(rng.read_u64(), SAMPLE_OUTPUT_U64LE[124]);
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        let mut tampered = Vec::from(*b"readbuf:\x01\x00");
+        tampered.extend_from_slice(&go_state(1, SAMPLE_SEED));
+        let mut too_short = Vec::from(*b"readbuf:\x01\xb7");
+        too_short.extend_from_slice(&go_state(0, SAMPLE_SEED));
+        for bad in [
+            go_state(125, SAMPLE_SEED),
+            go_state(u64::MAX, SAMPLE_SEED),
+            go_state(0, SAMPLE_SEED)[1..].to_vec(),
+            Vec::from(*b"readbuf:"),
+            tampered,
+            too_short,
+        ] {
+            assert!(ChaCha8State::from_go_binary(&bad).is_err(), "{bad:?}");
+        }
+        assert!(ChaCha8State::from_go_binary(&go_state(124, SAMPLE_SEED)).is_ok());
+    }
+}
+
+#[cfg(feature = "rkyv")]
+mod rkyv {
+    use rkyv::rancor::Error;
+
+    use crate::{ChaCha8Rand, ChaCha8State};
+
+    use super::{SAMPLE_OUTPUT_U64LE, SAMPLE_SEED};
+
+    #[test]
+    fn state_roundtrip() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        rng.read_bytes(&mut [0; 1000]);
+        let bytes = rkyv::to_bytes::<Error>(&rng.clone_state()).unwrap();
+
+        let archived = rkyv::access::<rkyv::Archived<ChaCha8State>, Error>(&bytes).unwrap();
+        assert_eq!(archived.bytes_consumed, 8);
+        assert_eq!(archived.iteration, 1);
+        let state: ChaCha8State = rkyv::deserialize::<_, Error>(archived).unwrap();
+
+        let mut restored = ChaCha8Rand::new(&[0; 32]);
+        restored.try_restore_state(&state).unwrap();
+        assert_eq!(restored.read_u64(), SAMPLE_OUTPUT_U64LE[125]);
+    }
+}
+
+#[cfg(feature = "borsh")]
+mod borsh {
+    use crate::{ChaCha8Rand, ChaCha8State};
+
+    use super::SAMPLE_SEED;
+
+    #[test]
+    fn state_roundtrip() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        rng.read_bytes(&mut [0; 1000]);
+        let state = rng.clone_state();
+        let bytes = borsh::to_vec(&state).unwrap();
+        assert_eq!(bytes, state.to_bytes());
+        let decoded: ChaCha8State = borsh::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, state);
+    }
+
+    #[test]
+    fn state_rejects_excessive_count() {
+        let mut bytes = ChaCha8Rand::new(SAMPLE_SEED).clone_state().to_bytes();
+        bytes[32..34].copy_from_slice(&993u16.to_le_bytes());
+        assert!(borsh::from_slice::<ChaCha8State>(&bytes).is_err());
+        assert!(borsh::from_slice::<ChaCha8State>(&bytes[..41]).is_err());
+    }
+}
+
+#[cfg(feature = "sha2")]
+mod sha2 {
+    use crate::{ChaCha8Rand, Seed};
+
+    #[test]
+    fn derive_from_str_is_sha256() {
+        // Test vector from FIPS 180-2.
+        let Seed(seed) = Seed::derive_from_str("abc");
+        assert_eq!(
+            Seed(seed),
+            Seed::from_hex(b"ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+                .unwrap()
+        );
+        let mut expected = ChaCha8Rand::new(&seed);
+        let mut rng = ChaCha8Rand::new_from_str("abc");
+        assert_eq!(rng.read_u64(), expected.read_u64());
+    }
+}
+
+#[cfg(any(feature = "getrandom_0_2", feature = "getrandom_0_3"))]
+mod entropy {
+    use crate::ChaCha8Rand;
+
+    #[test]
+    fn from_entropy_returns_its_seed() {
+        let (mut rng, seed) = ChaCha8Rand::from_entropy().unwrap();
+        let (_, other_seed) = ChaCha8Rand::from_entropy().unwrap();
+        assert_ne!(seed, other_seed);
+        let mut replay = ChaCha8Rand::new(&seed);
+        let mut expected = [0; 1000];
+        replay.read_bytes(&mut expected);
+        let mut actual = [0; 1000];
+        rng.read_bytes(&mut actual);
+        assert_eq!(actual, expected);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+mod zeroize {
+    use zeroize::Zeroize;
+
+    use crate::{ChaCha8Rand, ChaCha8State, Seed};
+
+    use super::SAMPLE_SEED;
+
+    #[test]
+    fn zeroized_rng_is_reseeded_with_zeros() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        rng.read_bytes(&mut [0; 1500]);
+        rng.zeroize();
+        assert_eq!(rng.initial_seed(), [0; 32]);
+        assert_eq!(rng.position(), 0);
+        let mut expected = ChaCha8Rand::new(&[0; 32]);
+        assert!(rng == expected);
+        assert_eq!(rng.read_u64(), expected.read_u64());
+    }
+
+    #[test]
+    fn zeroize_state_and_seed() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        rng.read_bytes(&mut [0; 1500]);
+        let mut state = rng.clone_state();
+        state.zeroize();
+        assert_eq!(
+            state,
+            ChaCha8State {
+                seed: [0; 32],
+                bytes_consumed: 0,
+                iteration: 0,
+            }
+        );
+        let mut seed = Seed(*SAMPLE_SEED);
+        seed.zeroize();
+        assert_eq!(seed, Seed([0; 32]));
+    }
+}
+
+#[cfg(feature = "tracing")]
+mod tracing {
+    use core::fmt;
+
+    use super::std::{
+        format,
+        string::{String, ToString},
+        sync::{Arc, Mutex},
+        vec::Vec,
+    };
+
+    use tracing::{
+        field::{Field, Visit},
+        span, Event, Metadata, Subscriber,
+    };
+
+    use crate::ChaCha8Rand;
+
+    use super::SAMPLE_SEED;
+
+    type Events = Vec<(String, Option<u64>)>;
+
+    /// Records the message and `iteration` field of every event.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Events>>);
+
+    struct EventVisitor(String, Option<u64>);
+
+    impl Visit for EventVisitor {
+        fn record_u64(&mut self, field: &Field, value: u64) {
+            if field.name() == "iteration" {
+                self.1 = Some(value);
+            }
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            if field.name() == "message" {
+                self.0 = format!("{value:?}");
+            }
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+            span::Id::from_u64(1)
+        }
+
+        fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut visitor = EventVisitor(String::new(), None);
+            event.record(&mut visitor);
+            self.0.lock().unwrap().push((visitor.0, visitor.1));
+        }
+
+        fn enter(&self, _: &span::Id) {}
+
+        fn exit(&self, _: &span::Id) {}
+    }
+
+    #[test]
+    fn events_for_reseeding_refills_and_restores() {
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+            rng.read_bytes(&mut [0; 1000]);
+            let state = rng.clone_state();
+            rng.set_seed(SAMPLE_SEED);
+            rng.skip_bytes(2000);
+            rng.try_restore_state(&state).unwrap();
+        });
+        let events = recorder.0.lock().unwrap().clone();
+        let expected = [
+            ("refill", Some(1)),
+            ("set_seed", None),
+            ("refill", Some(2)),
+            ("restore_state", Some(1)),
+        ];
+        let expected: Events = expected
+            .iter()
+            .map(|&(msg, iteration)| (msg.to_string(), iteration))
+            .collect();
+        assert_eq!(events, expected);
+    }
+}
+
+#[cfg(feature = "audit")]
+mod audit {
+    use alloc::vec::Vec;
+
+    use super::SAMPLE_SEED;
+    use crate::{AuditEntry, ChaCha8Rand};
+
+    #[test]
+    fn records_caller_locations_positions_and_lengths() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        rng.read_u32();
+        rng.enable_audit_log(4);
+        let first_line = line!() + 1;
+        rng.read_bytes(&mut [0; 980]);
+        rng.read_u64(); // crosses into the next iteration
+        rng.seek_to(5000).unwrap();
+        rng.skip_bytes(3);
+        rng.read_vec(7);
+        let log: Vec<AuditEntry> = rng.audit_log().collect();
+        let summary: Vec<_> = log
+            .iter()
+            .map(|e| (e.location.line() - first_line, e.position, e.len))
+            .collect();
+        assert_eq!(
+            summary,
+            [(0, 4, 980), (1, 984, 8), (3, 5000, 3), (4, 5003, 7)]
+        );
+        assert!(log.iter().all(|e| e.location.file() == file!()));
+
+        rng.read_array::<2>();
+        let lens: Vec<u64> = rng.audit_log().map(|e| e.len).collect();
+        assert_eq!(lens, [8, 3, 7, 2]);
+
+        rng.enable_audit_log(0);
+        rng.read_u128();
+        assert_eq!(rng.audit_log().len(), 0);
+    }
+
+    #[test]
+    fn bulk_float_fills_are_one_entry_at_the_caller() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        rng.read_u32();
+        rng.enable_audit_log(4);
+        // 150 `f64`s starting at byte 4 cross into the next iteration, including one `f64` that
+        // straddles the refill.
+        let line = line!() + 1;
+        rng.fill_f64_unit(&mut [0.0; 150]);
+        let log: Vec<AuditEntry> = rng.audit_log().collect();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].location.file(), file!());
+        assert_eq!(log[0].location.line(), line);
+        assert_eq!((log[0].position, log[0].len), (4, 1200));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn write_to_is_one_entry_at_the_caller() {
+        let mut rng = ChaCha8Rand::new(SAMPLE_SEED);
+        rng.enable_audit_log(4);
+        let line = line!() + 1;
+        rng.write_to(&mut Vec::new(), 3000).unwrap();
+        let log: Vec<AuditEntry> = rng.audit_log().collect();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].location.line(), line);
+        assert_eq!((log[0].position, log[0].len), (0, 3000));
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod seed_sequence {
+    use alloc::vec::Vec;
+
+    use super::SAMPLE_SEED;
+    use crate::{ChaCha8Rand, SeedSequence};
+
+    #[test]
+    fn keys_come_from_disjoint_parts_of_the_parent_stream() {
+        let root = SeedSequence::new(SAMPLE_SEED);
+        let mut stream = [[0; 32]; 4];
+        ChaCha8Rand::new(SAMPLE_SEED).read_bytes(stream.as_flattened_mut());
+        assert_eq!(root.seed(), stream[0]);
+        assert_eq!(root.child(0).seed(), SeedSequence::new(&stream[1]).seed());
+        assert_eq!(root.child(2).seed(), SeedSequence::new(&stream[3]).seed());
+
+        let grandchild = root.child(2).child(5);
+        assert_eq!(grandchild.spawn_key(), [2, 5]);
+        assert_eq!(
+            grandchild.seed(),
+            SeedSequence::new(&stream[3]).child(5).seed()
+        );
+        assert_eq!(
+            grandchild,
+            SeedSequence::with_spawn_key(SAMPLE_SEED, &[2, 5])
+        );
+    }
+
+    #[test]
+    fn spawn_and_children_agree_with_child() {
+        let mut root = SeedSequence::new(SAMPLE_SEED);
+        let spawned: Vec<_> = (0..40).map(|_| root.spawn()).collect();
+        assert_eq!(root.children_spawned(), 40);
+        let children: Vec<_> = root.children().take(40).collect();
+        assert_eq!(spawned, children);
+        for (i, child) in children.iter().enumerate() {
+            assert_eq!(*child, root.child(i as u64));
+        }
+        assert_eq!(root.spawn(), root.child(40));
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod rng_set {
+    use alloc::string::String;
+
+    use super::SAMPLE_SEED;
+    use crate::{ChaCha8Rand, RngSet};
+
+    #[test]
+    fn members_only_depend_on_root_seed_and_name() {
+        let mut a = RngSet::new(SAMPLE_SEED);
+        let mut b = RngSet::new(SAMPLE_SEED);
+        a.get("map").read_u64();
+        let x = a.get("ai").read_u64();
+        assert_eq!(b.get("ai").read_u64(), x);
+        assert_ne!(b.get("map").read_u64(), x);
+        assert!(a.names().eq(["ai", "map"]));
+
+        let mut derive = ChaCha8Rand::new(SAMPLE_SEED);
+        derive.mix_in(b"ai");
+        assert_eq!(ChaCha8Rand::new(&derive.initial_seed()).read_u64(), x);
+    }
+
+    #[test]
+    fn restore_is_all_or_nothing() {
+        let mut rngs = RngSet::new(SAMPLE_SEED);
+        rngs.get("a").read_u32();
+        let save = rngs.snapshot();
+        let a = rngs.get("a").read_u32();
+        let b = rngs.get("b").read_u32();
+
+        let mut bad = save.clone();
+        bad.members.push((
+            String::from("c"),
+            ChaCha8Rand::new(SAMPLE_SEED).clone_state(),
+        ));
+        bad.members[0].1.bytes_consumed = 993;
+        assert!(rngs.restore(&bad).is_err());
+        assert!(rngs.names().eq(["a", "b"]));
+
+        rngs.restore(&save).unwrap();
+        assert!(rngs.names().eq(["a"]));
+        assert_eq!(rngs.get("a").read_u32(), a);
+        assert_eq!(rngs.get("b").read_u32(), b);
+        assert_eq!(rngs.snapshot().members.len(), 2);
+    }
+}
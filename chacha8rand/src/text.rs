@@ -0,0 +1,83 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::ChaCha8Rand;
+
+/// Random characters and strings, e.g., for identifiers, tokens, and test fixtures.
+impl ChaCha8Rand {
+    /// Return a uniformly random Unicode scalar value, i.e., any `char` at all.
+    ///
+    /// There are 1,112,064 scalar values: everything from `U+0000` to `U+10FFFF` except for the
+    /// 2048 surrogate code points `U+D800..=U+DFFF`. This picks `x` with
+    /// [`ChaCha8Rand::read_u32_below`]`(1_112_064)` (so it usually consumes four bytes), and skips
+    /// over the surrogates by adding `0x800` if `x ≥ 0xD800`. Every `char` is equally likely, so
+    /// almost all of them will be from the supplementary planes and unassigned. That's great for
+    /// fuzzing, but if you want text that looks more realistic, use
+    /// [`ChaCha8Rand::read_char_from`] with a charset of your choice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let c = rng.read_char();
+    /// assert!(c.len_utf8() <= 4);
+    /// ```
+    pub fn read_char(&mut self) -> char {
+        const SURROGATES: u32 = 0xE000 - 0xD800;
+        const SCALAR_VALUES: u32 = char::MAX as u32 + 1 - SURROGATES;
+        let mut x = self.read_u32_below(SCALAR_VALUES);
+        if x >= 0xD800 {
+            x += SURROGATES;
+        }
+        char::from_u32(x).expect("surrogates were skipped")
+    }
+
+    /// Pick a uniformly random character from `charset`.
+    ///
+    /// This is the same as [`ChaCha8Rand::choose`] on the slice, so the index is picked without
+    /// bias and duplicates in `charset` make a character proportionally more likely.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `charset` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let grade = rng.read_char_from(&['A', 'B', 'C', 'D', 'F']);
+    /// # let _ = grade;
+    /// ```
+    pub fn read_char_from(&mut self, charset: &[char]) -> char {
+        *self
+            .choose(charset)
+            .expect("can't pick a character from an empty charset")
+    }
+
+    /// Build a string of `len` characters, each picked independently with
+    /// [`ChaCha8Rand::read_char_from`].
+    ///
+    /// `len` counts characters, not bytes: the string's length in bytes may be larger if
+    /// `charset` contains non-ASCII characters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `charset` is empty and `len` isn't zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// // Crockford's base32 alphabet avoids easily confused characters like I, L, O, and U.
+    /// let alphabet: Vec<char> = "0123456789ABCDEFGHJKMNPQRSTVWXYZ".chars().collect();
+    /// let code = rng.read_string_from(&alphabet, 8);
+    /// assert_eq!(code.len(), 8);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn read_string_from(&mut self, charset: &[char], len: usize) -> String {
+        (0..len).map(|_| self.read_char_from(charset)).collect()
+    }
+}
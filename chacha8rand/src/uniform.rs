@@ -0,0 +1,229 @@
+use core::ops::{Range, RangeInclusive};
+
+use crate::ChaCha8Rand;
+
+impl ChaCha8Rand {
+    /// Consume four bytes (occasionally more) and return a uniformly distributed `u32` in `0..n`.
+    ///
+    /// Unlike `read_u32() % n`, this has no bias towards small values. It uses Lemire's "nearly
+    /// divisionless" method ("Fast Random Integer Generation in an Interval", 2019), which is part
+    /// of the reproducibility contract:
+    ///
+    /// 1. Read `x` as if by [`ChaCha8Rand::read_u32`] and compute the 64-bit product `m = x * n`.
+    /// 2. If the low 32 bits of `m` are less than `2^32 mod n`, this `x` would make some results
+    ///    more likely than others, so go back to step 1.
+    /// 3. Otherwise, the result is the high 32 bits of `m`.
+    ///
+    /// Step 2 rejects fewer than `n / 2^32` of all candidates, so for small `n` it's very rare
+    /// that more than four bytes are consumed. The check is arranged so the common case doesn't
+    /// need a division.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let die_roll = rng.read_u32_below(6) + 1;
+    /// assert!((1..=6).contains(&die_roll));
+    /// ```
+    #[inline]
+    pub fn read_u32_below(&mut self, n: u32) -> u32 {
+        assert!(n != 0, "can't sample from an empty range");
+        let mut m = u64::from(self.read_u32()) * u64::from(n);
+        if (m as u32) < n {
+            let threshold = n.wrapping_neg() % n;
+            while (m as u32) < threshold {
+                m = u64::from(self.read_u32()) * u64::from(n);
+            }
+        }
+        (m >> 32) as u32
+    }
+
+    /// Consume eight bytes (occasionally more) and return a uniformly distributed `u64` in `0..n`.
+    ///
+    /// This is the same algorithm as [`ChaCha8Rand::read_u32_below`], but with `x` read as if by
+    /// [`ChaCha8Rand::read_u64`], a 128-bit product, and `2^64 mod n` as threshold.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    #[inline]
+    pub fn read_u64_below(&mut self, n: u64) -> u64 {
+        assert!(n != 0, "can't sample from an empty range");
+        let mut m = u128::from(self.read_u64()) * u128::from(n);
+        if (m as u64) < n {
+            let threshold = n.wrapping_neg() % n;
+            while (m as u64) < threshold {
+                m = u128::from(self.read_u64()) * u128::from(n);
+            }
+        }
+        (m >> 64) as u64
+    }
+
+    /// Consume eight bytes and return `true` with probability `p`.
+    ///
+    /// The result is exactly `self.read_f64() < p`, so `p = 0.0` is never true, `p = 1.0` is always
+    /// true, and in general the probability is `p` rounded up to a multiple of 2<sup>-53</sup>.
+    /// For a fair coin flip that only needs one byte, use `rng.sample::<bool>()`. For exact
+    /// rational probabilities, use [`ChaCha8Rand::read_ratio`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not between 0 and 1 (inclusive), e.g., if it's NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// if rng.read_bool(0.15) {
+    ///     println!("The goblin drops a rusty key.");
+    /// }
+    /// ```
+    #[inline]
+    pub fn read_bool(&mut self, p: f64) -> bool {
+        assert!((0.0..=1.0).contains(&p), "probability must be in [0, 1]");
+        self.read_f64() < p
+    }
+
+    /// Return `true` with probability exactly `numerator / denominator`.
+    ///
+    /// This uses only integer arithmetic: the result is
+    /// `self.read_u32_below(denominator) < numerator` (see [`ChaCha8Rand::read_u32_below`]), so it
+    /// usually consumes four bytes. No rounding is involved, so e.g. `read_ratio(1, 3)` is true
+    /// with probability exactly 1/3, which isn't representable as an `f64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denominator` is zero or `numerator` is larger than `denominator`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let hit = rng.read_ratio(2, 3);
+    /// # let _ = hit;
+    /// ```
+    #[inline]
+    pub fn read_ratio(&mut self, numerator: u32, denominator: u32) -> bool {
+        assert!(
+            denominator != 0 && numerator <= denominator,
+            "ratio must be between 0 and 1 with a non-zero denominator"
+        );
+        self.read_u32_below(denominator) < numerator
+    }
+
+    /// Sample a uniformly distributed integer from a range like `a..b` or `a..=b`.
+    ///
+    /// This works with both exclusive and inclusive ranges of all primitive integer types up to
+    /// 64 bits, signed or unsigned, plus `usize` and `isize`. See [`SampleRange`] for the exact
+    /// algorithm, which is stable across releases and platforms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let temperature = rng.read_range(-40i32..=50);
+    /// let index = rng.read_range(0..10usize);
+    /// assert!((-40..=50).contains(&temperature) && index < 10);
+    /// ```
+    #[inline]
+    pub fn read_range<T, R: SampleRange<T>>(&mut self, range: R) -> T {
+        range.sample_range(self)
+    }
+}
+
+/// Ranges that can be sampled uniformly with [`ChaCha8Rand::read_range`].
+///
+/// This is implemented for `Range<T>` and `RangeInclusive<T>` of all primitive integer types up to
+/// 64 bits, as well as `usize` and `isize`. Every range is reduced to sampling an offset from the
+/// start of the range, which is reproducible across releases and platforms:
+///
+/// * For types up to 32 bits wide, the offset is [`ChaCha8Rand::read_u32_below`] of the number of
+///   values in the range. For 64-bit types, it's [`ChaCha8Rand::read_u64_below`].
+/// * `usize` and `isize` are always treated like `u64` and `i64` (respectively), so the same range
+///   gives the same results on 32-bit and 64-bit targets.
+/// * Inclusive ranges that cover *all* values of a 32-bit or 64-bit type have more values than
+///   fit into the bound. For those, the value is simply [`ChaCha8Rand::read_u32`] or
+///   [`ChaCha8Rand::read_u64`] (cast to the signed type if necessary).
+pub trait SampleRange<T> {
+    /// Sample a value from the range, panicking if it's empty.
+    fn sample_range(self, rng: &mut ChaCha8Rand) -> T;
+}
+
+// Types up to 32 bits wide sample a `u32` and wider types sample a `u64`. The span of the range is
+// computed in the unsigned type of the same width, so it can't overflow even for signed types
+// (`$ty::MIN..$ty::MAX` has a span of `$unsigned::MAX`). Only the inclusive range covering all
+// values of a 32 or 64 bit type has a span that doesn't fit, which we handle separately.
+macro_rules! impl_sample_range {
+    ($($ty:ty => $unsigned:ty, $wide:ty, $below:ident, $read:ident;)*) => {$(
+        impl SampleRange<$ty> for Range<$ty> {
+            #[inline]
+            fn sample_range(self, rng: &mut ChaCha8Rand) -> $ty {
+                assert!(self.start < self.end, "can't sample from an empty range");
+                let span = self.end.wrapping_sub(self.start) as $unsigned as $wide;
+                self.start.wrapping_add(rng.$below(span) as $ty)
+            }
+        }
+
+        impl SampleRange<$ty> for RangeInclusive<$ty> {
+            #[inline]
+            fn sample_range(self, rng: &mut ChaCha8Rand) -> $ty {
+                let (start, end) = self.into_inner();
+                assert!(start <= end, "can't sample from an empty range");
+                let span = (end.wrapping_sub(start) as $unsigned as $wide).wrapping_add(1);
+                if span == 0 {
+                    return rng.$read() as $ty;
+                }
+                start.wrapping_add(rng.$below(span) as $ty)
+            }
+        }
+    )*};
+}
+
+impl_sample_range! {
+    u8 => u8, u32, read_u32_below, read_u32;
+    u16 => u16, u32, read_u32_below, read_u32;
+    u32 => u32, u32, read_u32_below, read_u32;
+    u64 => u64, u64, read_u64_below, read_u64;
+    i8 => u8, u32, read_u32_below, read_u32;
+    i16 => u16, u32, read_u32_below, read_u32;
+    i32 => u32, u32, read_u32_below, read_u32;
+    i64 => u64, u64, read_u64_below, read_u64;
+}
+
+// Pointer-sized integers go through the 64-bit implementation regardless of the target, so results
+// don't depend on the pointer width.
+macro_rules! impl_sample_range_via {
+    ($($ty:ty => $via:ty;)*) => {$(
+        impl SampleRange<$ty> for Range<$ty> {
+            #[inline]
+            fn sample_range(self, rng: &mut ChaCha8Rand) -> $ty {
+                (self.start as $via..self.end as $via).sample_range(rng) as $ty
+            }
+        }
+
+        impl SampleRange<$ty> for RangeInclusive<$ty> {
+            #[inline]
+            fn sample_range(self, rng: &mut ChaCha8Rand) -> $ty {
+                let (start, end) = self.into_inner();
+                (start as $via..=end as $via).sample_range(rng) as $ty
+            }
+        }
+    )*};
+}
+
+impl_sample_range_via! {
+    usize => u64;
+    isize => i64;
+}
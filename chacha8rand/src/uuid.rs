@@ -0,0 +1,56 @@
+use uuid::{Builder, Uuid};
+
+use crate::ChaCha8Rand;
+
+/// Generating UUIDs. Requires crate feature `uuid`.
+///
+/// All of these methods consume a fixed number of bytes from the stream and set the version and
+/// variant bits as required by [RFC 9562]. The remaining bits are taken verbatim from the stream,
+/// so the same seed always produces the same UUIDs.
+///
+/// Keep in mind that the collision resistance of random UUIDs relies on *every* generator having
+/// a unique, high-entropy seed. Two instances with the same seed (or two clones of one instance)
+/// will happily produce the same UUIDs. See [`ChaCha8Rand::read_bytes`] for more discussion.
+///
+/// [RFC 9562]: https://www.rfc-editor.org/rfc/rfc9562
+impl ChaCha8Rand {
+    /// Consume 16 bytes and turn them into a random (version 4) UUID.
+    ///
+    /// This is equivalent to reading 16 bytes and passing them to `Builder::from_random_bytes`: 122
+    /// of the 128 bits come from the stream, the other six are the version and variant fields.
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_uuid_v4(&mut self) -> Uuid {
+        let mut bytes = [0; 16];
+        self.read_bytes(&mut bytes);
+        Builder::from_random_bytes(bytes).into_uuid()
+    }
+
+    /// Consume 10 bytes and combine them with `timestamp_ms` into a time-ordered (version 7) UUID.
+    ///
+    /// The timestamp is the number of milliseconds since the Unix epoch, which makes up the first
+    /// 48 bits of the UUID. Higher bits of `timestamp_ms` are ignored, which won't matter until the
+    /// year 10889. The generator never looks at the clock itself, so you're free to pass in a
+    /// timestamp from a simulated clock to keep everything reproducible.
+    ///
+    /// The rest of the UUID is filled with 74 bits from the stream (`rand_a` and `rand_b` in RFC
+    /// 9562 parlance) around the version and variant fields. This method does *not* use any of the
+    /// optional counter schemes from section 6.2 of the RFC, so UUIDs created within the same
+    /// millisecond are ordered randomly among each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let earlier = rng.read_uuid_v7(1_700_000_000_000);
+    /// let later = rng.read_uuid_v7(1_700_000_000_001);
+    /// assert!(earlier < later);
+    /// assert_eq!(later.get_version_num(), 7);
+    /// ```
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn read_uuid_v7(&mut self, timestamp_ms: u64) -> Uuid {
+        let mut random_bytes = [0; 10];
+        self.read_bytes(&mut random_bytes);
+        Builder::from_unix_timestamp_millis(timestamp_ms, &random_bytes).into_uuid()
+    }
+}
@@ -0,0 +1,65 @@
+use core::mem::{size_of, size_of_val, MaybeUninit};
+
+use zerocopy::FromBytes;
+
+use crate::ChaCha8Rand;
+
+/// Creating values of `zerocopy` types. Requires crate feature `zerocopy`.
+///
+/// These methods fill the entire memory of the values with bytes from the stream, in memory order,
+/// so they have the same caveat as [`ChaCha8Rand::fill_pod`]: multi-byte fields are read in the
+/// target's native byte order, so their values differ between little-endian and big-endian
+/// targets. Padding bytes (which [`FromBytes`] types may have) consume bytes from the stream too.
+impl ChaCha8Rand {
+    /// Consume `size_of::<T>()` bytes and reinterpret them as a `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use chacha8rand::ChaCha8Rand;
+    /// use zerocopy::{FromBytes, Immutable, KnownLayout};
+    ///
+    /// #[derive(FromBytes, KnownLayout, Immutable)]
+    /// #[repr(C)]
+    /// struct UdpHeader {
+    ///     src_port: [u8; 2],
+    ///     dst_port: [u8; 2],
+    ///     length: [u8; 2],
+    ///     checksum: [u8; 2],
+    /// }
+    ///
+    /// let mut rng = ChaCha8Rand::new(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ123456");
+    /// let header: UdpHeader = rng.read_from_bytes();
+    /// assert_eq!(header.src_port, [0xa5, 0x16]);
+    /// ```
+    pub fn read_from_bytes<T: FromBytes>(&mut self) -> T {
+        let mut value = MaybeUninit::<T>::uninit();
+        // SAFETY: `MaybeUninit<T>` has the same size as `T`, and any bytes are valid for
+        // `MaybeUninit<u8>`.
+        let bytes = unsafe {
+            core::slice::from_raw_parts_mut(
+                value.as_mut_ptr().cast::<MaybeUninit<u8>>(),
+                size_of::<T>(),
+            )
+        };
+        self.read_bytes_uninit(bytes);
+        // SAFETY: all bytes are initialized now, and `T: FromBytes` means every initialized bit
+        // pattern is a valid `T`.
+        unsafe { value.assume_init() }
+    }
+
+    /// Overwrite every byte of `dest` (including padding) with bytes from the stream.
+    ///
+    /// This is the same as replacing every element with [`ChaCha8Rand::read_from_bytes`], but
+    /// doesn't have to go through the elements one by one.
+    pub fn fill_from_bytes<T: FromBytes>(&mut self, dest: &mut [T]) {
+        let len = size_of_val(dest);
+        // SAFETY: the slice covers exactly the memory of `dest`, which we have exclusive access
+        // to. `read_bytes_uninit` only writes initialized bytes, and any bit pattern is a valid `T`
+        // because of `T: FromBytes`, so `dest` remains valid. Writing to padding bytes is fine.
+        let bytes = unsafe {
+            core::slice::from_raw_parts_mut(dest.as_mut_ptr().cast::<MaybeUninit<u8>>(), len)
+        };
+        self.read_bytes_uninit(bytes);
+    }
+}
@@ -0,0 +1,67 @@
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{ChaCha8State, ChaChaRand, Seed, BUF_OUTPUT_LEN};
+
+/// Wiping key material. Requires crate feature `zeroize`.
+///
+/// This overwrites the current seed, the seed the generator was created with (see
+/// [`ChaCha8Rand::initial_seed`]), and the whole buffer, which includes the key for the next
+/// iteration. Afterwards, the generator is in the same state as `ChaCha8Rand::new_lazy(&[0; 32])`,
+/// so it's still safe to use, but its output is no longer related to the original seed.
+///
+/// With this feature, generators are also wiped when they're dropped. That can't cover copies of
+/// the generator that the compiler left behind when it was moved, or generators that are never
+/// dropped, such as the last one in a [`Storage`] placed in a `static`. To keep a generator at one
+/// address for its whole life, create it with [`ChaCha8Rand::new_in`] or `new_boxed` and zeroize it
+/// explicitly when needed.
+///
+/// [`ChaCha8Rand::initial_seed`]: crate::ChaCha8Rand::initial_seed
+/// [`ChaCha8Rand::new_in`]: crate::ChaCha8Rand::new_in
+/// [`Storage`]: crate::Storage
+impl<const ROUNDS: usize> Zeroize for ChaChaRand<ROUNDS> {
+    fn zeroize(&mut self) {
+        count!(self, reseed(self.position()));
+        digest!(self.digest_flush());
+        self.seed.zeroize();
+        self.origin_seed.zeroize();
+        self.buf.zeroize();
+        // This is the state of a lazily created generator (see `ChaCha8Rand::is_unfilled`), so
+        // the next read refills the buffer from the all-zero key in `buf` instead of reading zeros.
+        self.bytes_consumed = BUF_OUTPUT_LEN;
+        digest!(self.digest_resync());
+        self.iteration = u64::MAX;
+        self.origin_iteration = 0;
+    }
+}
+
+/// Requires crate feature `zeroize`.
+impl<const ROUNDS: usize> ZeroizeOnDrop for ChaChaRand<ROUNDS> {}
+
+/// Requires crate feature `zeroize`.
+impl<const ROUNDS: usize> Drop for ChaChaRand<ROUNDS> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// Wiping the seed and position. Requires crate feature `zeroize`.
+///
+/// Since snapshots are `Copy`, they can't be wiped automatically when dropped. Call `zeroize`
+/// explicitly on every copy you want to wipe.
+impl Zeroize for ChaCha8State {
+    fn zeroize(&mut self) {
+        self.seed.zeroize();
+        self.bytes_consumed.zeroize();
+        self.iteration.zeroize();
+    }
+}
+
+/// Wiping the seed. Requires crate feature `zeroize`.
+///
+/// Since seeds are `Copy`, they can't be wiped automatically when dropped. Call `zeroize`
+/// explicitly on every copy you want to wipe.
+impl Zeroize for Seed {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
@@ -20,8 +20,18 @@ const WASM_RUNNER_ENV: &str = "CARGO_TARGET_WASM32_WASIP1_RUNNER";
 fn test_matrix() -> xshell::Result<()> {
     let sh = Shell::new()?;
 
-    // Any combination of features should work and run tests.
-    cmd!(sh, "cargo hack test -p chacha8rand --feature-powerset").run()?;
+    // Any combination of features should work and run tests. The exception is the portable SIMD
+    // backend, which needs nightly, so it's tested separately.
+    cmd!(
+        sh,
+        "cargo hack test -p chacha8rand -p chacha8rand-core --feature-powerset --exclude-features unstable_portable_simd"
+    )
+    .run()?;
+    cmd!(
+        sh,
+        "cargo +nightly test -p chacha8rand -p chacha8rand-core --features unstable_portable_simd"
+    )
+    .run()?;
 
     let targets = [
         "aarch64-unknown-linux-gnu", // for neon
@@ -35,9 +45,11 @@ fn test_matrix() -> xshell::Result<()> {
         // are picked up within the container. This is a problem if those contain
         // -Clink-arg=-fuse-lld, which doesn't work inside the container. Setting the RUSTFLAGS
         // variable overrides the flags from the config files.
+        // This uses nightly because of `unstable_portable_simd`, which is most interesting on
+        // targets that don't have a hand-written backend (such as s390x) anyway.
         cmd!(
             sh,
-            "cross test --target {target} --all-targets --all-features"
+            "cross +nightly test --target {target} --all-targets --all-features"
         )
         .env("RUSTFLAGS", "")
         .run()?;